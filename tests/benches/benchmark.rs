@@ -1,112 +1,108 @@
 use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::measurement::{Measurement, ValueFormatter, WallTime};
+use criterion::{BatchSize, BenchmarkGroup, BenchmarkId, Throughput};
 
 
-use std::collections::BTreeMap; 
-use old_rand;
+use std::collections::{BTreeMap, BTreeSet};
 use multisig::{Committee, KeypairShare, Signer};
+use thesis::aggregation::{self, AggregationPolicy};
+use thesis::asserts;
+use thesis::faults::{self, BitFlipInjector};
+use thesis::fixtures::{FixtureCache, Keygen};
 use thesis::frost;
-use std::mem;
+use thesis::gossip::MembershipGossip;
+use thesis::generate;
+use thesis::hash::{self, HashFunction};
+use thesis::latency::{self, LatencyMatrix};
+use thesis::limits;
+use thesis::netsim::{self, NetworkModel, SignerFaults, SimConfig, SimProtocol};
+use thesis::nonce_pool::NoncePool;
+use thesis::params::Params;
+use thesis::pipeline::BatchCoordinator;
+use thesis::policy::{self, Policy};
+use thesis::preflight;
+use thesis::rng::{RngProvider, SeededRngProvider, ThreadRngProvider};
+use thesis::scheme::{self, ThresholdScheme};
+use thesis::speculative;
+use thesis::Settings;
+use thesis::transcript;
+use thesis::tweak::{self, MessageTweak, PayloadTweak};
+use std::io::Read;
+use old_rand::Rng;
 
 const SYSTEM_SIZE: usize = 30;
 const THRESHOLD: usize = (2 * SYSTEM_SIZE + 1 + 2) / 3;
 
 const MESSAGE: &[u8] = b"HELLO WORLD"; 
 
+fn params() -> Params {
+    Params::new(SYSTEM_SIZE, THRESHOLD).expect("benchmark parameters are valid")
+}
 
+// Both schemes sign the same transcript-derived bytes, so their numbers are
+// measured over identical inputs.
+fn signed_message() -> Vec<u8> {
+    transcript::payload_transcript(HashFunction::default(), MESSAGE)
+}
 
-fn multisig_bench(c: &mut Criterion) {
-    let mut group = c.benchmark_group("multisig");
-    group.sampling_mode(criterion::SamplingMode::Flat);
-
-    // --- 1. Benchmark: initialisation (Key Generation and Committee Creation) ---
-    // This measures the time to generate all participant key shares and build the committee.
-    group.bench_function("multisig_initialisation", |b| {
-        b.iter(|| {
-            // Key generation for all participants
-            let participants = (0..SYSTEM_SIZE).map(|_| KeypairShare::default()).collect::<Vec<_>>();
-            // Committee creation and adding all verifying shares
-            let mut committee = Committee::new();
-            for share in participants.iter().map(|keypair| keypair.verifying_share.clone()) {
-                committee.add_key(share.clone());
-            }
-        });
-    });
-
-    // --- Setup for subsequent multisig benchmarks ---
-    // Generate participants and committee once for use across signing and verification benchmarks.
-    let participants: Vec<KeypairShare> = (0..SYSTEM_SIZE).map(|_| KeypairShare::default()).collect();
-    let mut committee_builder = Committee::new();
-    for share in participants.iter().map(|keypair| keypair.verifying_share.clone()) {
-        committee_builder.add_key(share.clone());
-    }
-    let committee = committee_builder;
-
-    // --- 2. Benchmark: Signing (Single Signer) ---
-    // This measures the time for one individual signer to create their signature share.
-    let single_signer = &participants[0];
-    group.bench_function("multisig_signing", |b| {
-        b.iter(|| {
-            single_signer.sign(MESSAGE);
-        });
-    });
-
-    // --- Setup for Verification Benchmark ---
-    // Generate a certificate once for the verification benchmark.
-    let certificate = participants
-        .iter()
-        .take(THRESHOLD)
-        .map(|keypair| keypair.sign(MESSAGE))
-        .collect::<Vec<_>>();
-
-    // --- 4. Benchmark: Verifying the final signature ---
-    // This measures the time to verify the collected certificate using the committee's public keys.
-    group.bench_function("multisig_verify", |b| {
-        b.iter(|| {
-            committee.verify(MESSAGE, &certificate, THRESHOLD);
-        });
-    });
+fn frost_settings() -> frost::FrostSettings {
+    frost::FrostSettings::new(params(), HashFunction::default()).expect("benchmark parameters are valid for FROST")
+}
 
-    let mut total_multisig_cert_size = 0;
-    if !certificate.is_empty() {
-        for sig_share in &certificate {
-            total_multisig_cert_size += mem::size_of_val(sig_share);
-        }
-        println!("Multisig: Total size of certificate ({} shares): {} bytes", certificate.len(), total_multisig_cert_size);
-    } else {
-         println!("Multisig: Certificate is empty, cannot determine size.");
-    }
+// Dealer keys for `settings`, read from target/fixtures when a previous run
+// cached them. THESIS_REGENERATE_FIXTURES=1 forces fresh ones.
+fn cached_package(settings: &frost::FrostSettings) -> frost::FrostPackage {
+    let regenerate = std::env::var_os("THESIS_REGENERATE_FIXTURES").is_some_and(|value| value != "0");
+    let cache = FixtureCache::new("target/fixtures").with_regenerate(regenerate);
+    let (package, _) = cache.load(Keygen::Dealer, settings, 0).expect("fixture could not be loaded or generated");
+    package
+}
 
 
-    group.finish();
-}
 
 fn frost_bench(c: &mut Criterion) {
     let mut group = c.benchmark_group("frost");
     group.sampling_mode(criterion::SamplingMode::Flat);
 
-    // A single FROST configuration is used for all benchmarks.
-    let settings = frost::FrostSettings {
-        system_size: SYSTEM_SIZE as u16,
-        threshold: THRESHOLD as u16,
-    };
+    // A single FROST configuration is used for all benchmarks. The raw
+    // MESSAGE keeps these numbers comparable with the committed baselines; the
+    // `_transcript` variants sign what the other groups sign.
+    let settings = frost_settings();
     let message = MESSAGE;
-    let mut rng = old_rand::thread_rng();
+    let transcript = signed_message();
+    let mut rng_provider = ThreadRngProvider;
+    let mut rng = rng_provider.rng();
 
-    // 1. Benchmark: FROST Setup (Distributed Key Generation (DKG))
+    // 1. Benchmark: FROST Setup (trusted dealer key generation)
     group.bench_function("frost_initialisation", |b| {
         b.iter(|| {
-            let mut iter_rng = old_rand::thread_rng();
+            let mut iter_rng = rng_provider.rng();
             frost::setup(&settings, &mut iter_rng).unwrap();
         });
     });
 
+    // 1b. Benchmark: FROST Setup (Distributed Key Generation (DKG)), all participants simulated
+    group.bench_function("frost_dkg_initialisation", |b| {
+        b.iter(|| {
+            let mut iter_rng = rng_provider.rng();
+            frost::setup_dkg(&settings, &mut iter_rng).unwrap();
+        });
+    });
+
     // Create a package once to be used as input for the next benchmark.
-    let package = frost::setup(&settings, &mut rng).unwrap();
+    let package = cached_package(&settings);
+
+    // Benchmark: every participant validating its share against the dealer's VSS commitments
+    group.bench_function("frost_share_validation", |b| {
+        b.iter(|| {
+            frost::validate_shares(&package).unwrap();
+        });
+    });
 
     // // 2. Benchmark: FROST Commitments (Round 1)
     // group.bench_function("commitments", |b| {
     //     b.iter(|| {
-    //         let mut iter_rng = old_rand::thread_rng();
+    //         let mut iter_rng = rng_provider.rng();
     //         frost::vote_commitments(&settings, &package, &mut iter_rng).unwrap();
     //     });
     // });
@@ -121,11 +117,19 @@ fn frost_bench(c: &mut Criterion) {
     let signing_package = frost_ed25519::SigningPackage::new(round1.commitments().clone(), message);
 
     // 2. Benchmark: FROST Sign (Round 2 for a single participant)
+    // From here on Criterion reports signatures per second next to the time.
+    group.throughput(Throughput::Elements(1));
     group.bench_function("frost_signing", |b| {
         b.iter(|| {
             frost_ed25519::round2::sign(&signing_package, nonces, key_package).unwrap()
         });
     });
+    let transcript_package = frost_ed25519::SigningPackage::new(round1.commitments().clone(), &transcript);
+    group.bench_function("frost_signing_transcript", |b| {
+        b.iter(|| {
+            frost_ed25519::round2::sign(&transcript_package, nonces, key_package).unwrap()
+        });
+    });
 
     // // 3. Benchmark: FROST Sign (Round 2 + Aggregation)
     // group.bench_function("sign", |b| {
@@ -159,22 +163,792 @@ fn frost_bench(c: &mut Criterion) {
         &signature_shares,
         package.public(),
     ).unwrap();
-    println!("FROST: Total size of signature: {} bytes", mem::size_of_val(&group_signature));
 
     // 4. Benchmark: FROST Verification (of the aggregated signature)
     group.bench_function("frost_verify", |b| {
         b.iter(|| {
-            assert!(package.public().verifying_key().verify(message, &group_signature).is_ok());
+            asserts::signature_verifies(package.public(), message, &group_signature).unwrap();
+        });
+    });
+    let transcript_round1 = frost::vote_commitments(&settings, &package, &mut rng).unwrap();
+    let transcript_round2 = frost::sign_message(&settings, &package, &transcript_round1, &transcript).unwrap();
+    let transcript_signature = frost_ed25519::aggregate(
+        transcript_round2.signing_package(),
+        transcript_round2.signature_shares(),
+        package.public(),
+    ).unwrap();
+    group.bench_function("frost_verify_transcript", |b| {
+        b.iter(|| {
+            asserts::signature_verifies(package.public(), &transcript, &transcript_signature).unwrap();
+        });
+    });
+
+    // 5. Benchmark: online signing latency (round 1 + round 2) with and without
+    // nonces precomputed into a pool ahead of the session
+    let signers = params().signer_identifiers();
+    group.bench_function("frost_online_signing", |b| {
+        b.iter(|| {
+            let round1 = frost::vote_commitments(&settings, &package, &mut rng).unwrap();
+            frost::sign_message(&settings, &package, &round1, message).unwrap();
+        });
+    });
+    group.bench_function("frost_online_signing_preprocessed", |b| {
+        b.iter_batched(
+            || {
+                let mut pool = NoncePool::new();
+                pool.precompute(&package, &signers, 1, &mut rng).unwrap();
+                pool
+            },
+            |mut pool| {
+                let round1 = pool.take_round1(&signers).unwrap();
+                frost::sign_message(&settings, &package, &round1, message).unwrap();
+            },
+            BatchSize::SmallInput,
+        );
+    });
+
+    group.finish();
+}
+
+fn aggregation_policy_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("aggregation_policy");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+
+    let settings = frost_settings();
+    let mut rng = ThreadRngProvider.rng();
+    let package = frost::setup(&settings, &mut rng).unwrap();
+    let round1 = frost::vote_commitments(&settings, &package, &mut rng).unwrap();
+    let round2 = frost::sign_message(&settings, &package, &round1, MESSAGE).unwrap();
+    group.throughput(Throughput::Elements(1));
+
+    // Each policy under increasing rates of invalid shares.
+    for bad_share_rate in [0.0, 0.05, 0.2] {
+        let shares = aggregation::corrupt_shares(round2.signature_shares(), bad_share_rate, &mut rng);
+        for policy in AggregationPolicy::ALL {
+            group.bench_function(format!("{:?}_{}", policy, bad_share_rate), |b| {
+                b.iter(|| {
+                    aggregation::aggregate_with_policy(policy, round2.signing_package(), &shares, package.public())
+                        .unwrap()
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+// The same setup/sign/aggregate pipeline instantiated for one FROST ciphersuite.
+fn ciphersuite_bench<C: frost_core::Ciphersuite>(group: &mut BenchmarkGroup<WallTime>, name: &str) {
+    let settings = frost_settings();
+    let message = signed_message();
+    let mut rng = ThreadRngProvider.rng();
+
+    group.bench_function(format!("{}_initialisation", name), |b| {
+        b.iter(|| frost::generic::setup::<C, _>(&settings, &mut rng).unwrap());
+    });
+
+    let package = frost::generic::setup::<C, _>(&settings, &mut rng).unwrap();
+    let round1 = frost::generic::vote_commitments(&settings, &package, &mut rng).unwrap();
+    group.bench_function(format!("{}_signing", name), |b| {
+        b.iter(|| frost::generic::sign_message(&settings, &package, &round1, &message).unwrap());
+    });
+
+    let round2 = frost::generic::sign_message(&settings, &package, &round1, &message).unwrap();
+    group.bench_function(format!("{}_aggregate_verify", name), |b| {
+        b.iter(|| frost::generic::aggregate_verify(&settings, &package, &round1, &round2, &message).unwrap());
+    });
+}
+
+fn ciphersuites_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ciphersuites");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+
+    ciphersuite_bench::<frost_ed25519::Ed25519Sha512>(&mut group, "ed25519");
+    #[cfg(feature = "secp256k1")]
+    ciphersuite_bench::<frost_secp256k1::Secp256K1Sha256>(&mut group, "secp256k1");
+    #[cfg(feature = "secp256k1")]
+    {
+        let settings = frost_settings();
+        let mut rng = ThreadRngProvider.rng();
+        let chain = thesis::bridge::run_bridge(&settings, &mut rng).unwrap();
+        println!("Bridge: ed25519 -> secp256k1 -> ed25519 chain of {} certificates verified", chain.len());
+        group.bench_function("bridge_certify_and_verify", |b| {
+            b.iter(|| thesis::bridge::run_bridge(&settings, &mut rng).unwrap())
+        });
+    }
+    #[cfg(feature = "ristretto255")]
+    ciphersuite_bench::<frost_ristretto255::Ristretto255Sha512>(&mut group, "ristretto255");
+    #[cfg(feature = "p256")]
+    ciphersuite_bench::<frost_p256::P256Sha256>(&mut group, "p256");
+
+    group.finish();
+}
+
+// Written once against `ThresholdScheme` and instantiated for every scheme.
+fn scheme_bench<S: ThresholdScheme>(c: &mut Criterion, settings: &S::Settings) {
+    let mut group = c.benchmark_group(S::NAME);
+    group.sampling_mode(criterion::SamplingMode::Flat);
+    let message = signed_message();
+    let mut rng = ThreadRngProvider.rng();
+
+    group.bench_function("setup", |b| b.iter(|| S::setup(settings, &mut rng).unwrap()));
+
+    let keys = S::setup(settings, &mut rng).unwrap();
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("round1", |b| b.iter(|| S::sign_round1(settings, &keys, &mut rng).unwrap()));
+
+    let round1 = S::sign_round1(settings, &keys, &mut rng).unwrap();
+    group.bench_function("round2", |b| {
+        b.iter(|| S::sign_round2(settings, &keys, &round1, &message).unwrap())
+    });
+
+    let round2 = S::sign_round2(settings, &keys, &round1, &message).unwrap();
+    group.bench_function("aggregate", |b| b.iter(|| S::aggregate(settings, &keys, &round2).unwrap()));
+
+    let signature = S::aggregate(settings, &keys, &round2).unwrap();
+    group.bench_function("verify", |b| {
+        b.iter(|| S::verify(settings, &keys, &message, &signature).unwrap())
+    });
+
+    group.finish();
+}
+
+// Signing artifacts of increasing size: hashing the whole buffer in memory
+// against streaming it through `reader_digest` and signing the digest. The
+// signing part is the same fixed-size session either way, so the hashing
+// dominates as the artifact grows.
+fn large_message_bench<S: ThresholdScheme>(c: &mut Criterion, settings: &S::Settings) {
+    let mut group = c.benchmark_group(format!("{}_large_message", S::NAME));
+    group.sampling_mode(criterion::SamplingMode::Flat);
+    group.sample_size(10);
+    let mut rng = ThreadRngProvider.rng();
+    let keys = S::setup(settings, &mut rng).unwrap();
+    let hash = settings.hash_function();
+
+    for size in [1usize << 20, 1 << 26] {
+        let artifact = vec![0xabu8; size];
+        let (digest, length) = transcript::reader_digest(hash, artifact.as_slice()).unwrap();
+        assert_eq!(length, size as u64);
+        let signature = scheme::sign_digest::<S, _>(settings, &keys, &digest, &mut rng).unwrap();
+        scheme::verify_digest::<S>(settings, &keys, &digest, &signature).unwrap();
+
+        group.throughput(Throughput::Bytes(size as u64));
+        let label = format!("{}MiB", size >> 20);
+        group.bench_function(format!("in_memory_{label}"), |b| {
+            b.iter(|| {
+                let message = transcript::payload_transcript(hash, &artifact);
+                scheme::sign_with_keys::<S, _>(settings, &keys, &message, &mut rng).unwrap()
+            })
+        });
+        group.bench_function(format!("streamed_{label}"), |b| {
+            b.iter(|| {
+                let source = std::io::repeat(0xab).take(size as u64);
+                let (digest, _) = transcript::reader_digest(hash, source).unwrap();
+                scheme::sign_digest::<S, _>(settings, &keys, &digest, &mut rng).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+// Committees near the u16 identifier limit with t = n - 1. Setup runs once
+// outside the measurement and only 10 samples are taken; configurations whose
+// memory estimate does not fit are reported and skipped. Only n = 1000 runs by
+// default; THESIS_MAX_COMMITTEE=1 adds n = 10000 and n = 65535.
+fn max_committee_bench(c: &mut Criterion) {
+    let mut sizes = vec![1_000usize];
+    if std::env::var_os("THESIS_MAX_COMMITTEE").is_some_and(|value| value != "0") {
+        sizes.extend([10_000, u16::MAX as usize]);
+    }
+    let nothing = limits::MemoryEstimate { setup_bytes: 0, signing_bytes: 0 };
+    if limits::ensure_fits(nothing, 0.8) == Ok(limits::MemoryCheck::Skipped) {
+        eprintln!("max_committee: available memory unknown, running every size unchecked");
+    }
+
+    let mut group = c.benchmark_group("max_committee");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(1));
+    let message = signed_message();
+    let mut rng = ThreadRngProvider.rng();
+
+    for system_size in sizes {
+        let params = Params::new(system_size, system_size - 1).unwrap();
+        let settings = frost::FrostSettings::new(params, HashFunction::default()).unwrap();
+
+        let estimate = limits::multisig_estimate(params);
+        match limits::ensure_fits(estimate, 0.8) {
+            Ok(_) => {
+                let participants: Vec<KeypairShare> = (0..system_size).map(|_| KeypairShare::default()).collect();
+                let mut committee = Committee::new();
+                for keypair in &participants {
+                    committee.add_key(keypair.verifying_share.clone());
+                }
+                let threshold = system_size - 1;
+                group.bench_function(format!("multisig_sign_verify_n{}", system_size), |b| {
+                    b.iter(|| {
+                        let certificate =
+                            participants.iter().take(threshold).map(|keypair| keypair.sign(&message)).collect::<Vec<_>>();
+                        committee.verify(&message, &certificate, threshold);
+                    })
+                });
+            }
+            Err(e) => eprintln!("max_committee: refusing multisig at n = {}: {}", system_size, e),
+        }
+
+        let estimate = limits::frost_dealer_estimate(params);
+        match limits::ensure_fits(estimate, 0.8) {
+            Ok(_) => {
+                let package = cached_package(&settings);
+                group.bench_function(format!("frost_sign_verify_n{}", system_size), |b| {
+                    b.iter(|| {
+                        let round1 = frost::vote_commitments(&settings, &package, &mut rng).unwrap();
+                        let round2 = frost::sign_message(&settings, &package, &round1, &message).unwrap();
+                        frost::aggregate_verify(&settings, &package, &round1, &round2, &message).unwrap();
+                    })
+                });
+            }
+            Err(e) => eprintln!("max_committee: refusing FROST at n = {}: {}", system_size, e),
+        }
+
+        eprintln!(
+            "Estimated peak memory at n = {}: multisig {} MiB, FROST dealer {} MiB, FROST DKG {} MiB",
+            system_size,
+            limits::multisig_estimate(params).peak_bytes() >> 20,
+            limits::frost_dealer_estimate(params).peak_bytes() >> 20,
+            limits::frost_dkg_estimate(params).peak_bytes() >> 20,
+        );
+    }
+
+    group.finish();
+}
+
+fn hash_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+
+    let settings = frost_settings();
+    let mut rng = ThreadRngProvider.rng();
+    let package = frost::setup(&settings, &mut rng).unwrap();
+    let leaves: Vec<&[u8]> = (0..SYSTEM_SIZE).map(|_| MESSAGE).collect();
+
+    // Framing overhead per hash choice: envelope, committee digest and Merkle commitment.
+    for hash_function in HashFunction::ALL {
+        group.bench_function(format!("envelope_{}", hash_function.name()), |b| {
+            b.iter(|| hash::envelope_digest(hash_function, b"bench", MESSAGE));
+        });
+        group.bench_function(format!("committee_digest_{}", hash_function.name()), |b| {
+            b.iter(|| hash::committee_digest(hash_function, package.public()).unwrap());
+        });
+        group.bench_function(format!("merkle_root_{}", hash_function.name()), |b| {
+            b.iter(|| hash::merkle_root(hash_function, &leaves));
+        });
+    }
+
+    group.finish();
+}
+
+fn fault_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("faults");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+
+    let settings = frost_settings();
+    let mut rng = ThreadRngProvider.rng();
+    let package = frost::setup(&settings, &mut rng).unwrap();
+
+    // Detection statistics for corrupted protocol messages at several byte corruption rates.
+    for rate in [0.001, 0.01, 0.1] {
+        let injector = BitFlipInjector::new(rate);
+        let stats = faults::bit_flip_campaign(&settings, &package, MESSAGE, &injector, 100, &mut rng);
+        println!(
+            "Bit flips (rate {}): {} runs, {}/{} messages corrupted, {} detected, {} forgeries accepted, {} panics",
+            rate, stats.runs, stats.corrupted_messages, stats.messages, stats.detected(), stats.accepted_forgeries, stats.panics
+        );
+
+        let rows = faults::correlation_report(&settings, MESSAGE, injector, &[1, 2, 3], 10).unwrap();
+        faults::write_correlation_csv(&rows, std::io::stdout()).unwrap();
+
+        group.bench_function(format!("frost_bit_flips_{}", rate), |b| {
+            b.iter(|| faults::run_with_bit_flips(&settings, &package, MESSAGE, &injector, &mut rng).unwrap());
         });
+    }
+
+    group.finish();
+}
+
+fn latency_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("latency");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+
+    let settings = frost_settings();
+    let mut rng = ThreadRngProvider.rng();
+    let package = frost::setup(&settings, &mut rng).unwrap();
+    let matrix = LatencyMatrix::synthetic(SYSTEM_SIZE, 150.0, &mut rng);
+    let coordinator = 0;
+
+    // Achieved (simulated) completion latency of the optimised subset vs random subsets.
+    let (optimised, random) = matrix.compare_with_random(coordinator, THRESHOLD, 1000, &mut rng);
+    println!("Latency: optimised subset {:.1} ms, random subset {:.1} ms (mean)", optimised, random);
+
+    group.bench_function("select_signers", |b| {
+        b.iter(|| matrix.select_signers(coordinator, THRESHOLD));
+    });
+
+    // Round 1 restricted to the selected subset.
+    let signers = latency::to_identifiers(&params(), &matrix.select_signers(coordinator, THRESHOLD)).unwrap();
+    group.bench_function("frost_commitments_selected", |b| {
+        b.iter(|| frost::vote_commitments_for(&settings, &package, &signers, &mut rng).unwrap());
+    });
+
+    // Speculative sessions: invite t + k signers so up to k crashed signers don't force a new session.
+    // The three closest signers other than the coordinator itself crash.
+    let crashed: std::collections::BTreeSet<usize> =
+        matrix.by_round_trip(coordinator).into_iter().filter(|&i| i != coordinator).take(3).collect();
+    for extra in 0..=3 {
+        let outcome = speculative::run_speculative(
+            &settings, &package, &matrix, coordinator, &crashed, extra, 1000.0, MESSAGE, &mut rng,
+        )
+        .unwrap();
+        println!(
+            "Speculative (t + {}), {} crashed: {:.1} ms over {} sessions",
+            extra, crashed.len(), outcome.completion_ms, outcome.sessions
+        );
+    }
+
+    group.finish();
+}
+
+// Resharing to a larger and to a smaller committee.
+fn reshare_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("reshare");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+    let settings = frost_settings();
+    let mut rng = ThreadRngProvider.rng();
+    let package = frost::setup(&settings, &mut rng).unwrap();
+
+    for max_faulty in [6, 13] {
+        let new_settings = frost::FrostSettings::new(Params::bft(max_faulty).unwrap(), HashFunction::default()).unwrap();
+        let identifiers = frost::generic::default_identifiers(new_settings.system_size()).unwrap();
+        let label = format!("{}_of_{}", new_settings.threshold(), new_settings.system_size());
+        group.bench_function(label, |b| {
+            b.iter(|| frost::reshare(&settings, &package, &new_settings, &identifiers, &mut rng).unwrap())
+        });
+    }
+    group.finish();
+}
+
+// Throughput of signing a queue of messages, one session per message, with
+// and without collecting the next commitments during aggregation.
+fn pipeline_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pipeline");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+    let settings = frost_settings();
+    let mut rng = ThreadRngProvider.rng();
+    let package = frost::setup(&settings, &mut rng).unwrap();
+    let messages: Vec<Vec<u8>> = (0..100)
+        .map(|index| generate::indexed_message(&settings, MESSAGE, index))
+        .collect();
+    let coordinator = BatchCoordinator::new(&settings, &package);
+    // The commitment thread needs a Send RNG, which ThreadRng is not.
+    let mut seeded = SeededRngProvider::new(rng.r#gen()).rng();
+
+    let (signatures, report) = coordinator.sign_pipelined(&messages, &mut seeded).unwrap();
+    generate::batch_verify(package.public(), &messages, &signatures).unwrap();
+    println!("Pipeline: {:.1} signatures/s over {} messages", report.signatures_per_second(), report.signatures);
+
+    group.throughput(Throughput::Elements(messages.len() as u64));
+    group.bench_function("sequential", |b| b.iter(|| coordinator.sign_sequential(&messages, &mut rng).unwrap()));
+    group.bench_function("pipelined", |b| b.iter(|| coordinator.sign_pipelined(&messages, &mut seeded).unwrap()));
+    group.finish();
+}
+
+// Scaling of parallel signing and batch verification with the number of
+// worker threads.
+#[cfg(feature = "rayon")]
+fn parallel_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+    let settings = frost_settings();
+    let mut rng = ThreadRngProvider.rng();
+    let package = frost::setup(&settings, &mut rng).unwrap();
+    let count = 100;
+    let messages: Vec<Vec<u8>> = (0..count as u64)
+        .map(|index| generate::indexed_message(&settings, MESSAGE, index))
+        .collect();
+    let signatures = generate::generate_signatures_parallel(&settings, &package, MESSAGE, count, &mut rng).unwrap();
+    generate::batch_verify_parallel(package.public(), &messages, &signatures).unwrap();
+
+    let cores = std::thread::available_parallelism().map_or(1, usize::from);
+    let mut threads = vec![1, 2, 4, 8, cores];
+    threads.retain(|&t| t <= cores);
+    threads.dedup();
+    // pool.install needs a Send closure, which rules out ThreadRng.
+    let mut seeded = SeededRngProvider::new(rng.r#gen()).rng();
+    group.throughput(Throughput::Elements(count as u64));
+    for t in threads {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(t).build().unwrap();
+        group.bench_function(format!("sign_{t}_threads"), |b| {
+            b.iter(|| {
+                pool.install(|| {
+                    generate::generate_signatures_parallel(&settings, &package, MESSAGE, count, &mut seeded).unwrap()
+                })
+            })
+        });
+        group.bench_function(format!("verify_{t}_threads"), |b| {
+            b.iter(|| pool.install(|| generate::batch_verify_parallel(package.public(), &messages, &signatures)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+// Cost of the per-message key tweak on top of a normal session. A tweaked
+// signature must verify only under the tweaked key, which is checked first.
+fn tweak_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tweak");
+    let settings = frost_settings();
+    let mut rng = ThreadRngProvider.rng();
+    let message = signed_message();
+    let package = frost::setup(&settings, &mut rng).unwrap();
+    let group_key = *package.public().verifying_key();
+    let hook = PayloadTweak::default();
+
+    let signature = tweak::sign_tweaked(&settings, &package, &hook, &message, &mut rng).unwrap();
+
+    let scalar = hook.derive(&group_key, &message).unwrap();
+    group.bench_function("derive", |b| b.iter(|| hook.derive(&group_key, &message).unwrap()));
+    group.bench_function("tweak_verifying_key", |b| {
+        b.iter(|| tweak::tweak_verifying_key(&group_key, &scalar).unwrap())
+    });
+    group.bench_function("tweak_package", |b| b.iter(|| tweak::tweak_package(&package, &scalar).unwrap()));
+    group.bench_function("verify_tweaked", |b| {
+        b.iter(|| tweak::verify_tweaked(&group_key, &hook, &message, &signature).unwrap())
     });
+    group.finish();
+}
+
+// Threshold randomness beacon on the BLS key: contributions, aggregation and
+// verification of one round.
+#[cfg(feature = "bls")]
+fn beacon_bench(c: &mut Criterion) {
+    use thesis::beacon::Beacon;
+    use thesis::bls::{self, BlsSettings};
+
+    let mut group = c.benchmark_group("beacon");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+    let mut rng = ThreadRngProvider.rng();
+    let settings = BlsSettings::new(Params::new(SYSTEM_SIZE, SYSTEM_SIZE / 3).unwrap());
+    let package = bls::setup(&settings, &mut rng).unwrap();
+    let beacon = Beacon::new(&settings, &package);
+    let round = 1;
+    let contributions: Vec<_> = package
+        .secret()
+        .iter()
+        .map(|(&index, share)| beacon.contribute(index, share, round))
+        .collect();
 
+    let threshold = settings.threshold as usize;
+    let first = beacon.aggregate(round, &contributions[..threshold]).unwrap();
+
+    let (&index, share) = package.secret().iter().next().unwrap();
+    group.bench_function("contribute", |b| b.iter(|| beacon.contribute(index, share, round)));
+    group.bench_function("aggregate", |b| {
+        b.iter(|| beacon.aggregate(round, &contributions[..threshold]).unwrap())
+    });
+    group.bench_function("verify_round", |b| b.iter(|| beacon.verify_round(round, &first).unwrap()));
     group.finish();
 }
 
+// Taproot key-path signing and libsecp256k1's BIP-340 verification, with and
+// without a script tree.
+#[cfg(feature = "taproot")]
+fn taproot_bench(c: &mut Criterion) {
+    use thesis::taproot;
+
+    let mut group = c.benchmark_group("taproot");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+    group.throughput(Throughput::Elements(1));
+    let settings = frost_settings();
+    let mut rng = ThreadRngProvider.rng();
+    let message = signed_message();
+    let package = taproot::setup(&settings, &mut rng).unwrap();
+
+    for (name, merkle_root) in [("key_path", None), ("script_tree", Some(&[7u8; 32][..]))] {
+        let signature = taproot::sign_key_path(&settings, &package, merkle_root, &message, &mut rng).unwrap();
+        let output = taproot::x_only(&taproot::output_key(&package, merkle_root)).unwrap();
+
+        group.bench_function(format!("sign_{name}"), |b| {
+            b.iter(|| taproot::sign_key_path(&settings, &package, merkle_root, &message, &mut rng).unwrap())
+        });
+        group.bench_function(format!("verify_bip340_{name}"), |b| {
+            b.iter(|| taproot::verify_bip340(&output, &message, &signature).unwrap())
+        });
+    }
+    group.finish();
+}
+
+// Hierarchical committee: 2-of-3 organizations, each a 3-of-5 FROST group.
+// Signing is timed with every organization available and with one offline.
+fn policy_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("policy");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+    group.throughput(Throughput::Elements(1));
+    let mut rng = ThreadRngProvider.rng();
+    let message = signed_message();
+    let organizations = (0..3)
+        .map(|index| Policy::group(&format!("org-{index}"), Params::new(5, 3).unwrap()))
+        .collect();
+    let tree = Policy::threshold("consortium", 2, organizations);
+    let keys = policy::setup(&tree, HashFunction::default(), &mut rng).unwrap();
+
+    for offline in [vec![], vec![0]] {
+        let unavailable: BTreeSet<Vec<usize>> = offline.iter().map(|&index| vec![index]).collect();
+        let certificate = policy::sign(&tree, &keys, &message, &unavailable, &mut rng).unwrap();
+        policy::verify(&tree, &keys, &message, &certificate).unwrap();
+        group.bench_function(format!("sign_2_of_3_orgs_{}_offline", offline.len()), |b| {
+            b.iter(|| policy::sign(&tree, &keys, &message, &unavailable, &mut rng).unwrap())
+        });
+    }
+
+    let certificate = policy::sign(&tree, &keys, &message, &BTreeSet::new(), &mut rng).unwrap();
+    group.bench_function("verify_2_of_3_orgs", |b| {
+        b.iter(|| policy::verify(&tree, &keys, &message, &certificate).unwrap())
+    });
+    group.finish();
+}
+
+// ROAST at 5-of-7 over a synthetic network, with (silent, invalid) faulty
+// signer counts for the benches: none, a crash after round 1, the maximum of
+// n - t invalid shares, and one of each.
+const ROAST_FAULTS: [(usize, usize); 4] = [(0, 0), (1, 0), (0, 2), (1, 1)];
+
+fn roast_network(system_size: usize, threshold: usize) -> (NetworkModel, SimConfig) {
+    let mut rng = SeededRngProvider::new(7).rng();
+    let config = SimConfig {
+        coordinator: 0,
+        threshold,
+        retransmit_ms: 500.0,
+        deadline_ms: 60_000.0,
+    };
+    let network = NetworkModel {
+        latencies: LatencyMatrix::synthetic(system_size, 150.0, &mut rng),
+        jitter_ms: 20.0,
+        drop_probability: 0.0,
+        processing_ms: 1.0,
+    };
+    (network, config)
+}
+
+// Wall-clock cost of simulating one ROAST run per fault pattern. The same
+// benchmark ids are measured in rounds by `roast_rounds_bench`, so both end
+// up side by side in Criterion's output.
+fn roast_faults_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("roast_faults");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+    group.throughput(Throughput::Elements(1));
+    let mut rng = ThreadRngProvider.rng();
+    let (system_size, threshold) = (7, 5);
+    let (network, config) = roast_network(system_size, threshold);
+
+    for (silent, invalid) in ROAST_FAULTS {
+        let faults = SignerFaults::highest(system_size, config.coordinator, silent, invalid);
+        group.bench_function(format!("silent{silent}_invalid{invalid}"), |b| {
+            b.iter(|| netsim::simulate_with_faults(SimProtocol::Roast, &network, &config, &faults, &mut rng))
+        });
+    }
+    group.finish();
+}
+
+// Simulated ROAST latency at 5-of-7 with 0 to n - t crashed, slow (300 ms
+// late) or invalid signers. Each run reports its completion time, which is
+// what this group measures instead of the cost of simulating it.
+fn roast_fault_kinds_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("roast_fault_kinds");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+    let mut rng = ThreadRngProvider.rng();
+    let (system_size, threshold) = (7, 5);
+    let (network, config) = roast_network(system_size, threshold);
+
+    for faulty in 0..=system_size - threshold {
+        let kinds = [
+            ("crashed", SignerFaults::crashed(system_size, config.coordinator, faulty)),
+            ("slow", SignerFaults::slow(system_size, config.coordinator, faulty, 300.0)),
+            ("invalid", SignerFaults::highest(system_size, config.coordinator, 0, faulty)),
+        ];
+        for (kind, faults) in kinds {
+            group.bench_with_input(BenchmarkId::new(kind, faulty), &faults, |b, faults| {
+                b.iter_custom(|iters| {
+                    let simulated_ms: f64 = (0..iters)
+                        .map(|_| {
+                            let report =
+                                netsim::simulate_with_faults(SimProtocol::Roast, &network, &config, faults, &mut rng);
+                            report.completion_ms.expect("ROAST did not complete")
+                        })
+                        .sum();
+                    std::time::Duration::from_secs_f64(simulated_ms / 1000.0)
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+// Criterion measurement in protocol rounds instead of time. Rounds are not
+// something `start`/`end` can observe, so it only works with `iter_custom`,
+// which returns the rounds the simulated runs took.
+struct Rounds;
+
+impl Measurement for Rounds {
+    type Intermediate = ();
+    type Value = u64;
+
+    fn start(&self) -> Self::Intermediate {}
+
+    fn end(&self, _: Self::Intermediate) -> Self::Value {
+        0
+    }
+
+    fn add(&self, v1: &Self::Value, v2: &Self::Value) -> Self::Value {
+        v1 + v2
+    }
+
+    fn zero(&self) -> Self::Value {
+        0
+    }
+
+    fn to_f64(&self, value: &Self::Value) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &RoundsFormatter
+    }
+}
+
+struct RoundsFormatter;
+
+impl ValueFormatter for RoundsFormatter {
+    fn scale_values(&self, _typical_value: f64, _values: &mut [f64]) -> &'static str {
+        "rounds"
+    }
+
+    fn scale_throughputs(&self, _typical_value: f64, throughput: &Throughput, values: &mut [f64]) -> &'static str {
+        let (count, unit) = match throughput {
+            Throughput::Elements(count) => (*count, "rounds/signature"),
+            Throughput::Bytes(count) => (*count, "rounds/byte"),
+        };
+        for value in values {
+            *value /= count as f64;
+        }
+        unit
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "rounds"
+    }
+}
+
+fn roast_rounds_bench(c: &mut Criterion<Rounds>) {
+    let mut group = c.benchmark_group("roast_rounds");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+    let mut rng = ThreadRngProvider.rng();
+    let (system_size, threshold) = (7, 5);
+    let (network, config) = roast_network(system_size, threshold);
+
+    for (silent, invalid) in ROAST_FAULTS {
+        let faults = SignerFaults::highest(system_size, config.coordinator, silent, invalid);
+        group.bench_function(format!("silent{silent}_invalid{invalid}"), |b| {
+            b.iter_custom(|iters| {
+                (0..iters)
+                    .map(|_| {
+                        let report =
+                            netsim::simulate_with_faults(SimProtocol::Roast, &network, &config, &faults, &mut rng);
+                        assert!(report.completion_ms.is_some(), "ROAST did not complete");
+                        report.rounds as u64
+                    })
+                    .sum()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn gossip_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gossip");
+    group.sampling_mode(criterion::SamplingMode::Flat);
+    let mut rng = ThreadRngProvider.rng();
+
+    // Membership convergence before key generation, for committee sizes up to 1000.
+    for system_size in [30u16, 100, 1000] {
+        let stats = MembershipGossip::new(system_size, &[0], HashFunction::default()).run(3, 1000, &mut rng);
+        println!(
+            "Gossip n = {}: converged = {} after {} rounds ({:.0} ms at 50 ms/round), {} messages",
+            system_size, stats.converged, stats.rounds, stats.simulated_time_ms(50.0), stats.messages
+        );
+    }
+
+    group.bench_function("membership_convergence", |b| {
+        b.iter(|| MembershipGossip::new(SYSTEM_SIZE as u16, &[0], HashFunction::default()).run(3, 1000, &mut rng));
+    });
+
+    group.finish();
+}
+
+// Measured runs should start in a controlled environment, as with
+// run_queue. THESIS_FORCE=1 runs the benchmarks anyway.
+fn check_environment() {
+    let report = preflight::run_checks(std::path::Path::new("target"), preflight::MIN_FREE_BYTES);
+    if report.passed() {
+        return;
+    }
+    for check in &report.checks {
+        eprintln!("{}", check);
+    }
+    if std::env::var_os("THESIS_FORCE").is_some_and(|value| value != "0") {
+        eprintln!("Preflight failed, continuing because of THESIS_FORCE.");
+    } else {
+        eprintln!("Preflight failed; refusing measured runs (set THESIS_FORCE=1 to override).");
+        std::process::exit(1);
+    }
+}
+
 fn benchmarks(c: &mut Criterion) {
-    multisig_bench(c);
+    check_environment();
     frost_bench(c);
+    aggregation_policy_bench(c);
+    ciphersuites_bench(c);
+    scheme_bench::<scheme::Multisig>(c, &frost_settings());
+    scheme_bench::<scheme::Frost>(c, &frost_settings());
+    scheme_bench::<scheme::FrostDkg>(c, &frost_settings());
+    #[cfg(feature = "bls")]
+    scheme_bench::<scheme::Bls>(c, &thesis::bls::BlsSettings::new(params()));
+    large_message_bench::<scheme::Frost>(c, &frost_settings());
+    #[cfg(feature = "bls")]
+    large_message_bench::<scheme::Bls>(c, &thesis::bls::BlsSettings::new(params()));
+    max_committee_bench(c);
+    reshare_bench(c);
+    policy_bench(c);
+    pipeline_bench(c);
+    #[cfg(feature = "rayon")]
+    parallel_bench(c);
+    tweak_bench(c);
+    #[cfg(feature = "taproot")]
+    taproot_bench(c);
+    #[cfg(feature = "bls")]
+    beacon_bench(c);
+    hash_bench(c);
+    fault_bench(c);
+    latency_bench(c);
+    roast_faults_bench(c);
+    roast_fault_kinds_bench(c);
+    gossip_bench(c);
 }
 
 criterion_group!(benches, benchmarks);
-criterion_main!(benches);
\ No newline at end of file
+// Without faults every run takes exactly two rounds, and Criterion's plots
+// cannot estimate a density for identical samples.
+criterion_group! {
+    name = round_benches;
+    config = Criterion::default().with_measurement(Rounds).without_plots();
+    targets = roast_rounds_bench
+}
+criterion_main!(benches, round_benches);
\ No newline at end of file
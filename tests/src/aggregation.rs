@@ -0,0 +1,375 @@
+use frost::round2::SignatureShare;
+use frost_ed25519::{self as frost, Ed25519Group, Ed25519ScalarField, Field, Group, Identifier, Signature, SigningPackage, keys::PublicKeyPackage};
+use old_rand::{CryptoRng, Rng, RngCore};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::frost::{FrostPackage, FrostSettings};
+
+type Element = <Ed25519Group as Group>::Element;
+type Scalar = <Ed25519ScalarField as Field>::Scalar;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AggregationPolicy {
+    // Verify every share as it arrives, aggregate only a clean set.
+    VerifyEach,
+    // Aggregate straight away and rely on the final signature check; on
+    // failure frost's built-in cheater detection names a single culprit.
+    Optimistic,
+    // Aggregate optimistically; only when the final signature is invalid,
+    // bisect the signers, checking the summed share equations of each half,
+    // until every bad signer is isolated.
+    Hybrid,
+}
+
+impl AggregationPolicy {
+    pub const ALL: [AggregationPolicy; 3] = [
+        AggregationPolicy::VerifyEach,
+        AggregationPolicy::Optimistic,
+        AggregationPolicy::Hybrid,
+    ];
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct AggregationOutcome {
+    pub signature: Option<Signature>,
+    pub invalid_signers: BTreeSet<Identifier>,
+    pub share_verifications: usize,
+    pub aggregations: usize,
+}
+
+// Each signer's share equation z_i·G = R_i + c·λ_i·Y_i as its two sides.
+// The equations of a group of signers hold together iff their sums do, up to
+// shares crafted to cancel out, which random corruption does not produce.
+fn share_equations(
+    signing_package: &SigningPackage,
+    signature_shares: &BTreeMap<Identifier, SignatureShare>,
+    public: &PublicKeyPackage,
+) -> Result<BTreeMap<Identifier, (Scalar, Element)>, frost::Error> {
+    let verifying_key = public.verifying_key();
+    let binding_factors = frost_core::compute_binding_factor_list(signing_package, verifying_key, &[])?;
+    let group_commitment = frost_core::compute_group_commitment(signing_package, &binding_factors)?;
+    let challenge =
+        frost_core::challenge(&group_commitment.to_element(), verifying_key, signing_package.message())?.to_scalar();
+    signature_shares
+        .iter()
+        .map(|(identifier, signature_share)| {
+            let verifying_share = public
+                .verifying_shares()
+                .get(identifier)
+                .ok_or(frost::Error::UnknownIdentifier)?;
+            let binding_factor = binding_factors.get(identifier).ok_or(frost::Error::UnknownIdentifier)?;
+            let commitment_share = signing_package
+                .signing_commitment(identifier)
+                .ok_or(frost::Error::UnknownIdentifier)?
+                .to_group_commitment_share(binding_factor);
+            let lambda = frost_core::derive_interpolating_value(identifier, signing_package)?;
+            let bytes: [u8; 32] = signature_share
+                .serialize()
+                .try_into()
+                .map_err(|_| frost::Error::MalformedSignature)?;
+            let z = Ed25519ScalarField::deserialize(&bytes).map_err(|_| frost::Error::MalformedSignature)?;
+            let rhs = commitment_share.to_element() + verifying_share.to_element() * (challenge * lambda);
+            Ok((*identifier, (z, rhs)))
+        })
+        .collect()
+}
+
+fn group_holds(
+    equations: &BTreeMap<Identifier, (Scalar, Element)>,
+    signers: &[Identifier],
+    outcome: &mut AggregationOutcome,
+) -> bool {
+    outcome.share_verifications += 1;
+    let z: Scalar = signers.iter().map(|identifier| equations[identifier].0).sum();
+    let rhs: Element = signers.iter().map(|identifier| equations[identifier].1).sum();
+    Ed25519Group::generator() * z == rhs
+}
+
+// `signers` are known to contain a bad share; recurse into each half whose
+// equations fail until single signers remain.
+fn bisect(
+    equations: &BTreeMap<Identifier, (Scalar, Element)>,
+    signers: &[Identifier],
+    outcome: &mut AggregationOutcome,
+) {
+    if let [culprit] = signers {
+        tracing::warn!(signer = ?culprit, "share rejected");
+        outcome.invalid_signers.insert(*culprit);
+        return;
+    }
+    let (left, right) = signers.split_at(signers.len() / 2);
+    for half in [left, right] {
+        if !group_holds(equations, half, outcome) {
+            bisect(equations, half, outcome);
+        }
+    }
+}
+
+fn verify_shares(
+    signing_package: &SigningPackage,
+    signature_shares: &BTreeMap<Identifier, SignatureShare>,
+    public: &PublicKeyPackage,
+    outcome: &mut AggregationOutcome,
+) -> Result<(), frost::Error> {
+    for (identifier, signature_share) in signature_shares {
+        let verifying_share = public
+            .verifying_shares()
+            .get(identifier)
+            .ok_or(frost::Error::UnknownIdentifier)?;
+        outcome.share_verifications += 1;
+        if frost_core::verify_signature_share(
+            *identifier,
+            verifying_share,
+            signature_share,
+            signing_package,
+            public.verifying_key(),
+        )
+        .is_err()
+        {
+            tracing::warn!(signer = ?identifier, "share rejected");
+            outcome.invalid_signers.insert(*identifier);
+        } else {
+            tracing::debug!(signer = ?identifier, "share verified");
+        }
+    }
+    Ok(())
+}
+
+// A FROST signature needs a share from every participant in the signing
+// package, so once a bad share is found no signature is produced; the caller
+// has to open a new session without the reported signers, as `sign_robust`
+// does.
+pub fn aggregate_with_policy(
+    policy: AggregationPolicy,
+    signing_package: &SigningPackage,
+    signature_shares: &BTreeMap<Identifier, SignatureShare>,
+    public: &PublicKeyPackage,
+) -> Result<AggregationOutcome, frost::Error> {
+    let mut outcome = AggregationOutcome::default();
+
+    if policy == AggregationPolicy::VerifyEach {
+        verify_shares(signing_package, signature_shares, public, &mut outcome)?;
+        if !outcome.invalid_signers.is_empty() {
+            return Ok(outcome);
+        }
+    }
+
+    outcome.aggregations += 1;
+    match frost::aggregate(signing_package, signature_shares, public) {
+        Ok(signature) => {
+            tracing::info!(signers = signature_shares.len(), ?policy, "aggregated");
+            outcome.signature = Some(signature)
+        }
+        Err(frost::Error::InvalidSignatureShare { culprit }) => match policy {
+            AggregationPolicy::Hybrid => {
+                let equations = share_equations(signing_package, signature_shares, public)?;
+                let signers: Vec<Identifier> = equations.keys().copied().collect();
+                bisect(&equations, &signers, &mut outcome);
+            }
+            _ => {
+                tracing::warn!(signer = ?culprit, "share rejected");
+                outcome.invalid_signers.insert(culprit);
+            }
+        },
+        Err(e) => return Err(e),
+    }
+    Ok(outcome)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RobustSignature {
+    pub signature: Signature,
+    // The t signers of the session that produced the signature.
+    pub signers: Vec<Identifier>,
+    pub invalid_signers: BTreeSet<Identifier>,
+    pub sessions: usize,
+    pub share_verifications: usize,
+    pub aggregations: usize,
+}
+
+// Signing when more than t signers are available. A session runs over the
+// first t candidates; when `policy` reports bad shares, those signers are
+// dropped and a new session runs over the next t, so one bad signer out of
+// t + 2 costs one extra session instead of the signature. `tamper` sees the
+// shares before aggregation, which is where tests and benches put faults.
+pub fn sign_robust<RNG, F>(
+    policy: AggregationPolicy,
+    settings: &FrostSettings,
+    package: &FrostPackage,
+    candidates: &[Identifier],
+    message: &[u8],
+    rng: &mut RNG,
+    mut tamper: F,
+) -> Result<RobustSignature, frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+    F: FnMut(&mut BTreeMap<Identifier, SignatureShare>),
+{
+    let threshold = settings.threshold() as usize;
+    let mut candidates = candidates.to_vec();
+    let mut invalid_signers = BTreeSet::new();
+    let (mut sessions, mut share_verifications, mut aggregations) = (0, 0, 0);
+
+    while candidates.len() >= threshold {
+        let signers = &candidates[..threshold];
+        sessions += 1;
+        let round1 = crate::frost::vote_commitments_for(settings, package, signers, rng)?;
+        let round2 = crate::frost::sign_message(settings, package, &round1, message)?;
+        let mut shares = round2.signature_shares().clone();
+        tamper(&mut shares);
+        let outcome = aggregate_with_policy(policy, round2.signing_package(), &shares, package.public())?;
+        share_verifications += outcome.share_verifications;
+        aggregations += outcome.aggregations;
+        if let Some(signature) = outcome.signature {
+            return Ok(RobustSignature {
+                signature,
+                signers: signers.to_vec(),
+                invalid_signers,
+                sessions,
+                share_verifications,
+                aggregations,
+            });
+        }
+        if outcome.invalid_signers.is_empty() {
+            return Err(frost::Error::InvalidSignature);
+        }
+        candidates.retain(|identifier| !outcome.invalid_signers.contains(identifier));
+        invalid_signers.extend(outcome.invalid_signers);
+    }
+    Err(frost::Error::IncorrectNumberOfShares)
+}
+
+// Replaces each share with probability `rate`, clamped to [0, 1], by
+// another signer's share, which decodes fine but is invalid for this signer.
+pub fn corrupt_shares<RNG: RngCore>(
+    signature_shares: &BTreeMap<Identifier, SignatureShare>,
+    rate: f64,
+    rng: &mut RNG,
+) -> BTreeMap<Identifier, SignatureShare> {
+    let rate = if rate.is_nan() { 0.0 } else { rate.clamp(0.0, 1.0) };
+    let shares: Vec<SignatureShare> = signature_shares.values().copied().collect();
+    signature_shares
+        .iter()
+        .enumerate()
+        .map(|(index, (identifier, share))| {
+            if shares.len() > 1 && rng.gen_bool(rate) {
+                (*identifier, shares[(index + 1) % shares.len()])
+            } else {
+                (*identifier, *share)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frost as thesis_frost;
+    use crate::testing;
+
+    fn session(seed: u64) -> (PublicKeyPackage, thesis_frost::FrostRound2) {
+        let (settings, package) = testing::committee(seed);
+        let mut rng = testing::rng(seed);
+        let round1 = thesis_frost::vote_commitments(&settings, &package, &mut rng).unwrap();
+        let round2 = thesis_frost::sign_message(&settings, &package, &round1, b"aggregation").unwrap();
+        (package.public().clone(), round2)
+    }
+
+    #[test]
+    fn policies_agree_on_clean_shares() {
+        let (public, round2) = session(1);
+        for policy in AggregationPolicy::ALL {
+            let outcome =
+                aggregate_with_policy(policy, round2.signing_package(), round2.signature_shares(), &public).unwrap();
+            assert!(outcome.signature.is_some(), "{policy:?}");
+            assert!(outcome.invalid_signers.is_empty(), "{policy:?}");
+        }
+    }
+
+    #[test]
+    fn bisection_finds_every_bad_share() {
+        let (public, round2) = session(2);
+        let identifiers: Vec<Identifier> = round2.signature_shares().keys().copied().collect();
+        let mut shares = round2.signature_shares().clone();
+        // Signers 1 and 4 send signer 0's share.
+        let stolen = shares[&identifiers[0]];
+        shares.insert(identifiers[1], stolen);
+        shares.insert(identifiers[4], stolen);
+        let expected = BTreeSet::from([identifiers[1], identifiers[4]]);
+
+        let hybrid = aggregate_with_policy(AggregationPolicy::Hybrid, round2.signing_package(), &shares, &public).unwrap();
+        assert_eq!(hybrid.invalid_signers, expected);
+        assert!(hybrid.signature.is_none());
+        let verify_each =
+            aggregate_with_policy(AggregationPolicy::VerifyEach, round2.signing_package(), &shares, &public).unwrap();
+        assert_eq!(verify_each.invalid_signers, expected);
+        let optimistic =
+            aggregate_with_policy(AggregationPolicy::Optimistic, round2.signing_package(), &shares, &public).unwrap();
+        assert_eq!(optimistic.invalid_signers.len(), 1);
+        assert!(optimistic.invalid_signers.is_subset(&expected));
+    }
+
+    #[test]
+    fn one_bad_signer_out_of_t_plus_two_is_routed_around() {
+        let (settings, package) = testing::committee(4);
+        let candidates: Vec<Identifier> = package.secret().keys().copied().collect();
+        assert_eq!(candidates.len(), settings.threshold() as usize + 2);
+
+        for (index, &bad) in candidates.iter().enumerate() {
+            // The bad signer sends another signer's share of the same session.
+            let tamper = |shares: &mut BTreeMap<Identifier, SignatureShare>| {
+                if shares.contains_key(&bad) {
+                    let other = shares.keys().find(|&&identifier| identifier != bad).copied().unwrap();
+                    shares.insert(bad, shares[&other]);
+                }
+            };
+            let mut rng = testing::rng(index as u64);
+            let outcome =
+                sign_robust(AggregationPolicy::Optimistic, &settings, &package, &candidates, b"robust", &mut rng, tamper)
+                    .unwrap();
+            package.public().verifying_key().verify(b"robust", &outcome.signature).unwrap();
+            assert!(!outcome.signers.contains(&bad));
+            // A bad signer beyond the first t is never asked.
+            let in_first_session = index < settings.threshold() as usize;
+            let expected = if in_first_session { BTreeSet::from([bad]) } else { BTreeSet::new() };
+            assert_eq!(outcome.invalid_signers, expected);
+            assert_eq!(outcome.sessions, if in_first_session { 2 } else { 1 });
+        }
+    }
+
+    #[test]
+    fn too_many_bad_signers_fail() {
+        let (settings, package) = testing::committee(5);
+        let candidates: Vec<Identifier> = package.secret().keys().copied().collect();
+        let bad = BTreeSet::from([candidates[0], candidates[1], candidates[2]]);
+        // Every bad signer sends the first honest signer's share.
+        let tamper = |shares: &mut BTreeMap<Identifier, SignatureShare>| {
+            let honest = shares.keys().find(|identifier| !bad.contains(identifier)).copied().unwrap();
+            for identifier in &bad {
+                if shares.contains_key(identifier) {
+                    shares.insert(*identifier, shares[&honest]);
+                }
+            }
+        };
+        let outcome =
+            sign_robust(AggregationPolicy::Hybrid, &settings, &package, &candidates, b"robust", &mut testing::rng(5), tamper);
+        assert!(matches!(outcome, Err(frost::Error::IncorrectNumberOfShares)));
+    }
+
+    #[test]
+    fn corruption_rate_is_clamped() {
+        let (public, round2) = session(3);
+        let mut rng = testing::rng(3);
+        assert_eq!(&corrupt_shares(round2.signature_shares(), -1.0, &mut rng), round2.signature_shares());
+        assert_eq!(&corrupt_shares(round2.signature_shares(), f64::NAN, &mut rng), round2.signature_shares());
+
+        // Every share is replaced. The replacements are a rotation of the
+        // same shares, so their sum and the aggregate still verify; only the
+        // per-share checks notice.
+        let shares = corrupt_shares(round2.signature_shares(), 2.0, &mut rng);
+        assert!(shares.iter().all(|(identifier, share)| round2.signature_shares()[identifier] != *share));
+        let outcome =
+            aggregate_with_policy(AggregationPolicy::VerifyEach, round2.signing_package(), &shares, &public).unwrap();
+        assert_eq!(outcome.invalid_signers.len(), shares.len());
+    }
+}
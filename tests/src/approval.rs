@@ -0,0 +1,262 @@
+use frost_ed25519::{self as frost, Identifier, Signature};
+use old_rand::{CryptoRng, RngCore};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::frost::{FrostPackage, FrostSettings};
+
+#[derive(Debug)]
+pub enum ApprovalError {
+    Frost(frost::Error),
+    // Not enough signers approved the message.
+    TooFewApprovals {
+        approved: usize,
+        threshold: usize,
+        refusals: BTreeMap<Identifier, String>,
+    },
+}
+
+impl fmt::Display for ApprovalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApprovalError::Frost(e) => write!(f, "frost error: {e}"),
+            ApprovalError::TooFewApprovals {
+                approved,
+                threshold,
+                refusals,
+            } => write!(
+                f,
+                "{approved} signers approved, {threshold} required ({} refused)",
+                refusals.len()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ApprovalError {}
+
+impl From<frost::Error> for ApprovalError {
+    fn from(e: frost::Error) -> Self {
+        ApprovalError::Frost(e)
+    }
+}
+
+// What a signer knows about the request it is asked to sign.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SessionInfo {
+    pub session: u64,
+    pub signer: Identifier,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Decision {
+    Approve,
+    Refuse(String),
+}
+
+// Consulted by each signer before it commits to a session, so a signer never
+// produces a share for a message it would not sign.
+pub trait SigningPolicy {
+    fn approve(&self, message: &[u8], session: &SessionInfo) -> Decision;
+}
+
+pub struct AllowAll;
+
+impl SigningPolicy for AllowAll {
+    fn approve(&self, _message: &[u8], _session: &SessionInfo) -> Decision {
+        Decision::Approve
+    }
+}
+
+// Only the listed messages are signed.
+pub struct AllowList {
+    messages: HashSet<Vec<u8>>,
+}
+
+impl AllowList {
+    pub fn new<I: IntoIterator<Item = Vec<u8>>>(messages: I) -> Self {
+        AllowList {
+            messages: messages.into_iter().collect(),
+        }
+    }
+}
+
+impl SigningPolicy for AllowList {
+    fn approve(&self, message: &[u8], _session: &SessionInfo) -> Decision {
+        if self.messages.contains(message) {
+            Decision::Approve
+        } else {
+            Decision::Refuse("message is not on the allow list".to_string())
+        }
+    }
+}
+
+// At most `limit` approvals per signer within any `window`.
+pub struct RateLimit {
+    limit: usize,
+    window: Duration,
+    approvals: Mutex<BTreeMap<Identifier, VecDeque<Instant>>>,
+}
+
+impl RateLimit {
+    pub fn new(limit: usize, window: Duration) -> Self {
+        RateLimit {
+            limit,
+            window,
+            approvals: Mutex::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl SigningPolicy for RateLimit {
+    fn approve(&self, _message: &[u8], session: &SessionInfo) -> Decision {
+        let now = Instant::now();
+        let mut approvals = self.approvals.lock().expect("rate limit state poisoned");
+        let recent = approvals.entry(session.signer).or_default();
+        while recent.front().is_some_and(|&at| now.duration_since(at) >= self.window) {
+            recent.pop_front();
+        }
+        if recent.len() >= self.limit {
+            return Decision::Refuse(format!("more than {} signatures within {:?}", self.limit, self.window));
+        }
+        recent.push_back(now);
+        Decision::Approve
+    }
+}
+
+// Each signer runs its own policy; signers without one fall back to
+// `default`. This is how a committee with heterogeneous signers is modelled
+// in a single process.
+pub struct PerSigner {
+    policies: BTreeMap<Identifier, Box<dyn SigningPolicy>>,
+    default: Box<dyn SigningPolicy>,
+}
+
+impl PerSigner {
+    pub fn new(default: Box<dyn SigningPolicy>) -> Self {
+        PerSigner {
+            policies: BTreeMap::new(),
+            default,
+        }
+    }
+
+    pub fn with(mut self, signer: Identifier, policy: Box<dyn SigningPolicy>) -> Self {
+        self.policies.insert(signer, policy);
+        self
+    }
+}
+
+impl SigningPolicy for PerSigner {
+    fn approve(&self, message: &[u8], session: &SessionInfo) -> Decision {
+        self.policies
+            .get(&session.signer)
+            .unwrap_or(&self.default)
+            .approve(message, session)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ApprovedSignature {
+    pub signature: Signature,
+    pub signers: Vec<Identifier>,
+    // Signers asked before the threshold was reached that refused.
+    pub refusals: BTreeMap<Identifier, String>,
+}
+
+// Asks the signers in identifier order and runs the session with the first
+// t that approve, so a refusal falls back to the next participant instead of
+// stalling the session. Signers after the first t are not asked.
+pub fn sign_with_policy<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    message: &[u8],
+    session: u64,
+    policy: &dyn SigningPolicy,
+    rng: &mut RNG,
+) -> Result<ApprovedSignature, ApprovalError>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let threshold = settings.threshold() as usize;
+    let mut signers = Vec::with_capacity(threshold);
+    let mut refusals = BTreeMap::new();
+    for &signer in packages.secret().keys() {
+        if signers.len() == threshold {
+            break;
+        }
+        match policy.approve(message, &SessionInfo { session, signer }) {
+            Decision::Approve => signers.push(signer),
+            Decision::Refuse(reason) => {
+                tracing::info!(signer = ?signer, %reason, "signer refused");
+                refusals.insert(signer, reason);
+            }
+        }
+    }
+    if signers.len() < threshold {
+        return Err(ApprovalError::TooFewApprovals {
+            approved: signers.len(),
+            threshold,
+            refusals,
+        });
+    }
+
+    let round1 = crate::frost::vote_commitments_for(settings, packages, &signers, rng)?;
+    let round2 = crate::frost::sign_message(settings, packages, &round1, message)?;
+    let signature = frost::aggregate(round2.signing_package(), round2.signature_shares(), packages.public())?;
+    Ok(ApprovedSignature {
+        signature,
+        signers,
+        refusals,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn refusing_signers_are_replaced_by_the_next_ones() {
+        let (settings, package) = testing::committee(1);
+        let mut rng = testing::rng(2);
+        let refused: Vec<Identifier> = package.secret().keys().take(2).copied().collect();
+        let mut policy = PerSigner::new(Box::new(AllowList::new([b"message".to_vec()])));
+        for &signer in &refused {
+            policy = policy.with(signer, Box::new(AllowList::new([])));
+        }
+
+        let approved = sign_with_policy(&settings, &package, b"message", 0, &policy, &mut rng).unwrap();
+        package.public().verifying_key().verify(b"message", &approved.signature).unwrap();
+        assert_eq!(approved.refusals.keys().copied().collect::<Vec<_>>(), refused);
+        assert_eq!(approved.signers, package.secret().keys().skip(2).copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn too_few_approvals_give_no_signature() {
+        let (settings, package) = testing::committee(3);
+        let policy = AllowList::new([b"message".to_vec()]);
+        let outcome = sign_with_policy(&settings, &package, b"other", 0, &policy, &mut testing::rng(4));
+        assert!(matches!(
+            outcome,
+            Err(ApprovalError::TooFewApprovals { approved: 0, threshold: 5, refusals }) if refusals.len() == 7
+        ));
+    }
+
+    #[test]
+    fn rate_limit_counts_per_signer() {
+        let (first, second) = (Identifier::try_from(1u16).unwrap(), Identifier::try_from(2u16).unwrap());
+        let limit = RateLimit::new(1, Duration::from_secs(60));
+        let ask = |signer| limit.approve(b"message", &SessionInfo { session: 0, signer });
+        assert_eq!(ask(first), Decision::Approve);
+        assert!(matches!(ask(first), Decision::Refuse(_)));
+        assert_eq!(ask(second), Decision::Approve);
+
+        let (settings, package) = testing::committee(5);
+        let mut rng = testing::rng(6);
+        let limit = RateLimit::new(1, Duration::from_secs(60));
+        sign_with_policy(&settings, &package, b"message", 1, &limit, &mut rng).unwrap();
+        assert!(sign_with_policy(&settings, &package, b"message", 2, &limit, &mut rng).is_err());
+    }
+}
@@ -0,0 +1,243 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MANIFEST_FILE: &str = "artifacts.json";
+
+#[derive(Debug)]
+pub enum ArtifactError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    OutsideRoot(PathBuf),
+    UnknownKind(String),
+}
+
+impl fmt::Display for ArtifactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArtifactError::Io(e) => write!(f, "io error: {e}"),
+            ArtifactError::Json(e) => write!(f, "json error: {e}"),
+            ArtifactError::OutsideRoot(path) => write!(f, "{} is outside the artifact directory", path.display()),
+            ArtifactError::UnknownKind(kind) => write!(f, "unknown artifact kind {kind}"),
+        }
+    }
+}
+
+impl std::error::Error for ArtifactError {}
+
+impl From<io::Error> for ArtifactError {
+    fn from(e: io::Error) -> Self {
+        ArtifactError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ArtifactError {
+    fn from(e: serde_json::Error) -> Self {
+        ArtifactError::Json(e)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    SignatureShard,
+    KeyStore,
+    EventLog,
+    Result,
+    Other,
+}
+
+impl ArtifactKind {
+    pub const ALL: [ArtifactKind; 5] = [
+        ArtifactKind::SignatureShard,
+        ArtifactKind::KeyStore,
+        ArtifactKind::EventLog,
+        ArtifactKind::Result,
+        ArtifactKind::Other,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ArtifactKind::SignatureShard => "signature_shard",
+            ArtifactKind::KeyStore => "key_store",
+            ArtifactKind::EventLog => "event_log",
+            ArtifactKind::Result => "result",
+            ArtifactKind::Other => "other",
+        }
+    }
+}
+
+impl FromStr for ArtifactKind {
+    type Err = ArtifactError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ArtifactKind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == s)
+            .ok_or_else(|| ArtifactError::UnknownKind(s.to_string()))
+    }
+}
+
+// `path` is relative to the artifact directory, so the directory can be
+// moved between machines without invalidating the manifest.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactEntry {
+    pub path: PathBuf,
+    pub kind: ArtifactKind,
+    pub size: u64,
+    pub created_unix: u64,
+}
+
+impl ArtifactEntry {
+    pub fn age(&self, now: SystemTime) -> Duration {
+        let created = UNIX_EPOCH + Duration::from_secs(self.created_unix);
+        now.duration_since(created).unwrap_or_default()
+    }
+}
+
+// Which artifacts `clean` may delete. Expired entries go first; if the
+// directory is still over budget, the oldest remaining ones follow. Kinds in
+// `keep` are never deleted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub max_age: Option<Duration>,
+    pub max_total_bytes: Option<u64>,
+    pub keep: Vec<ArtifactKind>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CleanReport {
+    pub removed: Vec<ArtifactEntry>,
+    pub freed_bytes: u64,
+    pub remaining_bytes: u64,
+}
+
+// Tracks generated files under one directory in `artifacts.json`.
+pub struct ArtifactManager {
+    root: PathBuf,
+    entries: Vec<ArtifactEntry>,
+}
+
+impl ArtifactManager {
+    pub fn open<P: AsRef<Path>>(root: P) -> Result<Self, ArtifactError> {
+        let root = root.as_ref().to_path_buf();
+        fs::create_dir_all(&root)?;
+        let manifest = root.join(MANIFEST_FILE);
+        let entries = if manifest.exists() {
+            serde_json::from_slice(&fs::read(&manifest)?)?
+        } else {
+            Vec::new()
+        };
+        Ok(ArtifactManager { root, entries })
+    }
+
+    pub fn entries(&self) -> &[ArtifactEntry] {
+        &self.entries
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.size).sum()
+    }
+
+    // Adds or updates the entry for an existing file, then saves the manifest.
+    pub fn register<P: AsRef<Path>>(&mut self, path: P, kind: ArtifactKind) -> Result<&ArtifactEntry, ArtifactError> {
+        let relative = self.relative(path.as_ref())?;
+        let metadata = fs::metadata(self.root.join(&relative))?;
+        let created = metadata.created().or_else(|_| metadata.modified())?;
+        let entry = ArtifactEntry {
+            path: relative,
+            kind,
+            size: metadata.len(),
+            created_unix: created.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+        };
+
+        self.entries.retain(|existing| existing.path != entry.path);
+        self.entries.push(entry);
+        self.save()?;
+        Ok(self.entries.last().expect("entry was just pushed"))
+    }
+
+    // Drops entries whose files are gone and updates sizes of the rest.
+    pub fn refresh(&mut self) -> Result<(), ArtifactError> {
+        let root = &self.root;
+        self.entries.retain_mut(|entry| match fs::metadata(root.join(&entry.path)) {
+            Ok(metadata) => {
+                entry.size = metadata.len();
+                true
+            }
+            Err(_) => false,
+        });
+        self.save()
+    }
+
+    // Entries `policy` would remove, in deletion order.
+    pub fn plan(&self, policy: &RetentionPolicy, now: SystemTime) -> Vec<ArtifactEntry> {
+        let mut candidates: Vec<&ArtifactEntry> =
+            self.entries.iter().filter(|entry| !policy.keep.contains(&entry.kind)).collect();
+        candidates.sort_by_key(|entry| entry.created_unix);
+
+        let mut removed = Vec::new();
+        let mut remaining = self.total_size();
+        for entry in candidates {
+            let expired = policy.max_age.is_some_and(|max_age| entry.age(now) > max_age);
+            let over_budget = policy.max_total_bytes.is_some_and(|budget| remaining > budget);
+            if expired || over_budget {
+                remaining -= entry.size;
+                removed.push(entry.clone());
+            }
+        }
+        removed
+    }
+
+    // Applies `policy`. With `dry_run` nothing is deleted, but the report
+    // still lists what would be.
+    pub fn clean(&mut self, policy: &RetentionPolicy, dry_run: bool) -> Result<CleanReport, ArtifactError> {
+        self.refresh()?;
+        let removed = self.plan(policy, SystemTime::now());
+        let freed_bytes = removed.iter().map(|entry| entry.size).sum();
+
+        if !dry_run {
+            for entry in &removed {
+                match fs::remove_file(self.root.join(&entry.path)) {
+                    Ok(()) => {}
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                    Err(e) => return Err(e.into()),
+                }
+                self.entries.retain(|existing| existing.path != entry.path);
+            }
+            self.save()?;
+        }
+
+        Ok(CleanReport {
+            remaining_bytes: self.total_size() - if dry_run { freed_bytes } else { 0 },
+            removed,
+            freed_bytes,
+        })
+    }
+
+    // A relative `path` is taken from the current directory, like every other
+    // path a writer hands over; only the stored entry is relative to the root.
+    fn relative(&self, path: &Path) -> Result<PathBuf, ArtifactError> {
+        let root = self.root.canonicalize()?;
+        let absolute = path.canonicalize()?;
+        absolute
+            .strip_prefix(&root)
+            .map(Path::to_path_buf)
+            .map_err(|_| ArtifactError::OutsideRoot(path.to_path_buf()))
+    }
+
+    // Same temporary-file-and-rename pattern as the experiment queue.
+    fn save(&self) -> Result<(), ArtifactError> {
+        let path = self.root.join(MANIFEST_FILE);
+        let tmp_path = path.with_extension("json.tmp");
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(&serde_json::to_vec_pretty(&self.entries)?)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use frost_ed25519::keys::PublicKeyPackage;
+use frost_ed25519::{Identifier, Signature, VerifyingKey};
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::frost::FrostRound1;
+
+// Structured outcome checks shared by the benches, the soak binary and the
+// CLI's --selfcheck mode. Each check returns the reason it failed instead of
+// panicking, so callers decide whether a failure is fatal.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AssertFailure {
+    SignatureInvalid,
+    GroupKeyChanged,
+    SignerOutsideCommittee(Identifier),
+    TooFewSigners { signers: usize, threshold: usize },
+    NonceReused(Identifier),
+    OutOfBounds { name: String, value: f64, min: f64, max: f64 },
+}
+
+impl fmt::Display for AssertFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssertFailure::SignatureInvalid => write!(f, "signature does not verify under the group key"),
+            AssertFailure::GroupKeyChanged => write!(f, "group verifying key changed"),
+            AssertFailure::SignerOutsideCommittee(id) => write!(f, "signer {id:?} is not a committee member"),
+            AssertFailure::TooFewSigners { signers, threshold } => {
+                write!(f, "{signers} signers is below the threshold {threshold}")
+            }
+            AssertFailure::NonceReused(id) => write!(f, "signer {id:?} reused a nonce commitment"),
+            AssertFailure::OutOfBounds { name, value, min, max } => {
+                write!(f, "{name} = {value} is outside [{min}, {max}]")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AssertFailure {}
+
+pub fn signature_verifies(public: &PublicKeyPackage, message: &[u8], signature: &Signature) -> Result<(), AssertFailure> {
+    public
+        .verifying_key()
+        .verify(message, signature)
+        .map_err(|_| AssertFailure::SignatureInvalid)
+}
+
+pub fn group_key_unchanged(before: &VerifyingKey, after: &PublicKeyPackage) -> Result<(), AssertFailure> {
+    if after.verifying_key() != before {
+        return Err(AssertFailure::GroupKeyChanged);
+    }
+    Ok(())
+}
+
+// Every signer is a committee member and there are at least `threshold` of them.
+pub fn signers_in_committee<'a, I>(signers: I, public: &PublicKeyPackage, threshold: usize) -> Result<(), AssertFailure>
+where
+    I: IntoIterator<Item = &'a Identifier>,
+{
+    let mut count = 0;
+    for signer in signers {
+        if !public.verifying_shares().contains_key(signer) {
+            return Err(AssertFailure::SignerOutsideCommittee(*signer));
+        }
+        count += 1;
+    }
+    if count < threshold {
+        return Err(AssertFailure::TooFewSigners {
+            signers: count,
+            threshold,
+        });
+    }
+    Ok(())
+}
+
+pub fn within_bounds(name: &str, value: f64, min: f64, max: f64) -> Result<(), AssertFailure> {
+    if !(min..=max).contains(&value) {
+        return Err(AssertFailure::OutOfBounds {
+            name: name.to_string(),
+            value,
+            min,
+            max,
+        });
+    }
+    Ok(())
+}
+
+// Remembers every commitment seen across sessions; a repeated commitment
+// means the signer reused its nonces.
+#[derive(Default)]
+pub struct NonceTracker {
+    seen: HashSet<Vec<u8>>,
+}
+
+impl NonceTracker {
+    pub fn new() -> Self {
+        NonceTracker::default()
+    }
+
+    pub fn observe(&mut self, round1: &FrostRound1) -> Result<(), AssertFailure> {
+        for (signer, commitments) in round1.commitments() {
+            let bytes = commitments.serialize().expect("generated commitments serialize");
+            if !self.seen.insert(bytes) {
+                return Err(AssertFailure::NonceReused(*signer));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Collects named check results, for reports that list every check.
+#[derive(Default)]
+pub struct CheckReport {
+    pub results: Vec<(String, Result<(), AssertFailure>)>,
+}
+
+impl CheckReport {
+    pub fn record(&mut self, name: &str, result: Result<(), AssertFailure>) {
+        self.results.push((name.to_string(), result));
+    }
+
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|(_, result)| result.is_ok())
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = (&str, &AssertFailure)> {
+        self.results
+            .iter()
+            .filter_map(|(name, result)| result.as_ref().err().map(|failure| (name.as_str(), failure)))
+    }
+}
@@ -0,0 +1,218 @@
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum AttestError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Encoding(bincode::Error),
+    Hex(hex::FromHexError),
+    Key(ed25519_dalek::SignatureError),
+    MalformedKey,
+    MalformedSignature,
+    UnexpectedSigner,
+    DigestMismatch(String),
+}
+
+impl fmt::Display for AttestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttestError::Io(e) => write!(f, "io error: {e}"),
+            AttestError::Json(e) => write!(f, "json error: {e}"),
+            AttestError::Encoding(e) => write!(f, "encoding error: {e}"),
+            AttestError::Hex(e) => write!(f, "hex error: {e}"),
+            AttestError::Key(e) => write!(f, "signature error: {e}"),
+            AttestError::MalformedKey => write!(f, "malformed ed25519 key"),
+            AttestError::MalformedSignature => write!(f, "malformed ed25519 signature"),
+            AttestError::UnexpectedSigner => write!(f, "manifest was signed by an unexpected key"),
+            AttestError::DigestMismatch(path) => write!(f, "digest mismatch for {path}"),
+        }
+    }
+}
+
+impl std::error::Error for AttestError {}
+
+impl From<io::Error> for AttestError {
+    fn from(e: io::Error) -> Self {
+        AttestError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for AttestError {
+    fn from(e: serde_json::Error) -> Self {
+        AttestError::Json(e)
+    }
+}
+
+impl From<bincode::Error> for AttestError {
+    fn from(e: bincode::Error) -> Self {
+        AttestError::Encoding(e)
+    }
+}
+
+impl From<hex::FromHexError> for AttestError {
+    fn from(e: hex::FromHexError) -> Self {
+        AttestError::Hex(e)
+    }
+}
+
+impl From<ed25519_dalek::SignatureError> for AttestError {
+    fn from(e: ed25519_dalek::SignatureError) -> Self {
+        AttestError::Key(e)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub run_id: String,
+    pub entries: Vec<ManifestEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedManifest {
+    pub manifest: Manifest,
+    pub public_key: String,
+    pub signature: String,
+}
+
+impl Manifest {
+    pub fn build<P: AsRef<Path>>(run_id: &str, paths: &[P]) -> Result<Manifest, AttestError> {
+        let mut entries = Vec::with_capacity(paths.len());
+        for path in paths {
+            let path = path.as_ref();
+            let contents = fs::read(path)?;
+            entries.push(ManifestEntry {
+                path: path.display().to_string(),
+                size: contents.len() as u64,
+                sha256: hex::encode(Sha256::digest(&contents)),
+            });
+        }
+        // Sorted so the signed bytes don't depend on argument order.
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(Manifest {
+            run_id: run_id.to_string(),
+            entries,
+        })
+    }
+
+    fn signing_bytes(&self) -> Result<Vec<u8>, AttestError> {
+        let mut bytes = b"thesis-artifact-manifest-v1".to_vec();
+        bytes.extend(bincode::serialize(self)?);
+        Ok(bytes)
+    }
+
+    pub fn sign(self, key: &SigningKey) -> Result<SignedManifest, AttestError> {
+        let signature = key.sign(&self.signing_bytes()?);
+        Ok(SignedManifest {
+            manifest: self,
+            public_key: hex::encode(key.verifying_key().to_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        })
+    }
+}
+
+impl SignedManifest {
+    pub fn verifying_key(&self) -> Result<VerifyingKey, AttestError> {
+        parse_verifying_key(&self.public_key)
+    }
+
+    // Checks the maintainer signature against the caller's trusted key, never
+    // the embedded one, and, if `check_files` is set, that every listed
+    // artifact on disk still matches its recorded digest.
+    pub fn verify(&self, trusted_key: &VerifyingKey, check_files: bool) -> Result<(), AttestError> {
+        if &self.verifying_key()? != trusted_key {
+            return Err(AttestError::UnexpectedSigner);
+        }
+        let signature_bytes: [u8; 64] = hex::decode(&self.signature)?
+            .try_into()
+            .map_err(|_| AttestError::MalformedSignature)?;
+        let signature = Signature::from_bytes(&signature_bytes);
+        trusted_key.verify(&self.manifest.signing_bytes()?, &signature)?;
+
+        if check_files {
+            for entry in &self.manifest.entries {
+                let contents = fs::read(&entry.path)?;
+                if hex::encode(Sha256::digest(&contents)) != entry.sha256 {
+                    return Err(AttestError::DigestMismatch(entry.path.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), AttestError> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<SignedManifest, AttestError> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+}
+
+pub fn read_signing_key<P: AsRef<Path>>(path: P) -> Result<SigningKey, AttestError> {
+    let bytes: [u8; 32] = hex::decode(fs::read_to_string(path)?.trim())?
+        .try_into()
+        .map_err(|_| AttestError::MalformedKey)?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+pub fn parse_verifying_key(public_key_hex: &str) -> Result<VerifyingKey, AttestError> {
+    let bytes: [u8; 32] = hex::decode(public_key_hex.trim())?
+        .try_into()
+        .map_err(|_| AttestError::MalformedKey)?;
+    Ok(VerifyingKey::from_bytes(&bytes)?)
+}
+
+// A trusted key given as hex on the command line or pinned in a file.
+pub fn load_trusted_key(hex_or_path: &str) -> Result<VerifyingKey, AttestError> {
+    let path = Path::new(hex_or_path);
+    if path.is_file() {
+        parse_verifying_key(&fs::read_to_string(path)?)
+    } else {
+        parse_verifying_key(hex_or_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifests_verify_only_under_the_trusted_key() {
+        let dir = std::env::temp_dir().join(format!("thesis-attest-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let artifact = dir.join("results.csv");
+        fs::write(&artifact, b"n,t\n7,5\n").unwrap();
+
+        let maintainer = SigningKey::from_bytes(&[7; 32]);
+        let other = SigningKey::from_bytes(&[8; 32]);
+        let signed = Manifest::build("run", &[&artifact]).unwrap().sign(&maintainer).unwrap();
+        let pinned = dir.join("maintainer.pub");
+        fs::write(&pinned, &signed.public_key).unwrap();
+        let trusted = load_trusted_key(pinned.to_str().unwrap()).unwrap();
+        assert_eq!(trusted, load_trusted_key(&signed.public_key).unwrap());
+        signed.verify(&trusted, true).unwrap();
+
+        // Re-signed by someone else, the manifest is self-consistent but not
+        // from the trusted signer.
+        let forged = signed.manifest.clone().sign(&other).unwrap();
+        assert!(matches!(forged.verify(&trusted, false), Err(AttestError::UnexpectedSigner)));
+
+        fs::write(&artifact, b"n,t\n7,4\n").unwrap();
+        assert!(matches!(signed.verify(&trusted, true), Err(AttestError::DigestMismatch(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
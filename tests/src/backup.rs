@@ -0,0 +1,158 @@
+use bip39::Mnemonic;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use ed25519_dalek::SigningKey;
+use frost_ed25519::{self as frost, keys::KeyPackage};
+use old_rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use std::fmt;
+
+#[derive(Debug)]
+pub enum BackupError {
+    Mnemonic(bip39::Error),
+    Decryption,
+    Frost(frost::Error),
+}
+
+impl fmt::Display for BackupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackupError::Mnemonic(e) => write!(f, "invalid mnemonic: {e}"),
+            BackupError::Decryption => write!(f, "backup could not be decrypted (wrong mnemonic or corrupted file)"),
+            BackupError::Frost(e) => write!(f, "frost error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for BackupError {}
+
+impl From<bip39::Error> for BackupError {
+    fn from(e: bip39::Error) -> Self {
+        BackupError::Mnemonic(e)
+    }
+}
+
+impl From<frost::Error> for BackupError {
+    fn from(e: frost::Error) -> Self {
+        BackupError::Frost(e)
+    }
+}
+
+// Keys an operator can recreate from the 24 words written down at setup: a
+// long-term ed25519 identity/transport key and the key encrypting the
+// KeyPackage backup. Each is derived from the BIP-39 seed under its own label.
+pub struct SignerSecrets {
+    pub identity: SigningKey,
+    backup_key: [u8; 32],
+}
+
+pub fn generate_mnemonic<RNG: RngCore + CryptoRng>(rng: &mut RNG) -> Result<Mnemonic, BackupError> {
+    let mut entropy = [0u8; 32];
+    rng.fill_bytes(&mut entropy);
+    Ok(Mnemonic::from_entropy(&entropy)?)
+}
+
+pub fn parse_mnemonic(words: &str) -> Result<Mnemonic, BackupError> {
+    Ok(Mnemonic::parse_normalized(words)?)
+}
+
+impl SignerSecrets {
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str) -> Self {
+        let seed = mnemonic.to_seed(passphrase);
+        SignerSecrets {
+            identity: SigningKey::from_bytes(&derive(&seed, b"thesis/identity")),
+            backup_key: derive(&seed, b"thesis/keypackage-backup"),
+        }
+    }
+
+    pub fn encrypt_backup<RNG: RngCore + CryptoRng>(
+        &self,
+        key_package: &KeyPackage,
+        rng: &mut RNG,
+    ) -> Result<EncryptedBackup, BackupError> {
+        let mut nonce = [0u8; 12];
+        rng.fill_bytes(&mut nonce);
+        let plaintext = key_package.serialize()?;
+        let ciphertext = self
+            .cipher()
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| BackupError::Decryption)?;
+        Ok(EncryptedBackup { nonce, ciphertext })
+    }
+
+    pub fn restore_backup(&self, backup: &EncryptedBackup) -> Result<KeyPackage, BackupError> {
+        let plaintext = self
+            .cipher()
+            .decrypt(Nonce::from_slice(&backup.nonce), backup.ciphertext.as_slice())
+            .map_err(|_| BackupError::Decryption)?;
+        Ok(KeyPackage::deserialize(&plaintext)?)
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(Key::from_slice(&self.backup_key))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedBackup {
+    pub nonce: [u8; 12],
+    pub ciphertext: Vec<u8>,
+}
+
+fn derive(seed: &[u8; 64], label: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha512::new();
+    hasher.update((label.len() as u64).to_le_bytes());
+    hasher.update(label);
+    hasher.update(seed);
+    let digest = hasher.finalize();
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&digest[..32]);
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn mnemonic_restores_identity_and_key_package() {
+        let mut rng = testing::rng(1);
+        let (_, packages) = testing::committee(1);
+        let key_package = packages.secret().values().next().unwrap();
+
+        let mnemonic = generate_mnemonic(&mut rng).unwrap();
+        assert_eq!(mnemonic.word_count(), 24);
+        let secrets = SignerSecrets::from_mnemonic(&mnemonic, "");
+        let backup = secrets.encrypt_backup(key_package, &mut rng).unwrap();
+
+        // Recovery only has the words written down at setup.
+        let recovered = SignerSecrets::from_mnemonic(&parse_mnemonic(&mnemonic.to_string()).unwrap(), "");
+        assert_eq!(recovered.identity.to_bytes(), secrets.identity.to_bytes());
+        assert_eq!(&recovered.restore_backup(&backup).unwrap(), key_package);
+    }
+
+    #[test]
+    fn wrong_passphrase_or_tampering_fails_to_decrypt() {
+        let mut rng = testing::rng(2);
+        let (_, packages) = testing::committee(2);
+        let key_package = packages.secret().values().next().unwrap();
+        let mnemonic = generate_mnemonic(&mut rng).unwrap();
+        let secrets = SignerSecrets::from_mnemonic(&mnemonic, "passphrase");
+        let backup = secrets.encrypt_backup(key_package, &mut rng).unwrap();
+
+        let other = SignerSecrets::from_mnemonic(&mnemonic, "other");
+        assert_ne!(other.identity.to_bytes(), secrets.identity.to_bytes());
+        assert!(matches!(other.restore_backup(&backup), Err(BackupError::Decryption)));
+
+        let mut tampered = backup.clone();
+        tampered.ciphertext[0] ^= 1;
+        assert!(matches!(secrets.restore_backup(&tampered), Err(BackupError::Decryption)));
+    }
+
+    #[test]
+    fn invalid_mnemonic_is_rejected() {
+        assert!(matches!(parse_mnemonic("not a mnemonic"), Err(BackupError::Mnemonic(_))));
+    }
+}
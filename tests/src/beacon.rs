@@ -0,0 +1,206 @@
+// Threshold randomness beacon in the style of drand's unchained mode: the
+// committee signs the round number with its Shamir-shared BLS key, and the
+// round's randomness is the hash of the group signature. BLS signatures are
+// unique, so any t members interpolate the same signature and no subset can
+// bias the output. FROST could not be used here: a Schnorr signature depends
+// on the signers' nonces, so the last contributor could grind the result.
+use bls12_381::{G1Affine, G2Affine, Scalar};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::bls::{self, BlsError, BlsPackage, BlsRound2, BlsSettings};
+
+#[derive(Debug)]
+pub enum BeaconError {
+    Bls(BlsError),
+    // A contribution or output for another round than the one requested.
+    WrongRound { expected: u64, got: u64 },
+    RandomnessMismatch(u64),
+}
+
+impl fmt::Display for BeaconError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BeaconError::Bls(e) => write!(f, "{e}"),
+            BeaconError::WrongRound { expected, got } => write!(f, "expected round {expected}, got round {got}"),
+            BeaconError::RandomnessMismatch(round) => {
+                write!(f, "randomness of round {round} is not the hash of its signature")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BeaconError {}
+
+impl From<BlsError> for BeaconError {
+    fn from(e: BlsError) -> Self {
+        BeaconError::Bls(e)
+    }
+}
+
+// The message signed in `round`: SHA-256 of the big-endian round number.
+pub fn round_message(round: u64) -> [u8; 32] {
+    Sha256::digest(round.to_be_bytes()).into()
+}
+
+// One member's partial signature for a round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Contribution {
+    pub round: u64,
+    pub index: u16,
+    pub partial: G1Affine,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconOutput {
+    pub round: u64,
+    // Compressed G1 group signature, hex.
+    pub signature: String,
+    // SHA-256 of the compressed signature, hex.
+    pub randomness: String,
+}
+
+fn randomness(signature: &G1Affine) -> [u8; 32] {
+    Sha256::digest(signature.to_compressed()).into()
+}
+
+// The public side of the beacon: enough to check contributions and outputs,
+// without any secret shares.
+#[derive(Clone, Debug)]
+pub struct Beacon {
+    settings: BlsSettings,
+    public: G2Affine,
+    verifying_shares: BTreeMap<u16, G2Affine>,
+}
+
+impl Beacon {
+    pub fn new(settings: &BlsSettings, packages: &BlsPackage) -> Self {
+        Beacon {
+            settings: settings.clone(),
+            public: *packages.public(),
+            verifying_shares: packages.verifying_shares().clone(),
+        }
+    }
+
+    pub fn public(&self) -> &G2Affine {
+        &self.public
+    }
+
+    // Run by member `index` with its own secret share.
+    pub fn contribute(&self, index: u16, share: &Scalar, round: u64) -> Contribution {
+        Contribution {
+            round,
+            index,
+            partial: (bls::hash_message(&round_message(round)) * share).into(),
+        }
+    }
+
+    // Combines the contributions for `round`. Every contribution is checked
+    // against its member's verifying share first, so one bad partial cannot
+    // spoil the round as long as t valid ones remain.
+    pub fn aggregate(&self, round: u64, contributions: &[Contribution]) -> Result<BeaconOutput, BeaconError> {
+        let message = bls::hash_message(&round_message(round));
+        let mut valid = BTreeMap::new();
+        for contribution in contributions {
+            if contribution.round != round {
+                return Err(BeaconError::WrongRound {
+                    expected: round,
+                    got: contribution.round,
+                });
+            }
+            let Some(key) = self.verifying_shares.get(&contribution.index) else {
+                continue;
+            };
+            if bls::verify_point(key, &message, &contribution.partial) {
+                valid.insert(contribution.index, contribution.partial);
+            }
+        }
+        let round2 = BlsRound2 {
+            message,
+            signature_shares: valid,
+        };
+        let signature = bls::aggregate(&self.settings, &round2)?;
+        Ok(BeaconOutput {
+            round,
+            signature: hex::encode(signature.to_compressed()),
+            randomness: hex::encode(randomness(&signature)),
+        })
+    }
+
+    pub fn verify_round(&self, round: u64, output: &BeaconOutput) -> Result<(), BeaconError> {
+        if output.round != round {
+            return Err(BeaconError::WrongRound {
+                expected: round,
+                got: output.round,
+            });
+        }
+        let bytes: [u8; 48] = hex::decode(&output.signature)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(BlsError::InvalidSignature)?;
+        let signature = Option::<G1Affine>::from(G1Affine::from_compressed(&bytes)).ok_or(BlsError::InvalidSignature)?;
+        bls::verify(&self.public, &round_message(round), &signature)?;
+        if hex::encode(randomness(&signature)) != output.randomness {
+            return Err(BeaconError::RandomnessMismatch(round));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::Params;
+    use crate::testing;
+
+    fn beacon() -> (Beacon, BlsPackage) {
+        let settings = BlsSettings::new(Params::new(7, 3).unwrap());
+        let package = bls::setup(&settings, &mut testing::rng(1)).unwrap();
+        (Beacon::new(&settings, &package), package)
+    }
+
+    fn contributions(beacon: &Beacon, package: &BlsPackage, round: u64) -> Vec<Contribution> {
+        package
+            .secret()
+            .iter()
+            .map(|(&index, share)| beacon.contribute(index, share, round))
+            .collect()
+    }
+
+    #[test]
+    fn disjoint_signer_sets_give_the_same_randomness() {
+        let (beacon, package) = beacon();
+        let round_one = contributions(&beacon, &package, 1);
+        let first = beacon.aggregate(1, &round_one[..3]).unwrap();
+        let last = beacon.aggregate(1, &round_one[4..]).unwrap();
+        assert_eq!(first, last);
+        beacon.verify_round(1, &first).unwrap();
+        assert!(matches!(beacon.verify_round(2, &first), Err(BeaconError::WrongRound { expected: 2, got: 1 })));
+
+        let next = beacon.aggregate(2, &contributions(&beacon, &package, 2)).unwrap();
+        assert_ne!(next.randomness, first.randomness);
+    }
+
+    #[test]
+    fn bad_contributions_are_dropped_and_outputs_checked() {
+        let (beacon, package) = beacon();
+        let mut contributions = contributions(&beacon, &package, 1);
+        contributions[0].partial = contributions[1].partial;
+        let output = beacon.aggregate(1, &contributions[..4]).unwrap();
+        beacon.verify_round(1, &output).unwrap();
+        assert!(matches!(
+            beacon.aggregate(1, &contributions[..3]),
+            Err(BeaconError::Bls(BlsError::TooFewShares { shares: 2, threshold: 3 }))
+        ));
+
+        let mut stale = contributions.clone();
+        stale[2].round = 0;
+        assert!(matches!(beacon.aggregate(1, &stale), Err(BeaconError::WrongRound { expected: 1, got: 0 })));
+
+        let mut tampered = output;
+        tampered.randomness = hex::encode([0u8; 32]);
+        assert!(matches!(beacon.verify_round(1, &tampered), Err(BeaconError::RandomnessMismatch(1))));
+    }
+}
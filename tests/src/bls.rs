@@ -0,0 +1,417 @@
+// Threshold BLS over BLS12-381, as a non-interactive baseline next to FROST
+// and multisig. Signatures live in G1 (48 bytes) and keys in G2, the
+// "minimal signature size" variant. A trusted dealer Shamir-shares the
+// secret key; any t partial signatures interpolate to the group signature.
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar, pairing};
+use ff::Field;
+use old_rand::{CryptoRng, RngCore};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::params::{Params, ParamsError};
+
+const DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+
+#[derive(Debug)]
+pub enum BlsError {
+    Params(ParamsError),
+    TooFewShares { shares: usize, threshold: usize },
+    ZeroIndex,
+    InvalidShare(u16),
+    InvalidSignature,
+}
+
+impl fmt::Display for BlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlsError::Params(e) => write!(f, "invalid parameters: {e}"),
+            BlsError::TooFewShares { shares, threshold } => {
+                write!(f, "{shares} partial signatures is below the threshold {threshold}")
+            }
+            BlsError::ZeroIndex => write!(f, "signer index 0 is the group secret, not a share"),
+            BlsError::InvalidShare(index) => write!(f, "partial signature of signer {index} does not verify"),
+            BlsError::InvalidSignature => write!(f, "signature does not verify under the group key"),
+        }
+    }
+}
+
+impl std::error::Error for BlsError {}
+
+impl From<ParamsError> for BlsError {
+    fn from(e: ParamsError) -> Self {
+        BlsError::Params(e)
+    }
+}
+
+// Unlike FROST, a threshold of one is fine here.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BlsSettings {
+    pub system_size: u16,
+    pub threshold: u16,
+}
+
+impl BlsSettings {
+    pub fn new(params: Params) -> Self {
+        BlsSettings {
+            system_size: params.system_size(),
+            threshold: params.threshold(),
+        }
+    }
+}
+
+impl crate::Settings for BlsSettings {
+    fn system_size(&self) -> u16 {
+        self.system_size
+    }
+
+    fn threshold(&self) -> u16 {
+        self.threshold
+    }
+}
+
+// Signers are indexed 1..=n, the evaluation points of the sharing polynomial.
+#[derive(Clone, Debug)]
+pub struct BlsPackage {
+    pub(crate) secret: BTreeMap<u16, Scalar>,
+    pub(crate) verifying_shares: BTreeMap<u16, G2Affine>,
+    pub(crate) public: G2Affine,
+}
+
+impl BlsPackage {
+    pub fn secret(&self) -> &BTreeMap<u16, Scalar> {
+        &self.secret
+    }
+    pub fn verifying_shares(&self) -> &BTreeMap<u16, G2Affine> {
+        &self.verifying_shares
+    }
+    pub fn public(&self) -> &G2Affine {
+        &self.public
+    }
+}
+
+pub struct BlsRound2 {
+    pub(crate) message: G1Affine,
+    pub(crate) signature_shares: BTreeMap<u16, G1Affine>,
+}
+
+impl BlsRound2 {
+    // Partial signatures as the coordinator received them, which may be more
+    // than t and need not be valid.
+    pub fn new(message: &[u8], signature_shares: BTreeMap<u16, G1Affine>) -> Self {
+        BlsRound2 {
+            message: hash_message(message),
+            signature_shares,
+        }
+    }
+
+    pub fn signature_shares(&self) -> &BTreeMap<u16, G1Affine> {
+        &self.signature_shares
+    }
+}
+
+pub fn hash_message(message: &[u8]) -> G1Affine {
+    <G1Projective as HashToCurve<ExpandMsgXmd<sha2_09::Sha256>>>::hash_to_curve(message, DST).into()
+}
+
+pub(crate) fn verify_point(key: &G2Affine, message: &G1Affine, signature: &G1Affine) -> bool {
+    pairing(signature, &G2Affine::generator()) == pairing(message, key)
+}
+
+pub fn setup<RNG>(settings: &BlsSettings, rng: &mut RNG) -> Result<BlsPackage, BlsError>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let params = Params::new(settings.system_size as usize, settings.threshold as usize)?;
+    let coefficients: Vec<Scalar> = (0..params.threshold()).map(|_| Scalar::random(&mut *rng)).collect();
+    // Horner evaluation of the sharing polynomial at x.
+    let evaluate = |x: Scalar| coefficients.iter().rev().fold(Scalar::zero(), |acc, c| acc * x + c);
+
+    let generator = G2Projective::generator();
+    let secret: BTreeMap<u16, Scalar> = (1..=params.system_size())
+        .map(|index| (index, evaluate(Scalar::from(index as u64))))
+        .collect();
+    let verifying_shares = secret
+        .iter()
+        .map(|(&index, share)| (index, G2Affine::from(generator * share)))
+        .collect();
+    Ok(BlsPackage {
+        secret,
+        verifying_shares,
+        public: G2Affine::from(generator * coefficients[0]),
+    })
+}
+
+// The first t signers each produce a partial signature; there is no
+// commitment round.
+pub fn sign_message(settings: &BlsSettings, packages: &BlsPackage, message: &[u8]) -> BlsRound2 {
+    let signers: Vec<u16> = packages.secret.keys().take(settings.threshold as usize).copied().collect();
+    sign_message_for(packages, &signers, message)
+}
+
+// Partial signatures from exactly `signers`; unknown indices are skipped.
+pub fn sign_message_for(packages: &BlsPackage, signers: &[u16], message: &[u8]) -> BlsRound2 {
+    let point = hash_message(message);
+    let signature_shares = signers
+        .iter()
+        .filter_map(|index| packages.secret.get(index).map(|share| (*index, G1Affine::from(point * share))))
+        .collect();
+    BlsRound2 {
+        message: point,
+        signature_shares,
+    }
+}
+
+// Lagrange coefficient of `index` for interpolation at zero over `indices`.
+fn lagrange_at_zero(index: u16, indices: &[u16]) -> Scalar {
+    let x_i = Scalar::from(index as u64);
+    let (numerator, denominator) = indices
+        .iter()
+        .filter(|&&other| other != index)
+        .fold((Scalar::one(), Scalar::one()), |(num, den), &other| {
+            let x_j = Scalar::from(other as u64);
+            (num * x_j, den * (x_j - x_i))
+        });
+    // Indices are distinct and nonzero, so the denominator is invertible.
+    numerator * denominator.invert().unwrap()
+}
+
+pub fn aggregate(settings: &BlsSettings, round2: &BlsRound2) -> Result<G1Affine, BlsError> {
+    let shares = &round2.signature_shares;
+    if shares.len() < settings.threshold as usize {
+        return Err(BlsError::TooFewShares {
+            shares: shares.len(),
+            threshold: settings.threshold as usize,
+        });
+    }
+    let indices: Vec<u16> = shares.keys().copied().collect();
+    if indices.contains(&0) {
+        return Err(BlsError::ZeroIndex);
+    }
+    Ok(interpolate(shares, &indices))
+}
+
+// Interpolates the partial signatures of `indices`, all present in `shares`
+// and nonzero, at zero.
+fn interpolate(shares: &BTreeMap<u16, G1Affine>, indices: &[u16]) -> G1Affine {
+    indices
+        .iter()
+        .fold(G1Projective::identity(), |acc, &index| {
+            acc + shares[&index] * lagrange_at_zero(index, indices)
+        })
+        .into()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RobustAggregate {
+    pub signature: G1Affine,
+    // The t signers whose partials make up the signature.
+    pub signers: Vec<u16>,
+    pub invalid_signers: BTreeSet<u16>,
+    // Pairing checks on groups of partials (a single partial counts as a
+    // group), and subsets interpolated.
+    pub share_checks: usize,
+    pub aggregations: usize,
+}
+
+// Sum of the partials of `indices` against the sum of their verifying
+// shares, e(sum sigma_i, g2) = e(H(m), sum pk_i). One pairing check covers
+// the whole group, so a bad share is found in O(log t) checks.
+fn verify_group(packages: &BlsPackage, round2: &BlsRound2, indices: &[u16], checks: &mut usize) -> bool {
+    *checks += 1;
+    let (signature, key) = indices.iter().fold(
+        (G1Projective::identity(), G2Projective::identity()),
+        |(signature, key), index| {
+            (
+                signature + round2.signature_shares[index],
+                key + packages.verifying_shares[index],
+            )
+        },
+    );
+    verify_point(&key.into(), &round2.message, &signature.into())
+}
+
+// Narrows a group that failed `verify_group` down to one bad signer. Shares
+// crafted to cancel out can pass as a group, so this may come back empty.
+fn bisect(packages: &BlsPackage, round2: &BlsRound2, indices: &[u16], checks: &mut usize) -> Option<u16> {
+    if let [index] = indices {
+        return Some(*index);
+    }
+    let (left, right) = indices.split_at(indices.len() / 2);
+    for half in [left, right] {
+        if !verify_group(packages, round2, half, checks) {
+            return bisect(packages, round2, half, checks);
+        }
+    }
+    None
+}
+
+// Aggregation when more than t partials arrive. Any t of them interpolate
+// to the group signature, so the coordinator tries the t lowest indices and,
+// only if that signature does not verify, bisects the subset for a bad
+// partial and retries with the next unused signer in its place. This is
+// where threshold BLS differs from FROST: FROST shares are bound to the full
+// signing package and cannot be recombined over a subset, so
+// `aggregation::sign_robust` opens a new session instead.
+pub fn aggregate_robust(
+    settings: &BlsSettings,
+    packages: &BlsPackage,
+    round2: &BlsRound2,
+) -> Result<RobustAggregate, BlsError> {
+    let threshold = settings.threshold as usize;
+    let mut candidates = Vec::with_capacity(round2.signature_shares.len());
+    for &index in round2.signature_shares.keys() {
+        if index == 0 {
+            return Err(BlsError::ZeroIndex);
+        }
+        if !packages.verifying_shares.contains_key(&index) {
+            return Err(BlsError::InvalidShare(index));
+        }
+        candidates.push(index);
+    }
+    let mut invalid_signers = BTreeSet::new();
+    let mut share_checks = 0;
+    let mut aggregations = 0;
+
+    while candidates.len() >= threshold {
+        let signers = &candidates[..threshold];
+        aggregations += 1;
+        let signature = interpolate(&round2.signature_shares, signers);
+        if verify_point(&packages.public, &round2.message, &signature) {
+            return Ok(RobustAggregate {
+                signature,
+                signers: signers.to_vec(),
+                invalid_signers,
+                share_checks,
+                aggregations,
+            });
+        }
+        let bad = match bisect(packages, round2, signers, &mut share_checks) {
+            Some(index) => vec![index],
+            None => signers
+                .iter()
+                .copied()
+                .filter(|index| !verify_group(packages, round2, &[*index], &mut share_checks))
+                .collect(),
+        };
+        if bad.is_empty() {
+            // Every partial verifies on its own, so the interpolation cannot fail.
+            return Err(BlsError::InvalidSignature);
+        }
+        tracing::warn!(signers = ?bad, "partial signature rejected");
+        candidates.retain(|index| !bad.contains(index));
+        invalid_signers.extend(bad);
+    }
+    Err(BlsError::TooFewShares {
+        shares: candidates.len(),
+        threshold,
+    })
+}
+
+// Checks every partial signature against its verifying share; aggregation
+// itself does not, so a bad share only shows up as an invalid group signature.
+pub fn verify_shares(packages: &BlsPackage, round2: &BlsRound2) -> Result<(), BlsError> {
+    for (&index, share) in &round2.signature_shares {
+        let key = packages.verifying_shares.get(&index).ok_or(BlsError::InvalidShare(index))?;
+        if !verify_point(key, &round2.message, share) {
+            return Err(BlsError::InvalidShare(index));
+        }
+    }
+    Ok(())
+}
+
+pub fn verify(public: &G2Affine, message: &[u8], signature: &G1Affine) -> Result<(), BlsError> {
+    if !verify_point(public, &hash_message(message), signature) {
+        return Err(BlsError::InvalidSignature);
+    }
+    Ok(())
+}
+
+pub fn aggregate_verify(
+    settings: &BlsSettings,
+    packages: &BlsPackage,
+    round2: &BlsRound2,
+    message: &[u8],
+) -> Result<(), BlsError> {
+    let signature = aggregate(settings, round2)?;
+    verify(&packages.public, message, &signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    const MESSAGE: &[u8] = b"robust";
+
+    fn committee() -> (BlsSettings, BlsPackage) {
+        let settings = BlsSettings::new(Params::new(7, 4).unwrap());
+        let package = setup(&settings, &mut testing::rng(1)).unwrap();
+        (settings, package)
+    }
+
+    // Signer `index` sends the partial of its successor, which decodes fine
+    // but does not verify under its own share.
+    fn corrupt(round2: &BlsRound2, bad: &[u16]) -> BlsRound2 {
+        let shares = round2.signature_shares();
+        let mut corrupted = shares.clone();
+        for index in bad {
+            let next = shares.keys().copied().find(|other| other > index).unwrap_or(*shares.keys().next().unwrap());
+            corrupted.insert(*index, shares[&next]);
+        }
+        BlsRound2::new(MESSAGE, corrupted)
+    }
+
+    #[test]
+    fn any_t_partials_give_the_same_signature() {
+        let (settings, package) = committee();
+        let first = aggregate(&settings, &sign_message_for(&package, &[1, 2, 3, 4], MESSAGE)).unwrap();
+        let last = aggregate(&settings, &sign_message_for(&package, &[4, 5, 6, 7], MESSAGE)).unwrap();
+        assert_eq!(first, last);
+        verify(package.public(), MESSAGE, &first).unwrap();
+        assert!(verify(package.public(), b"other", &first).is_err());
+    }
+
+    #[test]
+    fn one_bad_partial_is_routed_around() {
+        let (settings, package) = committee();
+        let signers = [1, 2, 3, 4, 5, 6];
+        let round2 = sign_message_for(&package, &signers, MESSAGE);
+
+        let clean = aggregate_robust(&settings, &package, &round2).unwrap();
+        assert_eq!((clean.aggregations, clean.share_checks), (1, 0));
+        assert_eq!(clean.signers, vec![1, 2, 3, 4]);
+
+        for bad in signers {
+            let corrupted = corrupt(&round2, &[bad]);
+            let outcome = aggregate_robust(&settings, &package, &corrupted).unwrap();
+            verify(package.public(), MESSAGE, &outcome.signature).unwrap();
+            assert!(!outcome.signers.contains(&bad));
+            // A bad partial beyond the first t is never looked at.
+            let expected = if bad <= 4 { BTreeSet::from([bad]) } else { BTreeSet::new() };
+            assert_eq!(outcome.invalid_signers, expected);
+            assert!(matches!(verify_shares(&package, &corrupted), Err(BlsError::InvalidShare(index)) if index == bad));
+        }
+    }
+
+    #[test]
+    fn too_many_bad_partials_fail() {
+        let (settings, package) = committee();
+        let round2 = sign_message_for(&package, &[1, 2, 3, 4, 5, 6], MESSAGE);
+        let outcome = aggregate_robust(&settings, &package, &corrupt(&round2, &[1, 2, 3]));
+        assert!(matches!(outcome, Err(BlsError::TooFewShares { threshold: 4, .. })));
+    }
+
+    #[test]
+    fn unknown_signers_are_rejected() {
+        let (settings, package) = committee();
+        let round2 = sign_message_for(&package, &[1, 2, 3, 4], MESSAGE);
+        let mut shares = round2.signature_shares().clone();
+        shares.insert(8, shares[&1]);
+        let outcome = aggregate_robust(&settings, &package, &BlsRound2::new(MESSAGE, shares.clone()));
+        assert!(matches!(outcome, Err(BlsError::InvalidShare(8))));
+        shares.insert(0, shares[&1]);
+        let outcome = aggregate_robust(&settings, &package, &BlsRound2::new(MESSAGE, shares));
+        assert!(matches!(outcome, Err(BlsError::ZeroIndex)));
+    }
+}
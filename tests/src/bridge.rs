@@ -0,0 +1,214 @@
+// A FROST committee on one ciphersuite certifies the group key of a committee
+// on another, modelling cross-chain validator attestations. Certificates
+// carry serialized keys and signatures plus the ciphersuite IDs, so a chain
+// of them can be verified without knowing the suites at compile time.
+use frost_core::keys::PublicKeyPackage;
+use frost_core::{Ciphersuite, VerifyingKey};
+use frost_ed25519::Ed25519Sha512;
+use frost_secp256k1::Secp256K1Sha256;
+use old_rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::frost::{FrostPackage, FrostSettings, generic};
+use crate::hash::HashFunction;
+use crate::transcript::Transcript;
+
+#[derive(Debug)]
+pub enum BridgeError {
+    Ed25519(frost_ed25519::Error),
+    Secp256k1(frost_secp256k1::Error),
+    UnknownSuite(String),
+    UntrustedRoot,
+    BrokenChain(usize),
+}
+
+impl fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BridgeError::Ed25519(e) => write!(f, "ed25519 frost error: {e}"),
+            BridgeError::Secp256k1(e) => write!(f, "secp256k1 frost error: {e}"),
+            BridgeError::UnknownSuite(id) => write!(f, "unsupported ciphersuite {id}"),
+            BridgeError::UntrustedRoot => write!(f, "first certificate is not issued by the trusted root key"),
+            BridgeError::BrokenChain(i) => write!(f, "certificate {i} is not issued by the subject of the previous one"),
+        }
+    }
+}
+
+impl std::error::Error for BridgeError {}
+
+impl From<frost_ed25519::Error> for BridgeError {
+    fn from(e: frost_ed25519::Error) -> Self {
+        BridgeError::Ed25519(e)
+    }
+}
+
+impl From<frost_secp256k1::Error> for BridgeError {
+    fn from(e: frost_secp256k1::Error) -> Self {
+        BridgeError::Secp256k1(e)
+    }
+}
+
+// A group key together with the ciphersuite it belongs to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuiteKey {
+    pub suite: String,
+    pub key: Vec<u8>,
+}
+
+impl SuiteKey {
+    pub fn of<C: Ciphersuite>(key: &VerifyingKey<C>) -> Result<SuiteKey, BridgeError>
+    where
+        frost_core::Error<C>: Into<BridgeError>,
+    {
+        Ok(SuiteKey {
+            suite: C::ID.to_string(),
+            key: key.serialize().map_err(Into::into)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossCertificate {
+    pub certifier: SuiteKey,
+    pub subject: SuiteKey,
+    pub signature: Vec<u8>,
+}
+
+fn certificate_bytes(certifier: &SuiteKey, subject: &SuiteKey) -> Vec<u8> {
+    let mut transcript = Transcript::with_hash(HashFunction::default(), b"committee-bridge");
+    transcript
+        .append_message(b"certifier-suite", certifier.suite.as_bytes())
+        .append_message(b"certifier-key", &certifier.key)
+        .append_message(b"subject-suite", subject.suite.as_bytes())
+        .append_message(b"subject-key", &subject.key);
+    transcript.signing_bytes()
+}
+
+// Has the `certifier` committee sign the group key of `subject`.
+pub fn certify<C, S, RNG>(
+    settings: &FrostSettings,
+    certifier: &FrostPackage<C>,
+    subject: &PublicKeyPackage<S>,
+    rng: &mut RNG,
+) -> Result<CrossCertificate, BridgeError>
+where
+    C: Ciphersuite,
+    S: Ciphersuite,
+    RNG: RngCore + CryptoRng,
+    frost_core::Error<C>: Into<BridgeError>,
+    frost_core::Error<S>: Into<BridgeError>,
+{
+    let certifier_key = SuiteKey::of(certifier.public().verifying_key())?;
+    let subject_key = SuiteKey::of(subject.verifying_key())?;
+    let message = certificate_bytes(&certifier_key, &subject_key);
+
+    let signature = sign_bytes(settings, certifier, &message, rng).map_err(Into::into)?;
+    Ok(CrossCertificate {
+        certifier: certifier_key,
+        subject: subject_key,
+        signature,
+    })
+}
+
+fn sign_bytes<C, RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage<C>,
+    message: &[u8],
+    rng: &mut RNG,
+) -> Result<Vec<u8>, frost_core::Error<C>>
+where
+    C: Ciphersuite,
+    RNG: RngCore + CryptoRng,
+{
+    let round1 = generic::vote_commitments(settings, packages, rng)?;
+    let round2 = generic::sign_message(settings, packages, &round1, message)?;
+    frost_core::aggregate(round2.signing_package(), round2.signature_shares(), packages.public())?.serialize()
+}
+
+fn verify_typed<C: Ciphersuite>(key: &[u8], message: &[u8], signature: &[u8]) -> Result<(), frost_core::Error<C>> {
+    let key = VerifyingKey::<C>::deserialize(key)?;
+    let signature = frost_core::Signature::<C>::deserialize(signature)?;
+    key.verify(message, &signature)
+}
+
+impl CrossCertificate {
+    pub fn verify(&self) -> Result<(), BridgeError> {
+        let message = certificate_bytes(&self.certifier, &self.subject);
+        let (key, signature) = (&self.certifier.key, &self.signature);
+        match self.certifier.suite.as_str() {
+            id if id == Ed25519Sha512::ID => Ok(verify_typed::<Ed25519Sha512>(key, &message, signature)?),
+            id if id == Secp256K1Sha256::ID => Ok(verify_typed::<Secp256K1Sha256>(key, &message, signature)?),
+            other => Err(BridgeError::UnknownSuite(other.to_string())),
+        }
+    }
+}
+
+// Verifies a chain starting at `root`: each certificate must be valid and
+// issued by the subject of the one before it. Returns the last subject.
+pub fn verify_chain<'a>(root: &'a SuiteKey, chain: &'a [CrossCertificate]) -> Result<&'a SuiteKey, BridgeError> {
+    let mut expected = root;
+    for (index, certificate) in chain.iter().enumerate() {
+        if &certificate.certifier != expected {
+            return Err(if index == 0 {
+                BridgeError::UntrustedRoot
+            } else {
+                BridgeError::BrokenChain(index)
+            });
+        }
+        certificate.verify()?;
+        expected = &certificate.subject;
+    }
+    Ok(expected)
+}
+
+// ed25519 certifies secp256k1, which certifies ed25519 back; the chain is
+// verified from the ed25519 root and must end at the ed25519 key again.
+pub fn run_bridge<RNG>(settings: &FrostSettings, rng: &mut RNG) -> Result<Vec<CrossCertificate>, BridgeError>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let ed25519 = generic::setup::<Ed25519Sha512, _>(settings, rng)?;
+    let secp256k1 = generic::setup::<Secp256K1Sha256, _>(settings, rng)?;
+
+    let chain = vec![
+        certify(settings, &ed25519, secp256k1.public(), rng)?,
+        certify(settings, &secp256k1, ed25519.public(), rng)?,
+    ];
+    let root = SuiteKey::of(ed25519.public().verifying_key())?;
+    if verify_chain(&root, &chain)? != &root {
+        return Err(BridgeError::BrokenChain(chain.len()));
+    }
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn tampered_certificates_break_the_chain() {
+        let settings = testing::settings(4, 3);
+        let mut rng = testing::rng(1);
+        let chain = run_bridge(&settings, &mut rng).unwrap();
+        let root = chain[0].certifier.clone();
+        assert_eq!(chain[0].subject.suite, Secp256K1Sha256::ID);
+        assert_eq!(verify_chain(&root, &chain).unwrap(), &root);
+
+        let mut forged = chain.clone();
+        forged[1].signature[0] ^= 1;
+        assert!(matches!(verify_chain(&root, &forged), Err(BridgeError::Secp256k1(_))));
+
+        // A different subject key no longer matches the signed transcript,
+        // and the next certificate is no longer issued by the subject.
+        let mut substituted = chain.clone();
+        substituted[0].subject.key = chain[1].subject.key.clone();
+        assert!(matches!(verify_chain(&root, &substituted), Err(BridgeError::Ed25519(_))));
+        substituted[0].subject = chain[0].subject.clone();
+        substituted[1].certifier.key[1] ^= 1;
+        assert!(matches!(verify_chain(&root, &substituted), Err(BridgeError::BrokenChain(1))));
+
+        assert!(matches!(verify_chain(&chain[1].certifier, &chain), Err(BridgeError::UntrustedRoot)));
+    }
+}
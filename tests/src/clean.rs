@@ -0,0 +1,89 @@
+use std::env;
+use std::process;
+use std::time::Duration;
+use thesis::artifacts::{ArtifactError, ArtifactKind, ArtifactManager, RetentionPolicy};
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+fn usage() -> ! {
+    eprintln!("Usage:");
+    eprintln!("  clean list <artifact-dir>");
+    eprintln!("  clean register <artifact-dir> <kind> <file>...");
+    eprintln!("  clean run <artifact-dir> [--max-age-days N] [--max-total-gb N] [--keep KIND]... [--dry-run]");
+    eprintln!("Kinds: signature_shard, key_store, event_log, result, other");
+    process::exit(2);
+}
+
+fn parse_policy(args: &[String]) -> (RetentionPolicy, bool) {
+    let mut policy = RetentionPolicy::default();
+    let mut dry_run = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dry-run" => dry_run = true,
+            "--max-age-days" => {
+                let days: u64 = args.next().and_then(|v| v.parse().ok()).unwrap_or_else(|| usage());
+                policy.max_age = Some(Duration::from_secs(days * 24 * 60 * 60));
+            }
+            "--max-total-gb" => {
+                let gb: f64 = args.next().and_then(|v| v.parse().ok()).unwrap_or_else(|| usage());
+                policy.max_total_bytes = Some((gb * GIB) as u64);
+            }
+            "--keep" => {
+                let kind = args.next().and_then(|v| v.parse().ok()).unwrap_or_else(|| usage());
+                policy.keep.push(kind);
+            }
+            _ => usage(),
+        }
+    }
+    (policy, dry_run)
+}
+
+fn list(manager: &ArtifactManager) {
+    for entry in manager.entries() {
+        println!("{:>15} {:>12} {}", entry.kind.name(), entry.size, entry.path.display());
+    }
+    println!("Total: {} artifacts, {:.2} GiB", manager.entries().len(), manager.total_size() as f64 / GIB);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (Some(command), Some(root)) = (args.first(), args.get(1)) else { usage() };
+
+    let result = ArtifactManager::open(root).and_then(|mut manager| match command.as_str() {
+        "list" => {
+            manager.refresh()?;
+            list(&manager);
+            Ok(())
+        }
+        "register" if args.len() >= 4 => {
+            let kind: ArtifactKind = args[2].parse()?;
+            for file in &args[3..] {
+                manager.register(file, kind)?;
+            }
+            Ok(())
+        }
+        "run" => {
+            let (policy, dry_run) = parse_policy(&args[2..]);
+            let report = manager.clean(&policy, dry_run)?;
+            let verb = if dry_run { "would remove" } else { "removed" };
+            for entry in &report.removed {
+                println!("{} {}", verb, entry.path.display());
+            }
+            println!(
+                "{} {} artifacts, {:.2} GiB freed, {:.2} GiB remaining",
+                verb,
+                report.removed.len(),
+                report.freed_bytes as f64 / GIB,
+                report.remaining_bytes as f64 / GIB
+            );
+            Ok::<(), ArtifactError>(())
+        }
+        _ => usage(),
+    });
+
+    if let Err(e) = result {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,80 @@
+use clap::Parser;
+use std::path::{Path, PathBuf};
+use std::process;
+use thesis::results::{self, Metric, ResultsError};
+
+// Collects the latest Criterion estimates and the exported sizes into one
+// results file, and optionally compares them against an earlier one.
+#[derive(Parser)]
+#[command(name = "export_results", about = "Export benchmark results and check them against a baseline")]
+struct Cli {
+    /// Criterion output directory
+    #[arg(long, default_value = "target/criterion")]
+    dir: PathBuf,
+    /// Where to write the results; defaults to <dir>/results.json
+    #[arg(long)]
+    out: Option<PathBuf>,
+    /// Results file of an earlier run to compare against
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+    /// Relative growth of a metric that counts as a regression
+    #[arg(long, default_value_t = 0.05)]
+    threshold: f64,
+}
+
+fn collect(dir: &Path) -> Result<Vec<Metric>, ResultsError> {
+    let mut metrics = results::criterion_metrics(dir)?;
+    // sizes.json only exists once `reports sizes` has run.
+    match results::read_sizes(dir) {
+        Ok(sizes) => metrics.extend(results::size_metrics(&sizes)),
+        Err(ResultsError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    Ok(metrics)
+}
+
+fn run(cli: &Cli) -> Result<bool, ResultsError> {
+    let metrics = collect(&cli.dir)?;
+    let out = cli.out.clone().unwrap_or_else(|| results::results_path(&cli.dir));
+    results::write_results(&out, &metrics)?;
+    println!("Wrote {} metrics to {}", metrics.len(), out.display());
+
+    let Some(baseline) = &cli.baseline else {
+        return Ok(true);
+    };
+    let (deltas, unmatched) = results::compare(&results::read_results(baseline)?, &metrics, cli.threshold);
+    let mut regressions = 0;
+    for delta in &deltas {
+        let flag = if delta.regression { "REGRESSION" } else { "" };
+        println!(
+            "{:<60} {:>14.1} -> {:>14.1} {:<5} {:>+8.2}% {}",
+            delta.name, delta.baseline, delta.current, delta.unit, delta.relative_change * 100.0, flag
+        );
+        if delta.regression {
+            regressions += 1;
+        }
+    }
+    for name in &unmatched {
+        println!("{name:<60} only in one of the runs");
+    }
+    println!(
+        "{} metrics compared, {} regressed beyond {:.1}%, {} unmatched",
+        deltas.len(),
+        regressions,
+        cli.threshold * 100.0,
+        unmatched.len()
+    );
+    Ok(regressions == 0)
+}
+
+fn main() {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,447 @@
+use frost::round1::SigningCommitments;
+use frost::round2::SignatureShare;
+use frost_ed25519::{self as frost, Signature, SigningPackage};
+use old_rand::{CryptoRng, Rng, RngCore};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use crate::frost::{FrostPackage, FrostSettings};
+use crate::rng::{RngProvider, SeededRngProvider};
+
+// Flips one random bit in each byte of a message with probability `rate`,
+// modelling corruption of serialized protocol messages in transit.
+#[derive(Clone, Copy, Debug)]
+pub struct BitFlipInjector {
+    pub rate: f64,
+}
+
+impl BitFlipInjector {
+    pub fn new(rate: f64) -> Self {
+        BitFlipInjector {
+            rate: rate.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn corrupt<RNG: RngCore>(&self, bytes: &mut [u8], rng: &mut RNG) -> usize {
+        let mut flipped = 0;
+        for byte in bytes.iter_mut() {
+            if rng.gen_bool(self.rate) {
+                *byte ^= 1 << rng.gen_range(0..8);
+                flipped += 1;
+            }
+        }
+        flipped
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FaultStats {
+    pub runs: usize,
+    pub messages: usize,
+    pub corrupted_messages: usize,
+    pub rejected_on_decode: usize,
+    pub rejected_by_signer: usize,
+    pub rejected_on_aggregate: usize,
+    pub rejected_on_verify: usize,
+    pub accepted_forgeries: usize,
+    pub panics: usize,
+}
+
+impl FaultStats {
+    pub fn merge(&mut self, other: &FaultStats) {
+        self.runs += other.runs;
+        self.messages += other.messages;
+        self.corrupted_messages += other.corrupted_messages;
+        self.rejected_on_decode += other.rejected_on_decode;
+        self.rejected_by_signer += other.rejected_by_signer;
+        self.rejected_on_aggregate += other.rejected_on_aggregate;
+        self.rejected_on_verify += other.rejected_on_verify;
+        self.accepted_forgeries += other.accepted_forgeries;
+        self.panics += other.panics;
+    }
+
+    pub fn detected(&self) -> usize {
+        self.rejected_on_decode + self.rejected_by_signer + self.rejected_on_aggregate + self.rejected_on_verify
+    }
+
+    fn transmit<RNG: RngCore>(&mut self, bytes: &mut [u8], injector: Option<&BitFlipInjector>, rng: &mut RNG) -> bool {
+        self.messages += 1;
+        let corrupted = injector.is_some_and(|injector| injector.corrupt(bytes, rng) > 0);
+        if corrupted {
+            self.corrupted_messages += 1;
+        }
+        corrupted
+    }
+}
+
+// Which protocol messages pass through an injector; `None` means delivered intact.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FaultPlan {
+    pub commitments: Option<BitFlipInjector>,
+    pub shares: Option<BitFlipInjector>,
+    pub signature: Option<BitFlipInjector>,
+}
+
+impl FaultPlan {
+    pub fn everywhere(injector: BitFlipInjector) -> Self {
+        FaultPlan {
+            commitments: Some(injector),
+            shares: Some(injector),
+            signature: Some(injector),
+        }
+    }
+
+    pub fn only(kind: FaultKind, injector: BitFlipInjector) -> Self {
+        match kind {
+            FaultKind::CommitmentBitFlip => FaultPlan {
+                commitments: Some(injector),
+                ..FaultPlan::default()
+            },
+            FaultKind::ShareBitFlip => FaultPlan {
+                shares: Some(injector),
+                ..FaultPlan::default()
+            },
+            FaultKind::SignatureBitFlip => FaultPlan {
+                signature: Some(injector),
+                ..FaultPlan::default()
+            },
+        }
+    }
+}
+
+// Runs one FROST signing where every commitment, share and the final
+// signature pass through the injector. A run stops at the first stage that
+// rejects its input; a corrupted signature that still verifies is counted as
+// an accepted forgery.
+pub fn run_with_bit_flips<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    message: &[u8],
+    injector: &BitFlipInjector,
+    rng: &mut RNG,
+) -> Result<FaultStats, frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    run_with_faults(settings, packages, message, &FaultPlan::everywhere(*injector), rng)
+}
+
+pub fn run_with_faults<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    message: &[u8],
+    plan: &FaultPlan,
+    rng: &mut RNG,
+) -> Result<FaultStats, frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let mut stats = FaultStats {
+        runs: 1,
+        ..FaultStats::default()
+    };
+    let round1 = crate::frost::vote_commitments(settings, packages, rng)?;
+
+    let mut commitments = BTreeMap::new();
+    for (identifier, commitment) in round1.commitments() {
+        let mut bytes = commitment.serialize()?;
+        stats.transmit(&mut bytes, plan.commitments.as_ref(), rng);
+        match SigningCommitments::deserialize(&bytes) {
+            Ok(commitment) => commitments.insert(*identifier, commitment),
+            Err(_) => {
+                stats.rejected_on_decode += 1;
+                return Ok(stats);
+            }
+        };
+    }
+    let signing_package = SigningPackage::new(commitments, message);
+
+    let mut signature_shares = BTreeMap::new();
+    for (identifier, nonces) in round1.nonces() {
+        let key_package = &packages.secret()[identifier];
+        let signature_share = match frost::round2::sign(&signing_package, nonces, key_package) {
+            Ok(signature_share) => signature_share,
+            Err(_) => {
+                stats.rejected_by_signer += 1;
+                return Ok(stats);
+            }
+        };
+        let mut bytes = signature_share.serialize();
+        stats.transmit(&mut bytes, plan.shares.as_ref(), rng);
+        match SignatureShare::deserialize(&bytes) {
+            Ok(signature_share) => signature_shares.insert(*identifier, signature_share),
+            Err(_) => {
+                stats.rejected_on_decode += 1;
+                return Ok(stats);
+            }
+        };
+    }
+
+    let group_signature = match frost::aggregate(&signing_package, &signature_shares, packages.public()) {
+        Ok(group_signature) => group_signature,
+        Err(_) => {
+            stats.rejected_on_aggregate += 1;
+            return Ok(stats);
+        }
+    };
+
+    let mut bytes = group_signature.serialize()?;
+    let corrupted = stats.transmit(&mut bytes, plan.signature.as_ref(), rng);
+    let received = match Signature::deserialize(&bytes) {
+        Ok(signature) => signature,
+        Err(_) => {
+            stats.rejected_on_decode += 1;
+            return Ok(stats);
+        }
+    };
+    match packages.public().verifying_key().verify(message, &received) {
+        Ok(()) if corrupted && received != group_signature => stats.accepted_forgeries += 1,
+        Ok(()) => {}
+        Err(_) => stats.rejected_on_verify += 1,
+    }
+    Ok(stats)
+}
+
+pub fn bit_flip_campaign<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    message: &[u8],
+    injector: &BitFlipInjector,
+    runs: usize,
+    rng: &mut RNG,
+) -> FaultStats
+where
+    RNG: RngCore + CryptoRng,
+{
+    let mut total = FaultStats::default();
+    for _ in 0..runs {
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+            run_with_bit_flips(settings, packages, message, injector, rng)
+        }));
+        match outcome {
+            Ok(Ok(stats)) => total.merge(&stats),
+            // Errors outside the injected messages (e.g. serializing our own
+            // values) still end the run cleanly.
+            Ok(Err(_)) => total.runs += 1,
+            Err(_) => {
+                total.runs += 1;
+                total.panics += 1;
+            }
+        }
+    }
+    total
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum FaultKind {
+    CommitmentBitFlip,
+    ShareBitFlip,
+    SignatureBitFlip,
+}
+
+impl FaultKind {
+    pub const ALL: [FaultKind; 3] = [
+        FaultKind::CommitmentBitFlip,
+        FaultKind::ShareBitFlip,
+        FaultKind::SignatureBitFlip,
+    ];
+}
+
+// One row of the fault/effect correlation table: for a fault type, where the
+// corruption was detected, how many extra sessions it took to still produce a
+// signature, and the latency cost relative to fault-free sessions.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CorrelationRow {
+    pub fault: FaultKind,
+    pub seeds: usize,
+    pub trials: usize,
+    pub corrupted_messages: usize,
+    pub detected_on_decode: usize,
+    pub detected_by_signer: usize,
+    pub detected_on_aggregate: usize,
+    pub detected_on_verify: usize,
+    pub accepted_forgeries: usize,
+    pub panics: usize,
+    pub mean_extra_sessions: f64,
+    pub mean_latency_delta_us: f64,
+    pub gave_up: usize,
+}
+
+// Sessions per trial before a trial counts as failed.
+const MAX_SESSIONS: usize = 100;
+
+// Repeats the signing session until one completes without any detected fault.
+fn trial<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    message: &[u8],
+    plan: &FaultPlan,
+    total: &mut FaultStats,
+    rng: &mut RNG,
+) -> (usize, Duration, bool)
+where
+    RNG: RngCore + CryptoRng,
+{
+    let start = Instant::now();
+    for sessions in 1..=MAX_SESSIONS {
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| run_with_faults(settings, packages, message, plan, rng)));
+        match outcome {
+            Ok(Ok(stats)) => {
+                total.merge(&stats);
+                if stats.detected() == 0 {
+                    return (sessions, start.elapsed(), true);
+                }
+            }
+            Ok(Err(_)) => total.runs += 1,
+            Err(_) => {
+                total.runs += 1;
+                total.panics += 1;
+            }
+        }
+    }
+    (MAX_SESSIONS, start.elapsed(), false)
+}
+
+// Every seed deals its own committee from the seed's first stream and runs
+// its sessions on the second, so the rows aggregate over independent keys
+// and each fault type sees the same sessions.
+pub fn correlation_report(
+    settings: &FrostSettings,
+    message: &[u8],
+    injector: BitFlipInjector,
+    seeds: &[u64],
+    trials_per_seed: usize,
+) -> Result<Vec<CorrelationRow>, frost::Error> {
+    let committees = seeds
+        .iter()
+        .map(|&seed| {
+            let mut provider = SeededRngProvider::new(seed);
+            let packages = crate::frost::setup(settings, &mut provider.rng())?;
+            Ok((packages, provider))
+        })
+        .collect::<Result<Vec<_>, frost::Error>>()?;
+
+    // Fault-free sessions give the latency baseline.
+    let mut baseline_total = Duration::ZERO;
+    let mut baseline_trials = 0u32;
+    for (packages, provider) in &committees {
+        let mut rng = provider.clone().rng();
+        for _ in 0..trials_per_seed {
+            let mut ignored = FaultStats::default();
+            baseline_total += trial(settings, packages, message, &FaultPlan::default(), &mut ignored, &mut rng).1;
+            baseline_trials += 1;
+        }
+    }
+    let baseline = baseline_total.as_secs_f64() * 1e6 / baseline_trials.max(1) as f64;
+
+    Ok(FaultKind::ALL
+        .iter()
+        .map(|&fault| {
+            let plan = FaultPlan::only(fault, injector);
+            let mut total = FaultStats::default();
+            let mut extra_sessions = 0;
+            let mut latency_us = 0.0;
+            let mut gave_up = 0;
+            for (packages, provider) in &committees {
+                let mut rng = provider.clone().rng();
+                for _ in 0..trials_per_seed {
+                    let (sessions, elapsed, succeeded) = trial(settings, packages, message, &plan, &mut total, &mut rng);
+                    extra_sessions += sessions - 1;
+                    latency_us += elapsed.as_secs_f64() * 1e6;
+                    if !succeeded {
+                        gave_up += 1;
+                    }
+                }
+            }
+            let trials = seeds.len() * trials_per_seed;
+            CorrelationRow {
+                fault,
+                seeds: seeds.len(),
+                trials,
+                corrupted_messages: total.corrupted_messages,
+                detected_on_decode: total.rejected_on_decode,
+                detected_by_signer: total.rejected_by_signer,
+                detected_on_aggregate: total.rejected_on_aggregate,
+                detected_on_verify: total.rejected_on_verify,
+                accepted_forgeries: total.accepted_forgeries,
+                panics: total.panics,
+                mean_extra_sessions: extra_sessions as f64 / trials.max(1) as f64,
+                mean_latency_delta_us: latency_us / trials.max(1) as f64 - baseline,
+                gave_up,
+            }
+        })
+        .collect())
+}
+
+pub fn write_correlation_csv<W: io::Write>(rows: &[CorrelationRow], writer: W) -> Result<(), csv::Error> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn every_corrupted_message_is_rejected() {
+        let (settings, package) = testing::committee(1);
+        let mut rng = testing::rng(1);
+        for kind in FaultKind::ALL {
+            for rate in [0.02, 1.0] {
+                let plan = FaultPlan::only(kind, BitFlipInjector::new(rate));
+                for _ in 0..10 {
+                    // A panic anywhere in the pipeline fails the test.
+                    let stats = run_with_faults(&settings, &package, b"faults", &plan, &mut rng).unwrap();
+                    assert_eq!(stats.accepted_forgeries, 0, "{kind:?} at {rate}");
+                    assert_eq!(stats.detected(), usize::from(stats.corrupted_messages > 0), "{kind:?} at {rate}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn campaign_counts_no_panics_or_forgeries() {
+        let (settings, package) = testing::committee(2);
+        let mut rng = testing::rng(2);
+        let stats = bit_flip_campaign(&settings, &package, b"faults", &BitFlipInjector::new(0.01), 20, &mut rng);
+        assert_eq!(stats.runs, 20);
+        assert_eq!((stats.panics, stats.accepted_forgeries), (0, 0));
+        assert!(stats.corrupted_messages > 0);
+        assert!(stats.detected() <= stats.corrupted_messages);
+
+        let clean = bit_flip_campaign(&settings, &package, b"faults", &BitFlipInjector::new(0.0), 5, &mut rng);
+        assert_eq!((clean.corrupted_messages, clean.detected()), (0, 0));
+    }
+
+    #[test]
+    fn correlation_rows_cover_every_fault_reproducibly() {
+        let settings = testing::settings(5, 3);
+        let injector = BitFlipInjector::new(0.005);
+        let rows = correlation_report(&settings, b"faults", injector, &[1, 2], 2).unwrap();
+        assert_eq!(rows.iter().map(|row| row.fault).collect::<Vec<_>>(), FaultKind::ALL);
+        for row in &rows {
+            assert_eq!((row.seeds, row.trials), (2, 4));
+            assert_eq!((row.accepted_forgeries, row.panics, row.gave_up), (0, 0, 0));
+            let detected =
+                row.detected_on_decode + row.detected_by_signer + row.detected_on_aggregate + row.detected_on_verify;
+            assert!(detected <= row.corrupted_messages);
+            // Every detection costs exactly one extra session.
+            assert_eq!((row.mean_extra_sessions * row.trials as f64).round() as usize, detected);
+        }
+
+        // Only the timings change between runs of the same seeds.
+        let counts = |rows: &[CorrelationRow]| -> Vec<(usize, usize, f64)> {
+            rows.iter().map(|row| (row.corrupted_messages, row.detected_on_decode, row.mean_extra_sessions)).collect()
+        };
+        let again = correlation_report(&settings, b"faults", injector, &[1, 2], 2).unwrap();
+        assert_eq!(counts(&rows), counts(&again));
+    }
+}
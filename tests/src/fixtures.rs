@@ -0,0 +1,201 @@
+use frost_core::keys::{KeyPackage, PublicKeyPackage, VerifiableSecretSharingCommitment};
+use frost_core::{Ciphersuite, Error};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::artifacts::{ArtifactError, ArtifactKind, ArtifactManager};
+use crate::frost::{FrostPackage, FrostSettings};
+use crate::rng::{RngProvider, SeededRngProvider};
+
+const VERSION: u8 = 1;
+
+#[derive(Debug)]
+pub enum FixtureError<C: Ciphersuite> {
+    Io(io::Error),
+    Bincode(bincode::Error),
+    Frost(Error<C>),
+    Artifact(ArtifactError),
+    UnsupportedVersion(u8),
+    // The cached package is for different parameters than its file name says.
+    Stale(PathBuf),
+}
+
+impl<C: Ciphersuite> fmt::Display for FixtureError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixtureError::Io(e) => write!(f, "io error: {e}"),
+            FixtureError::Bincode(e) => write!(f, "bincode error: {e}"),
+            FixtureError::Frost(e) => write!(f, "frost error: {e}"),
+            FixtureError::Artifact(e) => write!(f, "{e}"),
+            FixtureError::UnsupportedVersion(v) => write!(f, "unsupported fixture version {v}"),
+            FixtureError::Stale(path) => write!(f, "{} does not match its parameters, regenerate it", path.display()),
+        }
+    }
+}
+
+impl<C: Ciphersuite> std::error::Error for FixtureError<C> {}
+
+impl<C: Ciphersuite> From<io::Error> for FixtureError<C> {
+    fn from(e: io::Error) -> Self {
+        FixtureError::Io(e)
+    }
+}
+
+impl<C: Ciphersuite> From<bincode::Error> for FixtureError<C> {
+    fn from(e: bincode::Error) -> Self {
+        FixtureError::Bincode(e)
+    }
+}
+
+impl<C: Ciphersuite> From<Error<C>> for FixtureError<C> {
+    fn from(e: Error<C>) -> Self {
+        FixtureError::Frost(e)
+    }
+}
+
+impl<C: Ciphersuite> From<ArtifactError> for FixtureError<C> {
+    fn from(e: ArtifactError) -> Self {
+        FixtureError::Artifact(e)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Keygen {
+    Dealer,
+    Dkg,
+}
+
+impl Keygen {
+    pub fn name(self) -> &'static str {
+        match self {
+            Keygen::Dealer => "dealer",
+            Keygen::Dkg => "dkg",
+        }
+    }
+}
+
+// Whether `FixtureCache::load` read the package or had to generate it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixtureSource {
+    Cached,
+    Generated,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "C: Ciphersuite")]
+struct StoredPackage<C: Ciphersuite> {
+    version: u8,
+    shares: Vec<KeyPackage<C>>,
+    public: PublicKeyPackage<C>,
+    commitment: Option<VerifiableSecretSharingCommitment<C>>,
+}
+
+// On-disk cache of generated key material, keyed by ciphersuite, key
+// generation method, (n, t) and seed. Generation uses stream 0 of
+// `SeededRngProvider::new(seed)`, so a cached package is exactly what a
+// fresh run with that seed would produce and cache hits do not change
+// results. Dealer and DKG setup at n = 100 take long enough that re-running
+// them for every benchmark configuration dominates the run time.
+//
+// Multisig committees are not cached: `KeypairShare` generation takes no
+// seed and is cheap next to FROST key generation.
+#[derive(Clone, Debug)]
+pub struct FixtureCache {
+    dir: PathBuf,
+    regenerate: bool,
+    artifacts: Option<PathBuf>,
+}
+
+impl FixtureCache {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        FixtureCache {
+            dir: dir.as_ref().to_path_buf(),
+            regenerate: false,
+            artifacts: None,
+        }
+    }
+
+    // Ignore (and overwrite) existing fixtures, e.g. after a frost upgrade
+    // changed the serialization.
+    pub fn with_regenerate(mut self, regenerate: bool) -> Self {
+        self.regenerate = regenerate;
+        self
+    }
+
+    // Register newly generated fixtures as key stores in the artifact
+    // directory `root`, which must contain the cache directory.
+    pub fn with_artifacts<P: AsRef<Path>>(mut self, root: P) -> Self {
+        self.artifacts = Some(root.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn path<C: Ciphersuite>(&self, keygen: Keygen, settings: &FrostSettings, seed: u64) -> PathBuf {
+        self.dir.join(format!(
+            "{}-{}-n{}-t{}-seed{}.bin",
+            C::ID.to_lowercase(),
+            keygen.name(),
+            settings.system_size(),
+            settings.threshold(),
+            seed
+        ))
+    }
+
+    pub fn load<C: Ciphersuite>(
+        &self,
+        keygen: Keygen,
+        settings: &FrostSettings,
+        seed: u64,
+    ) -> Result<(FrostPackage<C>, FixtureSource), FixtureError<C>> {
+        let path = self.path::<C>(keygen, settings, seed);
+        if !self.regenerate && path.exists() {
+            return Ok((read_package(&path, settings)?, FixtureSource::Cached));
+        }
+        let mut rng = SeededRngProvider::new(seed).rng();
+        let packages = match keygen {
+            Keygen::Dealer => crate::frost::generic::setup(settings, &mut rng)?,
+            Keygen::Dkg => crate::frost::generic::setup_dkg(settings, &mut rng)?,
+        };
+        fs::create_dir_all(&self.dir)?;
+        write_package(&path, &packages)?;
+        if let Some(root) = &self.artifacts {
+            ArtifactManager::open(root)?.register(&path, ArtifactKind::KeyStore)?;
+        }
+        Ok((packages, FixtureSource::Generated))
+    }
+}
+
+fn write_package<C: Ciphersuite>(path: &Path, packages: &FrostPackage<C>) -> Result<(), FixtureError<C>> {
+    let stored = StoredPackage {
+        version: VERSION,
+        shares: packages.secret.values().cloned().collect(),
+        public: packages.public.clone(),
+        commitment: packages.commitment.clone(),
+    };
+    // Written under a temporary name first, so an interrupted run never
+    // leaves a truncated fixture behind.
+    let partial = path.with_extension("partial");
+    fs::write(&partial, bincode::serialize(&stored)?)?;
+    fs::rename(partial, path)?;
+    Ok(())
+}
+
+fn read_package<C: Ciphersuite>(path: &Path, settings: &FrostSettings) -> Result<FrostPackage<C>, FixtureError<C>> {
+    let stored: StoredPackage<C> = bincode::deserialize(&fs::read(path)?)?;
+    if stored.version != VERSION {
+        return Err(FixtureError::UnsupportedVersion(stored.version));
+    }
+    let matches = stored.shares.len() == settings.system_size() as usize
+        && stored.public.verifying_shares().len() == settings.system_size() as usize
+        && stored.shares.iter().all(|share| *share.min_signers() == settings.threshold());
+    if !matches {
+        return Err(FixtureError::Stale(path.to_path_buf()));
+    }
+    Ok(FrostPackage {
+        secret: stored.shares.into_iter().map(|share| (*share.identifier(), share)).collect(),
+        public: stored.public,
+        commitment: stored.commitment,
+    })
+}
@@ -0,0 +1,129 @@
+use frost_ed25519::{self as frost, Signature, keys::PublicKeyPackage};
+use old_rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::frost::{FrostPackage, FrostRound1, FrostRound2, FrostSettings};
+use crate::transcript::Transcript;
+
+const SESSION_DOMAIN: &[u8] = b"thesis-session-v1";
+
+// Chosen fresh by the coordinator for every signing session. Signers sign the
+// framed message, so a signature from one session never verifies as the
+// output of another session over the same payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SessionContext {
+    pub session_nonce: [u8; 32],
+}
+
+impl SessionContext {
+    pub fn random<RNG: RngCore + CryptoRng>(rng: &mut RNG) -> Self {
+        let mut session_nonce = [0u8; 32];
+        rng.fill_bytes(&mut session_nonce);
+        SessionContext { session_nonce }
+    }
+
+    pub fn frame(&self, message: &[u8]) -> Vec<u8> {
+        let mut transcript = Transcript::new(SESSION_DOMAIN);
+        transcript
+            .append_message(b"session_nonce", &self.session_nonce)
+            .append_message(b"message", message);
+        transcript.signing_bytes()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionSignature {
+    pub session: SessionContext,
+    pub signature: Signature,
+}
+
+pub fn sign_in_session(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    round1: &FrostRound1,
+    session: &SessionContext,
+    message: &[u8],
+) -> Result<FrostRound2, frost::Error> {
+    crate::frost::sign_message(settings, packages, round1, &session.frame(message))
+}
+
+pub fn aggregate_in_session(
+    packages: &FrostPackage,
+    round2: &FrostRound2,
+    session: &SessionContext,
+) -> Result<SessionSignature, frost::Error> {
+    let signature = frost::aggregate(round2.signing_package(), round2.signature_shares(), packages.public())?;
+    Ok(SessionSignature {
+        session: *session,
+        signature,
+    })
+}
+
+// Verifies that `signature` is the output of `expected_session` for `message`.
+pub fn verify_in_session(
+    public: &PublicKeyPackage,
+    expected_session: &SessionContext,
+    message: &[u8],
+    signature: &SessionSignature,
+) -> Result<(), frost::Error> {
+    if &signature.session != expected_session {
+        return Err(frost::Error::InvalidSignature);
+    }
+    public
+        .verifying_key()
+        .verify(&expected_session.frame(message), &signature.signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HashFunction;
+    use crate::testing;
+    use crate::transcript::payload_transcript;
+
+    fn session_signature(session: &SessionContext, message: &[u8]) -> (PublicKeyPackage, SessionSignature) {
+        let (settings, package) = testing::committee(1);
+        let mut rng = testing::rng(2);
+        let round1 = crate::frost::vote_commitments(&settings, &package, &mut rng).unwrap();
+        let round2 = sign_in_session(&settings, &package, &round1, session, message).unwrap();
+        (package.public().clone(), aggregate_in_session(&package, &round2, session).unwrap())
+    }
+
+    #[test]
+    fn signature_verifies_in_its_own_session() {
+        let session = SessionContext::random(&mut testing::rng(3));
+        let (public, signature) = session_signature(&session, b"payload");
+        verify_in_session(&public, &session, b"payload", &signature).unwrap();
+        assert!(verify_in_session(&public, &session, b"other payload", &signature).is_err());
+    }
+
+    #[test]
+    fn signature_does_not_replay_into_another_session() {
+        let mut rng = testing::rng(4);
+        let (first, second) = (SessionContext::random(&mut rng), SessionContext::random(&mut rng));
+        let (public, signature) = session_signature(&first, b"payload");
+        assert!(verify_in_session(&public, &second, b"payload", &signature).is_err());
+
+        // Relabelling the signature with the other session does not help either.
+        let relabelled = SessionSignature {
+            session: second,
+            ..signature
+        };
+        assert!(verify_in_session(&public, &second, b"payload", &relabelled).is_err());
+    }
+
+    #[test]
+    fn frames_do_not_collide() {
+        let session = SessionContext {
+            session_nonce: [7; 32],
+        };
+        assert_ne!(session.frame(b"ab"), session.frame(b"a"));
+        assert_ne!(session.frame(b""), session.frame(&[0; 8]));
+        // Neither with the payload transcript of the same message nor with
+        // one whose payload carries the nonce.
+        assert_ne!(session.frame(b"payload"), payload_transcript(HashFunction::default(), b"payload"));
+        let mut smuggled = session.session_nonce.to_vec();
+        smuggled.extend_from_slice(b"payload");
+        assert_ne!(session.frame(b"payload"), payload_transcript(HashFunction::default(), &smuggled));
+    }
+}
@@ -1,14 +1,49 @@
-use frost::keys::{KeyPackage, PublicKeyPackage};
-use frost::round1::{SigningCommitments, SigningNonces};
-use frost::round2::SignatureShare;
-use frost_ed25519::{self as frost, Identifier, SigningPackage};
+use frost_core::keys::{KeyPackage, PublicKeyPackage, VerifiableSecretSharingCommitment};
+use frost_core::round1::{SigningCommitments, SigningNonces};
+use frost_core::round2::SignatureShare;
+use frost_core::{Ciphersuite, Identifier, SigningPackage};
+use frost_ed25519::{self as frost, Ed25519Sha512};
 use old_rand::{CryptoRng, RngCore};
 use std::collections::BTreeMap;
+use std::io::Write;
+
+use crate::hash::HashFunction;
+use crate::params::{Params, ParamsError};
+
+pub mod generic;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct FrostSettings {
-    pub system_size: u16,
-    pub threshold: u16,
+    system_size: u16,
+    threshold: u16,
+    hash: HashFunction,
+}
+
+impl FrostSettings {
+    pub fn new(params: Params, hash: HashFunction) -> Result<Self, ParamsError> {
+        let params = params.check_frost()?;
+        Ok(FrostSettings {
+            system_size: params.system_size(),
+            threshold: params.threshold(),
+            hash,
+        })
+    }
+
+    pub fn params(&self) -> Result<Params, ParamsError> {
+        Params::new(self.system_size as usize, self.threshold as usize)?.check_frost()
+    }
+
+    pub fn system_size(&self) -> u16 {
+        self.system_size
+    }
+
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    pub fn hash(&self) -> HashFunction {
+        self.hash
+    }
 }
 
 impl crate::Settings for FrostSettings {
@@ -19,47 +54,65 @@ impl crate::Settings for FrostSettings {
     fn threshold(&self) -> u16 {
         self.threshold
     }
+
+    fn hash_function(&self) -> HashFunction {
+        self.hash
+    }
 }
 
+// The package and round types are generic over the FROST ciphersuite and
+// default to ed25519, which is what the rest of the crate uses.
 #[derive(Clone, Debug)]
-pub struct FrostPackage {
-    pub(crate) secret: BTreeMap<Identifier, KeyPackage>,
-    pub(crate) public: PublicKeyPackage,
+pub struct FrostPackage<C: Ciphersuite = Ed25519Sha512> {
+    pub(crate) secret: BTreeMap<Identifier<C>, KeyPackage<C>>,
+    pub(crate) public: PublicKeyPackage<C>,
+    // Only a trusted dealer publishes a single commitment vector; with DKG each
+    // participant checks the others' shares during `dkg::part3` instead.
+    pub(crate) commitment: Option<VerifiableSecretSharingCommitment<C>>,
 }
 
-impl FrostPackage {
-    pub fn secret(&self) -> &BTreeMap<Identifier, KeyPackage> {
+impl<C: Ciphersuite> FrostPackage<C> {
+    pub fn secret(&self) -> &BTreeMap<Identifier<C>, KeyPackage<C>> {
         &self.secret
     }
-    pub fn public(&self) -> &PublicKeyPackage {
+    pub fn public(&self) -> &PublicKeyPackage<C> {
         &self.public
     }
+    pub fn commitment(&self) -> Option<&VerifiableSecretSharingCommitment<C>> {
+        self.commitment.as_ref()
+    }
+
+    // The VSS commitment is public, so it can be published next to the
+    // PublicKeyPackage for participants to check their shares against.
+    pub fn persist_commitment<W: Write>(&self, writer: W) -> bincode::Result<()> {
+        bincode::serialize_into(writer, &self.commitment)
+    }
 }
 
-pub struct FrostRound1 {
-    pub(crate) nonces: BTreeMap<Identifier, SigningNonces>,
-    pub(crate) commitments: BTreeMap<Identifier, SigningCommitments>,
+pub struct FrostRound1<C: Ciphersuite = Ed25519Sha512> {
+    pub(crate) nonces: BTreeMap<Identifier<C>, SigningNonces<C>>,
+    pub(crate) commitments: BTreeMap<Identifier<C>, SigningCommitments<C>>,
 }
 
-impl FrostRound1 {
-    pub fn nonces(&self) -> &BTreeMap<Identifier, SigningNonces> {
+impl<C: Ciphersuite> FrostRound1<C> {
+    pub fn nonces(&self) -> &BTreeMap<Identifier<C>, SigningNonces<C>> {
         &self.nonces
     }
-    pub fn commitments(&self) -> &BTreeMap<Identifier, SigningCommitments> {
+    pub fn commitments(&self) -> &BTreeMap<Identifier<C>, SigningCommitments<C>> {
         &self.commitments
     }
 }
 
-pub struct FrostRound2 {
-    pub(crate) signing_package: SigningPackage,
-    pub(crate) signature_shares: BTreeMap<Identifier, SignatureShare>,
+pub struct FrostRound2<C: Ciphersuite = Ed25519Sha512> {
+    pub(crate) signing_package: SigningPackage<C>,
+    pub(crate) signature_shares: BTreeMap<Identifier<C>, SignatureShare<C>>,
 }
 
-impl FrostRound2 {
-    pub fn signing_package(&self) -> &SigningPackage {
+impl<C: Ciphersuite> FrostRound2<C> {
+    pub fn signing_package(&self) -> &SigningPackage<C> {
         &self.signing_package
     }
-    pub fn signature_shares(&self) -> &BTreeMap<Identifier, SignatureShare> {
+    pub fn signature_shares(&self) -> &BTreeMap<Identifier<C>, SignatureShare<C>> {
         &self.signature_shares
     }
 }
@@ -68,30 +121,56 @@ pub fn setup<RNG>(settings: &FrostSettings, rng: &mut RNG) -> Result<FrostPackag
 where
     RNG: RngCore + CryptoRng,
 {
-    let max_signers = settings.system_size;
-    let min_signers = settings.threshold;
-    let (shares, pubkey_package) = frost::keys::generate_with_dealer(
-        max_signers,
-        min_signers,
-        frost::keys::IdentifierList::Default,
-        rng,
-    )?;
-
-    // Verifies the secret shares from the dealer and store them in a BTreeMap.
-    // In practice, the KeyPackages must be sent to its respective participants
-    // through a confidential and authenticated channel.
-    let mut key_packages: BTreeMap<_, _> = BTreeMap::new();
-
-    for (identifier, secret_share) in shares {
-        // ANCHOR: tkg_verify
-        let key_package = frost::keys::KeyPackage::try_from(secret_share)?;
-        // ANCHOR_END: tkg_verify
-        key_packages.insert(identifier, key_package);
-    }
-    Ok(FrostPackage {
-        secret: key_packages,
-        public: pubkey_package,
-    })
+    generic::setup(settings, rng)
+}
+
+pub fn setup_with_identifiers<RNG>(
+    settings: &FrostSettings,
+    identifiers: &[frost::Identifier],
+    rng: &mut RNG,
+) -> Result<FrostPackage, frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    generic::setup_with_identifiers(settings, identifiers, rng)
+}
+
+pub fn verify_share_against_commitments(
+    key_package: &frost::keys::KeyPackage,
+    commitment: &frost::keys::VerifiableSecretSharingCommitment,
+) -> Result<(), frost::Error> {
+    generic::verify_share_against_commitments(key_package, commitment)
+}
+
+pub fn validate_shares(packages: &FrostPackage) -> Result<(), frost::Error> {
+    generic::validate_shares(packages)
+}
+
+pub fn setup_dkg<RNG>(settings: &FrostSettings, rng: &mut RNG) -> Result<FrostPackage, frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    generic::setup_dkg(settings, rng)
+}
+
+pub fn refresh<RNG>(settings: &FrostSettings, packages: &FrostPackage, rng: &mut RNG) -> Result<FrostPackage, frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    generic::refresh(settings, packages, rng)
+}
+
+pub fn reshare<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    new_settings: &FrostSettings,
+    new_identifiers: &[frost::Identifier],
+    rng: &mut RNG,
+) -> Result<FrostPackage, frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    generic::reshare(settings, packages, new_settings, new_identifiers, rng)
 }
 
 pub fn vote_commitments<RNG>(
@@ -102,118 +181,51 @@ pub fn vote_commitments<RNG>(
 where
     RNG: RngCore + CryptoRng,
 {
-    let mut nonces_map = BTreeMap::new();
-    let mut commitments_map = BTreeMap::new();
-
-    ////////////////////////////////////////////////////////////////////////////
-    // Round 1: generating nonces and signing commitments for each participant
-    ////////////////////////////////////////////////////////////////////////////
-
-    // In practice, each iteration of this loop will be executed by its respective participant.
-    for participant_index in 1..=settings.threshold {
-        let participant_identifier = participant_index.try_into().expect("should be nonzero");
-        let key_package = &packages.secret[&participant_identifier];
-        // Generate one (1) nonce and one SigningCommitments instance for each
-        // participant, up to _threshold_.
-        // ANCHOR: round1_commit
-        let (nonces, commitments) = frost::round1::commit(key_package.signing_share(), rng);
-        // ANCHOR_END: round1_commit
-        // In practice, the nonces must be kept by the participant to use in the
-        // next round, while the commitment must be sent to the coordinator
-        // (or to every other participant if there is no coordinator) using
-        // an authenticated channel.
-        nonces_map.insert(participant_identifier, nonces);
-        commitments_map.insert(participant_identifier, commitments);
-    }
-    let nonces = nonces_map;
-    let commitments = commitments_map;
-    Ok(FrostRound1 {
-        nonces,
-        commitments,
-    })
+    generic::vote_commitments(settings, packages, rng)
+}
+
+// Same as `vote_commitments`, but for an explicitly chosen signer subset
+// (e.g. the one picked by `latency::LatencyMatrix::select_signers`).
+pub fn vote_commitments_for<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    signers: &[frost::Identifier],
+    rng: &mut RNG,
+) -> Result<FrostRound1, frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    generic::vote_commitments_for(settings, packages, signers, rng)
 }
 
 pub fn sign_message(
-    _settings: &FrostSettings,
+    settings: &FrostSettings,
     packages: &FrostPackage,
     round1: &FrostRound1,
     message: &[u8],
 ) -> Result<FrostRound2, frost::Error> {
-    // This is what the signature aggregator / coordinator needs to do:
-    // - decide what message to sign
-    // - take one (unused) commitment per signing participant
-    let mut signature_shares = BTreeMap::new();
-    // ANCHOR: round2_package
-
-    // In practice, the SigningPackage must be sent to all participants
-    // involved in the current signing (at least min_signers participants),
-    // using an authenticate channel (and confidential if the message is secret).
-    let signing_package = frost::SigningPackage::new(round1.commitments.clone(), message);
-    // ANCHOR_END: round2_package
-
-    ////////////////////////////////////////////////////////////////////////////
-    // Round 2: each participant generates their signature share
-    ////////////////////////////////////////////////////////////////////////////
-
-    // In practice, each iteration of this loop will be executed by its respective participant.
-    for participant_identifier in round1.nonces.keys() {
-        let key_package = &packages.secret[participant_identifier];
-
-        let nonces = &round1.nonces[participant_identifier];
-
-        // Each participant generates their signature share.
-        // ANCHOR: round2_sign
-        let signature_share = frost::round2::sign(&signing_package, nonces, key_package)?;
-        // ANCHOR_END: round2_sign
-
-        // In practice, the signature share must be sent to the Coordinator
-        // using an authenticated channel.
-        signature_shares.insert(*participant_identifier, signature_share);
-    }
-    Ok(FrostRound2 {
-        signing_package,
-        signature_shares,
-    })
+    generic::sign_message(settings, packages, round1, message)
 }
 
 pub fn aggregate_verify(
-    _settings: &FrostSettings,
+    settings: &FrostSettings,
     packages: &FrostPackage,
-    _round1: &FrostRound1,
+    round1: &FrostRound1,
     round2: &FrostRound2,
     message: &[u8],
 ) -> Result<(), frost::Error> {
-    // Aggregate (also verifies the signature shares)
-    // ANCHOR: aggregate
-    let group_signature = frost::aggregate(
-        &round2.signing_package,
-        &round2.signature_shares,
-        &packages.public,
-    )?;
-    // ANCHOR_END: aggregate
-
-    // Check that the threshold signature can be verified by the group public
-    // key (the verification key).
-    // ANCHOR: verify
-    let is_signature_valid = packages
-        .public
-        .verifying_key()
-        .verify(message, &group_signature)
-        .is_ok();
-    // ANCHOR_END: verify
-    assert!(is_signature_valid);
-    Ok(())
+    generic::aggregate_verify(settings, packages, round1, round2, message)
 }
 
-pub fn frost_example(max_faulty: u16) -> Result<(), frost::Error> {
-    let settings = FrostSettings {
-        system_size: 3 * max_faulty + 1,
-        threshold: 2 * max_faulty + 1,
-    };
-    let mut rng = old_rand::thread_rng();
-
-    let package = setup(&settings, &mut rng)?;
-    let round1 = vote_commitments(&settings, &package, &mut rng)?;
+pub fn frost_example<RNG>(max_faulty: u16, rng: &mut RNG) -> Result<(), frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let params = Params::bft(max_faulty as usize).map_err(|_| frost::Error::InvalidMaxSigners)?;
+    let settings =
+        FrostSettings::new(params, HashFunction::default()).map_err(|_| frost::Error::InvalidMinSigners)?;
+    let package = setup(&settings, rng)?;
+    let round1 = vote_commitments(&settings, &package, rng)?;
 
     let message = b"message to sign";
 
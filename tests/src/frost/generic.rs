@@ -0,0 +1,605 @@
+// Ciphersuite-generic versions of the FROST harness. The functions in the
+// parent module are the ed25519 instantiations used throughout the crate;
+// these run the same code against any `frost_core::Ciphersuite` (e.g.
+// secp256k1, ristretto255, P-256 behind their cargo features).
+use frost_core::keys::{KeyPackage, SecretShare, VerifiableSecretSharingCommitment};
+use frost_core::{self as frost, Ciphersuite, Error, Field, Group, GroupError, Identifier};
+use old_rand::{CryptoRng, RngCore};
+use std::collections::BTreeMap;
+
+use super::{FrostPackage, FrostRound1, FrostRound2, FrostSettings};
+
+pub fn setup<C, RNG>(settings: &FrostSettings, rng: &mut RNG) -> Result<FrostPackage<C>, Error<C>>
+where
+    C: Ciphersuite,
+    RNG: RngCore + CryptoRng,
+{
+    deal(settings, frost::keys::IdentifierList::<C>::Default, rng)
+}
+
+// Same as `setup`, but the participants get the given identifiers instead of
+// 1..=n, e.g. ones derived from their names or public keys with
+// `Identifier::derive`. There must be exactly `system_size` of them.
+pub fn setup_with_identifiers<C, RNG>(
+    settings: &FrostSettings,
+    identifiers: &[Identifier<C>],
+    rng: &mut RNG,
+) -> Result<FrostPackage<C>, Error<C>>
+where
+    C: Ciphersuite,
+    RNG: RngCore + CryptoRng,
+{
+    deal(settings, frost::keys::IdentifierList::Custom(identifiers), rng)
+}
+
+fn deal<C, RNG>(
+    settings: &FrostSettings,
+    identifiers: frost::keys::IdentifierList<C>,
+    rng: &mut RNG,
+) -> Result<FrostPackage<C>, Error<C>>
+where
+    C: Ciphersuite,
+    RNG: RngCore + CryptoRng,
+{
+    let max_signers = settings.system_size();
+    let min_signers = settings.threshold();
+    let (shares, pubkey_package) = frost::keys::generate_with_dealer(max_signers, min_signers, identifiers, rng)?;
+
+    // Verifies the secret shares from the dealer and store them in a BTreeMap.
+    // In practice, the KeyPackages must be sent to its respective participants
+    // through a confidential and authenticated channel.
+    let mut key_packages: BTreeMap<_, _> = BTreeMap::new();
+
+    // The dealer sends the same commitment vector along with every share.
+    let commitment = shares
+        .values()
+        .next()
+        .map(|share| share.commitment().clone())
+        .ok_or(Error::IncorrectNumberOfShares)?;
+
+    for (identifier, secret_share) in shares {
+        // ANCHOR: tkg_verify
+        let key_package = frost::keys::KeyPackage::try_from(secret_share)?;
+        // ANCHOR_END: tkg_verify
+        key_packages.insert(identifier, key_package);
+    }
+    Ok(FrostPackage {
+        secret: key_packages,
+        public: pubkey_package,
+        commitment: Some(commitment),
+    })
+}
+
+// Checks a participant's share against the dealer's published commitments,
+// i.e. that g^share equals the commitment polynomial evaluated at the
+// participant's identifier, and that the derived keys match the KeyPackage.
+pub fn verify_share_against_commitments<C: Ciphersuite>(
+    key_package: &KeyPackage<C>,
+    commitment: &VerifiableSecretSharingCommitment<C>,
+) -> Result<(), Error<C>> {
+    let secret_share = SecretShare::new(
+        *key_package.identifier(),
+        *key_package.signing_share(),
+        commitment.clone(),
+    );
+    let (verifying_share, verifying_key) = secret_share.verify()?;
+    if &verifying_share != key_package.verifying_share()
+        || &verifying_key != key_package.verifying_key()
+    {
+        return Err(Error::IncorrectCommitment);
+    }
+    Ok(())
+}
+
+pub fn validate_shares<C: Ciphersuite>(packages: &FrostPackage<C>) -> Result<(), Error<C>> {
+    // DKG shares were already verified by every participant in part3.
+    let Some(commitment) = &packages.commitment else {
+        return Ok(());
+    };
+    // In practice, each iteration of this loop will be executed by its respective participant.
+    for key_package in packages.secret.values() {
+        verify_share_against_commitments(key_package, commitment)?;
+    }
+    Ok(())
+}
+
+// Distributed key generation: every participant runs the three DKG parts and
+// no party ever learns the group secret. The message exchange is simulated by
+// moving packages between per-participant inboxes.
+pub fn setup_dkg<C, RNG>(settings: &FrostSettings, rng: &mut RNG) -> Result<FrostPackage<C>, Error<C>>
+where
+    C: Ciphersuite,
+    RNG: RngCore + CryptoRng,
+{
+    let max_signers = settings.system_size();
+    let min_signers = settings.threshold();
+    let identifiers = default_identifiers::<C>(max_signers)?;
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Round 1: each participant commits to its secret polynomial
+    ////////////////////////////////////////////////////////////////////////////
+
+    let mut round1_secret_packages = BTreeMap::new();
+    let mut received_round1_packages: BTreeMap<Identifier<C>, BTreeMap<_, _>> = BTreeMap::new();
+
+    for &participant_identifier in &identifiers {
+        let (round1_secret_package, round1_package) =
+            frost::keys::dkg::part1(participant_identifier, max_signers, min_signers, &mut *rng)?;
+        round1_secret_packages.insert(participant_identifier, round1_secret_package);
+
+        // In practice, the round 1 package is broadcast to every other participant.
+        for &receiver_identifier in &identifiers {
+            if receiver_identifier != participant_identifier {
+                received_round1_packages
+                    .entry(receiver_identifier)
+                    .or_default()
+                    .insert(participant_identifier, round1_package.clone());
+            }
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Round 2: each participant sends a secret share to every other participant
+    ////////////////////////////////////////////////////////////////////////////
+
+    let mut round2_secret_packages = BTreeMap::new();
+    let mut received_round2_packages: BTreeMap<Identifier<C>, BTreeMap<_, _>> = BTreeMap::new();
+
+    for (participant_identifier, round1_secret_package) in round1_secret_packages {
+        let (round2_secret_package, round2_packages) =
+            frost::keys::dkg::part2(round1_secret_package, &received_round1_packages[&participant_identifier])?;
+        round2_secret_packages.insert(participant_identifier, round2_secret_package);
+
+        // In practice, each round 2 package must be sent to its receiver through
+        // a confidential and authenticated channel.
+        for (receiver_identifier, round2_package) in round2_packages {
+            received_round2_packages
+                .entry(receiver_identifier)
+                .or_default()
+                .insert(participant_identifier, round2_package);
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Round 3: each participant verifies its shares and derives its key package
+    ////////////////////////////////////////////////////////////////////////////
+
+    let mut key_packages = BTreeMap::new();
+    let mut pubkey_package = None;
+
+    for (participant_identifier, round2_secret_package) in &round2_secret_packages {
+        let (key_package, participant_pubkey_package) = frost::keys::dkg::part3(
+            round2_secret_package,
+            &received_round1_packages[participant_identifier],
+            &received_round2_packages[participant_identifier],
+        )?;
+        key_packages.insert(*participant_identifier, key_package);
+
+        // Every participant derives the same PublicKeyPackage; a mismatch means
+        // the exchange was inconsistent.
+        match &pubkey_package {
+            Some(existing) if existing != &participant_pubkey_package => {
+                return Err(Error::IncorrectPackage);
+            }
+            Some(_) => {}
+            None => pubkey_package = Some(participant_pubkey_package),
+        }
+    }
+
+    Ok(FrostPackage {
+        secret: key_packages,
+        public: pubkey_package.ok_or(Error::IncorrectNumberOfPackages)?,
+        commitment: None,
+    })
+}
+
+// Proactive share refresh with the same participants and threshold: the
+// dealer distributes shares of zero, which every participant adds to its
+// current share. The group verifying key stays the same, old shares become
+// useless on their own.
+pub fn refresh<C, RNG>(settings: &FrostSettings, packages: &FrostPackage<C>, rng: &mut RNG) -> Result<FrostPackage<C>, Error<C>>
+where
+    C: Ciphersuite,
+    RNG: RngCore + CryptoRng,
+{
+    let identifiers: Vec<Identifier<C>> = packages.secret.keys().copied().collect();
+    let (zero_shares, pubkey_package) = frost::keys::refresh::compute_refreshing_shares(
+        packages.public.clone(),
+        settings.system_size(),
+        settings.threshold(),
+        &identifiers,
+        rng,
+    )?;
+
+    // In practice, each zero share is sent to its participant through a
+    // confidential and authenticated channel, and applied locally.
+    let mut key_packages = BTreeMap::new();
+    for (identifier, zero_share) in identifiers.iter().zip(zero_shares) {
+        let key_package = frost::keys::refresh::refresh_share(zero_share, &packages.secret[identifier])?;
+        key_packages.insert(*identifier, key_package);
+    }
+
+    if pubkey_package.verifying_key() != packages.public.verifying_key() {
+        return Err(Error::IncorrectPackage);
+    }
+    Ok(FrostPackage {
+        secret: key_packages,
+        public: pubkey_package,
+        commitment: None,
+    })
+}
+
+type Scalar<C> = <<<C as Ciphersuite>::Group as Group>::Field as Field>::Scalar;
+
+fn scalar_from_bytes<C: Ciphersuite>(bytes: &[u8]) -> Result<Scalar<C>, Error<C>> {
+    let bytes = bytes.to_vec().try_into().map_err(|_| Error::MalformedSigningKey)?;
+    Ok(<<C::Group as Group>::Field as Field>::deserialize(&bytes)?)
+}
+
+// Lagrange coefficient of `signer` for interpolating at zero over `signers`.
+fn lagrange_at_zero<C: Ciphersuite>(signer: &Identifier<C>, signers: &[Identifier<C>]) -> Result<Scalar<C>, Error<C>> {
+    let x_i = scalar_from_bytes::<C>(&signer.serialize())?;
+    let mut numerator = <<C::Group as Group>::Field as Field>::one();
+    let mut denominator = <<C::Group as Group>::Field as Field>::one();
+    for other in signers.iter().filter(|other| *other != signer) {
+        let x_j = scalar_from_bytes::<C>(&other.serialize())?;
+        numerator = numerator * x_j;
+        denominator = denominator * (x_j - x_i);
+    }
+    Ok(numerator * <<C::Group as Group>::Field as Field>::invert(&denominator)?)
+}
+
+type Element<C> = <<C as Ciphersuite>::Group as Group>::Element;
+
+fn element_from_bytes<C: Ciphersuite>(bytes: &[u8]) -> Result<Element<C>, Error<C>> {
+    let bytes = bytes.to_vec().try_into().map_err(|_| Error::GroupError(GroupError::MalformedElement))?;
+    Ok(<C::Group as Group>::deserialize(&bytes)?)
+}
+
+fn element_to_bytes<C: Ciphersuite>(element: &Element<C>) -> Result<Vec<u8>, Error<C>> {
+    Ok(<C::Group as Group>::serialize(element)?.as_ref().to_vec())
+}
+
+// The identifiers 1..=n that `setup` and `setup_dkg` assign.
+pub fn default_identifiers<C: Ciphersuite>(system_size: u16) -> Result<Vec<Identifier<C>>, Error<C>> {
+    (1..=system_size).map(Identifier::<C>::try_from).collect()
+}
+
+// Resharing to a new committee with identifiers `new_identifiers` and
+// threshold `new_settings.threshold()`, keeping the group verifying key.
+// The first t old participants each deal a fresh sharing of their Lagrange-
+// weighted share. Every new participant checks its sub-shares against the
+// dealers' commitments, checks that each dealer's constant term is the
+// dealer's weighted verifying share, and adds the sub-shares up. The summed
+// commitment must commit to the old group key; it is kept as the new
+// package's commitment. Old shares are not valid for the new committee.
+pub fn reshare<C, RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage<C>,
+    new_settings: &FrostSettings,
+    new_identifiers: &[Identifier<C>],
+    rng: &mut RNG,
+) -> Result<FrostPackage<C>, Error<C>>
+where
+    C: Ciphersuite,
+    RNG: RngCore + CryptoRng,
+{
+    let dealers: Vec<Identifier<C>> = packages.secret.keys().take(settings.threshold() as usize).copied().collect();
+    if dealers.len() < settings.threshold() as usize {
+        return Err(Error::IncorrectNumberOfShares);
+    }
+
+    let mut sums: BTreeMap<Identifier<C>, Scalar<C>> = BTreeMap::new();
+    let mut summed_commitment: Vec<Element<C>> = Vec::new();
+    for dealer in &dealers {
+        let lagrange = lagrange_at_zero(dealer, &dealers)?;
+        let share = scalar_from_bytes::<C>(&packages.secret[dealer].signing_share().serialize())?;
+        let weighted = frost::SigningKey::from_scalar(lagrange * share)?;
+        let (sub_shares, _) = frost::keys::split(
+            &weighted,
+            new_settings.system_size(),
+            new_settings.threshold(),
+            frost::keys::IdentifierList::Custom(new_identifiers),
+            rng,
+        )?;
+
+        // The dealer's constant term must be lambda_i * Y_i, otherwise it is
+        // sharing something other than its part of the group secret.
+        let verifying_share = packages
+            .public
+            .verifying_shares()
+            .get(dealer)
+            .ok_or(Error::UnknownIdentifier)?;
+        let expected = element_from_bytes::<C>(&verifying_share.serialize()?)? * lagrange;
+        let commitment = sub_shares
+            .values()
+            .next()
+            .map(|sub_share| sub_share.commitment().clone())
+            .ok_or(Error::IncorrectNumberOfShares)?;
+
+        // In practice, each sub-share is sent to its new participant through
+        // a confidential and authenticated channel and verified there, and
+        // the commitment is broadcast so every participant sees the same one.
+        for (identifier, sub_share) in sub_shares {
+            if sub_share.commitment() != &commitment {
+                return Err(Error::IncorrectCommitment);
+            }
+            let (_, constant_term) = sub_share.verify()?;
+            if constant_term.serialize()? != element_to_bytes::<C>(&expected)? {
+                return Err(Error::IncorrectCommitment);
+            }
+            let value = scalar_from_bytes::<C>(&sub_share.signing_share().serialize())?;
+            sums.entry(identifier)
+                .and_modify(|sum| *sum = *sum + value)
+                .or_insert(value);
+        }
+
+        let coefficients = commitment
+            .serialize()?
+            .iter()
+            .map(|bytes| element_from_bytes::<C>(bytes))
+            .collect::<Result<Vec<_>, _>>()?;
+        if summed_commitment.is_empty() {
+            summed_commitment = coefficients;
+        } else {
+            for (sum, coefficient) in summed_commitment.iter_mut().zip(coefficients) {
+                *sum = *sum + coefficient;
+            }
+        }
+    }
+    let commitment = VerifiableSecretSharingCommitment::deserialize(
+        summed_commitment
+            .iter()
+            .map(element_to_bytes::<C>)
+            .collect::<Result<Vec<_>, _>>()?,
+    )?;
+
+    // Each new participant checks its summed share against the summed
+    // commitment, which also gives its verifying share and the group key.
+    let verifying_key = *packages.public.verifying_key();
+    let mut key_packages = BTreeMap::new();
+    let mut verifying_shares = BTreeMap::new();
+    for (identifier, sum) in sums {
+        let signing_share =
+            frost::keys::SigningShare::deserialize(<<C::Group as Group>::Field as Field>::serialize(&sum).as_ref())?;
+        let (verifying_share, group_key) = SecretShare::new(identifier, signing_share, commitment.clone()).verify()?;
+        if group_key != verifying_key {
+            return Err(Error::IncorrectCommitment);
+        }
+        key_packages.insert(
+            identifier,
+            KeyPackage::new(identifier, signing_share, verifying_share, verifying_key, new_settings.threshold()),
+        );
+        verifying_shares.insert(identifier, verifying_share);
+    }
+    Ok(FrostPackage {
+        secret: key_packages,
+        public: frost::keys::PublicKeyPackage::new(verifying_shares, verifying_key),
+        commitment: Some(commitment),
+    })
+}
+
+pub fn vote_commitments<C, RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage<C>,
+    rng: &mut RNG,
+) -> Result<FrostRound1<C>, Error<C>>
+where
+    C: Ciphersuite,
+    RNG: RngCore + CryptoRng,
+{
+    // The first t participants in identifier order, whether the identifiers
+    // are the default 1..=n or custom ones.
+    let params = settings.params().map_err(|_| Error::InvalidMinSigners)?;
+    let signers: Vec<Identifier<C>> = packages.secret.keys().take(params.threshold() as usize).copied().collect();
+    if signers.len() < params.threshold() as usize {
+        return Err(Error::IncorrectNumberOfShares);
+    }
+    vote_commitments_for(settings, packages, &signers, rng)
+}
+
+// Same as `vote_commitments`, but for an explicitly chosen signer subset
+// (e.g. the one picked by `latency::LatencyMatrix::select_signers`).
+pub fn vote_commitments_for<C, RNG>(
+    _settings: &FrostSettings,
+    packages: &FrostPackage<C>,
+    signers: &[Identifier<C>],
+    rng: &mut RNG,
+) -> Result<FrostRound1<C>, Error<C>>
+where
+    C: Ciphersuite,
+    RNG: RngCore + CryptoRng,
+{
+    let mut nonces_map = BTreeMap::new();
+    let mut commitments_map = BTreeMap::new();
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Round 1: generating nonces and signing commitments for each participant
+    ////////////////////////////////////////////////////////////////////////////
+
+    // In practice, each iteration of this loop will be executed by its respective participant.
+    for participant_identifier in signers {
+        let key_package = packages
+            .secret
+            .get(participant_identifier)
+            .ok_or(Error::UnknownIdentifier)?;
+        // Generate one (1) nonce and one SigningCommitments instance for each
+        // selected participant.
+        // ANCHOR: round1_commit
+        let (nonces, commitments) = frost::round1::commit(key_package.signing_share(), rng);
+        // ANCHOR_END: round1_commit
+        // In practice, the nonces must be kept by the participant to use in the
+        // next round, while the commitment must be sent to the coordinator
+        // (or to every other participant if there is no coordinator) using
+        // an authenticated channel.
+        nonces_map.insert(*participant_identifier, nonces);
+        commitments_map.insert(*participant_identifier, commitments);
+        tracing::debug!(signer = ?participant_identifier, "commitment received");
+    }
+    let nonces = nonces_map;
+    let commitments = commitments_map;
+    Ok(FrostRound1 {
+        nonces,
+        commitments,
+    })
+}
+
+pub fn sign_message<C: Ciphersuite>(
+    _settings: &FrostSettings,
+    packages: &FrostPackage<C>,
+    round1: &FrostRound1<C>,
+    message: &[u8],
+) -> Result<FrostRound2<C>, Error<C>> {
+    // This is what the signature aggregator / coordinator needs to do:
+    // - decide what message to sign
+    // - take one (unused) commitment per signing participant
+    let mut signature_shares = BTreeMap::new();
+    // ANCHOR: round2_package
+
+    // In practice, the SigningPackage must be sent to all participants
+    // involved in the current signing (at least min_signers participants),
+    // using an authenticate channel (and confidential if the message is secret).
+    let signing_package = frost::SigningPackage::new(round1.commitments.clone(), message);
+    // ANCHOR_END: round2_package
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Round 2: each participant generates their signature share
+    ////////////////////////////////////////////////////////////////////////////
+
+    // In practice, each iteration of this loop will be executed by its respective participant.
+    for participant_identifier in round1.nonces.keys() {
+        let key_package = &packages.secret[participant_identifier];
+
+        let nonces = &round1.nonces[participant_identifier];
+
+        // Each participant generates their signature share.
+        // ANCHOR: round2_sign
+        let signature_share = frost::round2::sign(&signing_package, nonces, key_package)?;
+        // ANCHOR_END: round2_sign
+
+        // In practice, the signature share must be sent to the Coordinator
+        // using an authenticated channel.
+        signature_shares.insert(*participant_identifier, signature_share);
+        tracing::debug!(signer = ?participant_identifier, "share received");
+    }
+    Ok(FrostRound2 {
+        signing_package,
+        signature_shares,
+    })
+}
+
+pub fn aggregate_verify<C: Ciphersuite>(
+    _settings: &FrostSettings,
+    packages: &FrostPackage<C>,
+    _round1: &FrostRound1<C>,
+    round2: &FrostRound2<C>,
+    message: &[u8],
+) -> Result<(), Error<C>> {
+    // Aggregate (also verifies the signature shares)
+    // ANCHOR: aggregate
+    let group_signature = frost::aggregate(
+        &round2.signing_package,
+        &round2.signature_shares,
+        &packages.public,
+    )?;
+    // ANCHOR_END: aggregate
+    tracing::info!(signers = round2.signature_shares.len(), "aggregated");
+
+    // Check that the threshold signature can be verified by the group public
+    // key (the verification key).
+    // ANCHOR: verify
+    let is_signature_valid = packages
+        .public
+        .verifying_key()
+        .verify(message, &group_signature)
+        .is_ok();
+    // ANCHOR_END: verify
+    assert!(is_signature_valid);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HashFunction;
+    use crate::params::Params;
+    use crate::rng::{RngProvider, SeededRngProvider};
+    use frost_ed25519::Ed25519Sha512;
+
+    fn settings(system_size: usize, threshold: usize) -> FrostSettings {
+        FrostSettings::new(Params::new(system_size, threshold).unwrap(), HashFunction::default()).unwrap()
+    }
+
+    fn sign_and_verify(
+        settings: &FrostSettings,
+        packages: &FrostPackage<Ed25519Sha512>,
+        key: &frost::VerifyingKey<Ed25519Sha512>,
+    ) {
+        let mut rng = SeededRngProvider::new(2).rng();
+        let round1 = vote_commitments(settings, packages, &mut rng).unwrap();
+        let round2 = sign_message(settings, packages, &round1, b"after reshare").unwrap();
+        let signature = frost::aggregate(&round2.signing_package, &round2.signature_shares, &packages.public).unwrap();
+        key.verify(b"after reshare", &signature).unwrap();
+    }
+
+    #[test]
+    fn reshared_committees_sign_under_the_original_key() {
+        let old = settings(7, 5);
+        let mut rng = SeededRngProvider::new(1).rng();
+        let packages = setup::<Ed25519Sha512, _>(&old, &mut rng).unwrap();
+        let key = *packages.public.verifying_key();
+
+        for new in [settings(10, 7), settings(4, 3)] {
+            let identifiers: Vec<Identifier<Ed25519Sha512>> = (0..new.system_size())
+                .map(|index| Identifier::derive(format!("member-{index}").as_bytes()).unwrap())
+                .collect();
+            let reshared = reshare(&old, &packages, &new, &identifiers, &mut rng).unwrap();
+            assert_eq!(reshared.public.verifying_key(), &key);
+            assert_eq!(reshared.secret.keys().copied().collect::<Vec<_>>(), {
+                let mut sorted = identifiers.clone();
+                sorted.sort();
+                sorted
+            });
+            validate_shares(&reshared).unwrap();
+            sign_and_verify(&new, &reshared, &key);
+        }
+    }
+
+    #[test]
+    fn signatures_after_refresh_verify_under_the_original_key() {
+        let settings = settings(7, 5);
+        let mut rng = SeededRngProvider::new(4).rng();
+        let packages = setup::<Ed25519Sha512, _>(&settings, &mut rng).unwrap();
+        let key = *packages.public.verifying_key();
+
+        let refreshed = refresh(&settings, &packages, &mut rng).unwrap();
+        assert_eq!(refreshed.public.verifying_key(), &key);
+        for (identifier, key_package) in &refreshed.secret {
+            assert_ne!(key_package.signing_share(), packages.secret[identifier].signing_share());
+        }
+        sign_and_verify(&settings, &refreshed, &key);
+        let refreshed = refresh(&settings, &refreshed, &mut rng).unwrap();
+        sign_and_verify(&settings, &refreshed, &key);
+    }
+
+    #[test]
+    fn dealer_with_a_wrong_share_is_rejected() {
+        let old = settings(7, 5);
+        let mut rng = SeededRngProvider::new(3).rng();
+        let mut packages = setup::<Ed25519Sha512, _>(&old, &mut rng).unwrap();
+
+        // The first dealer deals another participant's share as its own.
+        let ids: Vec<_> = packages.secret.keys().copied().collect();
+        let (first, other) = (packages.secret[&ids[0]].clone(), packages.secret[&ids[1]].clone());
+        packages.secret.insert(
+            ids[0],
+            KeyPackage::new(ids[0], *other.signing_share(), *first.verifying_share(), *first.verifying_key(), 5),
+        );
+        let new = settings(7, 5);
+        let identifiers = default_identifiers(new.system_size()).unwrap();
+        assert!(matches!(
+            reshare(&old, &packages, &new, &identifiers, &mut rng),
+            Err(Error::IncorrectCommitment)
+        ));
+    }
+}
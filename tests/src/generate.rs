@@ -0,0 +1,208 @@
+use frost_ed25519::{self as frost, Signature, keys::PublicKeyPackage};
+use old_rand::{CryptoRng, Rng, RngCore};
+use std::time::{Duration, Instant};
+
+use crate::frost::{FrostPackage, FrostSettings};
+use crate::hash::HashFunction;
+use crate::transcript;
+
+// Signatures are checked in batches of this many during the final pass.
+const BATCH_SIZE: usize = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GenerateReport {
+    pub count: usize,
+    pub sampled: usize,
+    // Signing only, excluding the inline spot checks.
+    pub generation: Duration,
+    pub spot_verification: Duration,
+    pub batch_verification: Duration,
+}
+
+impl GenerateReport {
+    pub fn generation_and_verification(&self) -> Duration {
+        self.generation + self.spot_verification + self.batch_verification
+    }
+}
+
+// The message for signature `index`: the payload transcript of `payload`
+// followed by the index, so every generated signature is over distinct bytes.
+pub fn indexed_message(settings: &FrostSettings, payload: &[u8], index: u64) -> Vec<u8> {
+    indexed_payload(settings.hash(), payload, index)
+}
+
+// Same, for verifiers that only know the hash function.
+pub fn indexed_payload(hash: HashFunction, payload: &[u8], index: u64) -> Vec<u8> {
+    let mut bytes = payload.to_vec();
+    bytes.extend_from_slice(&index.to_le_bytes());
+    transcript::payload_transcript(hash, &bytes)
+}
+
+// Generates `count` signatures. Each one is verified inline with probability
+// `sample_rate`; afterwards all of them are verified in batches, so a bad
+// signature is still caught without paying for single verification of every
+// signature during generation.
+pub fn generate_signatures<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    payload: &[u8],
+    count: usize,
+    sample_rate: f64,
+    rng: &mut RNG,
+) -> Result<(Vec<Signature>, GenerateReport), frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let mut messages = Vec::with_capacity(count);
+    let mut signatures = Vec::with_capacity(count);
+    let mut generation = Duration::ZERO;
+    let mut spot_verification = Duration::ZERO;
+    let mut sampled = 0;
+
+    for index in 0..count as u64 {
+        let message = indexed_message(settings, payload, index);
+        let _session = tracing::info_span!("session", index).entered();
+        let start = Instant::now();
+        let round1 = crate::frost::vote_commitments(settings, packages, rng)?;
+        let round2 = crate::frost::sign_message(settings, packages, &round1, &message)?;
+        let signature = frost::aggregate(round2.signing_package(), round2.signature_shares(), packages.public())?;
+        generation += start.elapsed();
+
+        if rng.r#gen::<f64>() < sample_rate {
+            let start = Instant::now();
+            packages.public().verifying_key().verify(&message, &signature)?;
+            spot_verification += start.elapsed();
+            sampled += 1;
+        }
+        messages.push(message);
+        signatures.push(signature);
+    }
+
+    let start = Instant::now();
+    batch_verify(packages.public(), &messages, &signatures)?;
+    let batch_verification = start.elapsed();
+
+    Ok((
+        signatures,
+        GenerateReport {
+            count,
+            sampled,
+            generation,
+            spot_verification,
+            batch_verification,
+        },
+    ))
+}
+
+// FROST(Ed25519, SHA-512) signatures are plain RFC 8032 ed25519 signatures
+// under the group key, so standard ed25519 implementations accept them.
+pub fn external_key(public: &PublicKeyPackage) -> Result<ed25519_dalek::VerifyingKey, frost::Error> {
+    let key_bytes: [u8; 32] = public
+        .verifying_key()
+        .serialize()?
+        .try_into()
+        .map_err(|_| frost::Error::MalformedVerifyingKey)?;
+    ed25519_dalek::VerifyingKey::from_bytes(&key_bytes).map_err(|_| frost::Error::MalformedVerifyingKey)
+}
+
+pub fn external_signature(signature: &Signature) -> Result<ed25519_dalek::Signature, frost::Error> {
+    let bytes: [u8; 64] = signature
+        .serialize()?
+        .try_into()
+        .map_err(|_| frost::Error::MalformedSignature)?;
+    Ok(ed25519_dalek::Signature::from_bytes(&bytes))
+}
+
+// Verifies with ed25519-dalek instead of frost, using the strict RFC 8032
+// checks (canonical encodings, no small-order keys or R).
+pub fn verify_external(public: &PublicKeyPackage, message: &[u8], signature: &Signature) -> Result<(), frost::Error> {
+    external_key(public)?
+        .verify_strict(message, &external_signature(signature)?)
+        .map_err(|_| frost::Error::InvalidSignature)
+}
+
+// Same, through ed25519-dalek's batch verification.
+pub fn batch_verify(public: &PublicKeyPackage, messages: &[Vec<u8>], signatures: &[Signature]) -> Result<(), frost::Error> {
+    let key = external_key(public)?;
+    for (messages, signatures) in messages.chunks(BATCH_SIZE).zip(signatures.chunks(BATCH_SIZE)) {
+        let messages: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+        let signatures = signatures
+            .iter()
+            .map(external_signature)
+            .collect::<Result<Vec<_>, frost::Error>>()?;
+        let keys = vec![key; signatures.len()];
+        ed25519_dalek::verify_batch(&messages, &signatures, &keys).map_err(|_| frost::Error::InvalidSignature)?;
+    }
+    Ok(())
+}
+
+// Parallel signing of independent sessions. Session `index` draws from its
+// own ChaCha20 stream of a seed taken from `rng`, so the output does not
+// depend on how rayon schedules sessions onto threads.
+#[cfg(feature = "rayon")]
+pub fn generate_signatures_parallel<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    payload: &[u8],
+    count: usize,
+    rng: &mut RNG,
+) -> Result<Vec<Signature>, frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    use rand_chacha::ChaCha20Rng;
+    use rand_chacha::rand_core::SeedableRng;
+    use rayon::prelude::*;
+
+    let seed: [u8; 32] = rng.r#gen();
+    (0..count as u64)
+        .into_par_iter()
+        .map(|index| {
+            let mut rng = ChaCha20Rng::from_seed(seed);
+            rng.set_stream(index);
+            let message = indexed_message(settings, payload, index);
+            let round1 = crate::frost::vote_commitments(settings, packages, &mut rng)?;
+            let round2 = crate::frost::sign_message(settings, packages, &round1, &message)?;
+            frost::aggregate(round2.signing_package(), round2.signature_shares(), packages.public())
+        })
+        .collect()
+}
+
+// `batch_verify` with the signatures split evenly over rayon's threads, in
+// batches of at most BATCH_SIZE.
+#[cfg(feature = "rayon")]
+pub fn batch_verify_parallel(
+    public: &PublicKeyPackage,
+    messages: &[Vec<u8>],
+    signatures: &[Signature],
+) -> Result<(), frost::Error> {
+    use rayon::prelude::*;
+
+    let chunk = messages.len().div_ceil(rayon::current_num_threads()).clamp(1, BATCH_SIZE);
+    messages
+        .par_chunks(chunk)
+        .zip(signatures.par_chunks(chunk))
+        .try_for_each(|(messages, signatures)| batch_verify(public, messages, signatures))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn signatures_verify_under_ed25519_dalek() {
+        let (settings, package) = testing::committee(1);
+        let mut rng = testing::rng(2);
+        let (signatures, report) = generate_signatures(&settings, &package, b"payload", 4, 1.0, &mut rng).unwrap();
+        assert_eq!((report.count, report.sampled), (4, 4));
+
+        let messages: Vec<Vec<u8>> = (0..4).map(|index| indexed_message(&settings, b"payload", index)).collect();
+        for (message, signature) in messages.iter().zip(&signatures) {
+            verify_external(package.public(), message, signature).unwrap();
+        }
+        batch_verify(package.public(), &messages, &signatures).unwrap();
+        assert!(verify_external(package.public(), &messages[1], &signatures[0]).is_err());
+        assert!(batch_verify(package.public(), &messages[1..], &signatures[..3]).is_err());
+    }
+}
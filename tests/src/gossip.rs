@@ -0,0 +1,120 @@
+use old_rand::seq::IteratorRandom;
+use old_rand::RngCore;
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+
+use crate::hash::HashFunction;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GossipStats {
+    pub system_size: usize,
+    pub rounds: usize,
+    pub messages: usize,
+    pub converged: bool,
+    pub wall_time: Duration,
+}
+
+impl GossipStats {
+    pub fn simulated_time_ms(&self, round_ms: f64) -> f64 {
+        self.rounds as f64 * round_ms
+    }
+}
+
+// Simulated push-pull membership gossip run before key generation. Every
+// participant starts out knowing only itself and the bootstrap participants;
+// each round it exchanges its full membership view with `fanout` random known
+// peers. The committee is agreed on once every view is complete and all
+// membership hashes match, which is what key generation would check.
+pub struct MembershipGossip {
+    views: Vec<BTreeSet<u16>>,
+    hash: HashFunction,
+}
+
+impl MembershipGossip {
+    pub fn new(system_size: u16, bootstrap: &[u16], hash: HashFunction) -> Self {
+        let views = (0..system_size)
+            .map(|participant| {
+                let mut view: BTreeSet<u16> = bootstrap.iter().copied().filter(|&b| b < system_size).collect();
+                view.insert(participant);
+                view
+            })
+            .collect();
+        MembershipGossip { views, hash }
+    }
+
+    pub fn membership_hash(&self, participant: usize) -> Vec<u8> {
+        let mut hasher = self.hash.hasher();
+        for member in &self.views[participant] {
+            hasher.update(&member.to_le_bytes());
+        }
+        hasher.finalize()
+    }
+
+    pub fn converged(&self) -> bool {
+        let n = self.views.len();
+        if self.views.iter().any(|view| view.len() != n) {
+            return false;
+        }
+        // An empty committee has no views to compare.
+        if n == 0 {
+            return true;
+        }
+        let reference = self.membership_hash(0);
+        (1..n).all(|participant| self.membership_hash(participant) == reference)
+    }
+
+    // One synchronous gossip round; returns the number of messages sent.
+    pub fn round<RNG: RngCore>(&mut self, fanout: usize, rng: &mut RNG) -> usize {
+        let snapshot = self.views.clone();
+        let mut messages = 0;
+        for (participant, view) in snapshot.iter().enumerate() {
+            let peers = view
+                .iter()
+                .copied()
+                .filter(|&peer| peer as usize != participant)
+                .choose_multiple(rng, fanout);
+            for peer in peers {
+                // Push our view, pull theirs.
+                self.views[peer as usize].extend(view.iter().copied());
+                self.views[participant].extend(snapshot[peer as usize].iter().copied());
+                messages += 2;
+            }
+        }
+        messages
+    }
+
+    pub fn run<RNG: RngCore>(&mut self, fanout: usize, max_rounds: usize, rng: &mut RNG) -> GossipStats {
+        let start = Instant::now();
+        let mut messages = 0;
+        let mut rounds = 0;
+        while !self.converged() && rounds < max_rounds {
+            messages += self.round(fanout, rng);
+            rounds += 1;
+        }
+        GossipStats {
+            system_size: self.views.len(),
+            rounds,
+            messages,
+            converged: self.converged(),
+            wall_time: start.elapsed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn converges_and_handles_an_empty_committee() {
+        let mut rng = testing::rng(1);
+        let stats = MembershipGossip::new(30, &[0], HashFunction::default()).run(3, 1000, &mut rng);
+        assert!(stats.converged);
+        assert!(stats.rounds > 0);
+
+        let stats = MembershipGossip::new(0, &[0], HashFunction::default()).run(3, 1000, &mut rng);
+        assert!(stats.converged);
+        assert_eq!((stats.rounds, stats.messages), (0, 0));
+    }
+}
@@ -0,0 +1,131 @@
+use frost_ed25519::{self as frost, keys::PublicKeyPackage};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::transcript::Transcript;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HashFunction {
+    #[default]
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+impl HashFunction {
+    pub const ALL: [HashFunction; 3] = [HashFunction::Sha256, HashFunction::Sha512, HashFunction::Blake3];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashFunction::Sha256 => "sha256",
+            HashFunction::Sha512 => "sha512",
+            HashFunction::Blake3 => "blake3",
+        }
+    }
+
+    pub fn output_len(&self) -> usize {
+        match self {
+            HashFunction::Sha256 => 32,
+            HashFunction::Sha512 => 64,
+            HashFunction::Blake3 => 32,
+        }
+    }
+
+    pub fn hasher(&self) -> Hasher {
+        match self {
+            HashFunction::Sha256 => Hasher::Sha256(Sha256::new()),
+            HashFunction::Sha512 => Hasher::Sha512(Sha512::new()),
+            HashFunction::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        let mut hasher = self.hasher();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+/// Incremental hasher for whichever `HashFunction` was selected.
+pub enum Hasher {
+    Sha256(Sha256),
+    Sha512(Sha512),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(h) => h.update(data),
+            Hasher::Sha512(h) => h.update(data),
+            Hasher::Blake3(h) => {
+                h.update(data);
+            }
+        }
+    }
+
+    // Length-prefixing keeps ("ab", "c") and ("a", "bc") from colliding.
+    pub fn update_framed(&mut self, data: &[u8]) {
+        self.update(&(data.len() as u64).to_le_bytes());
+        self.update(data);
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Sha256(h) => h.finalize().to_vec(),
+            Hasher::Sha512(h) => h.finalize().to_vec(),
+            Hasher::Blake3(h) => h.finalize().as_bytes().to_vec(),
+        }
+    }
+}
+
+pub fn committee_digest(hash: HashFunction, public: &PublicKeyPackage) -> Result<Vec<u8>, frost::Error> {
+    let mut hasher = hash.hasher();
+    hasher.update_framed(b"committee");
+    hasher.update_framed(&public.verifying_key().serialize()?);
+    for (identifier, share) in public.verifying_shares() {
+        hasher.update_framed(&identifier.serialize());
+        hasher.update_framed(&share.serialize()?);
+    }
+    Ok(hasher.finalize())
+}
+
+pub fn envelope_digest(hash: HashFunction, domain: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut transcript = Transcript::with_hash(hash, b"envelope");
+    transcript.append_message(b"domain", domain).append_message(b"message", message);
+    transcript.signing_bytes()
+}
+
+pub fn merkle_root(hash: HashFunction, leaves: &[&[u8]]) -> Vec<u8> {
+    // Leaves and inner nodes use distinct prefixes so a leaf can never be
+    // reinterpreted as an inner node (second-preimage protection).
+    let mut level: Vec<Vec<u8>> = leaves
+        .iter()
+        .map(|leaf| {
+            let mut hasher = hash.hasher();
+            hasher.update(&[0x00]);
+            hasher.update(leaf);
+            hasher.finalize()
+        })
+        .collect();
+    if level.is_empty() {
+        return hash.digest(&[]);
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut hasher = hash.hasher();
+                    hasher.update(&[0x01]);
+                    hasher.update(left);
+                    hasher.update(right);
+                    hasher.finalize()
+                }
+                [single] => single.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level.pop().expect("level is non-empty")
+}
@@ -0,0 +1,187 @@
+use frost_ed25519::{self as frost, Signature, keys::PublicKeyPackage};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::frost::{FrostPackage, FrostRound1, FrostSettings};
+use crate::hash::{self, HashFunction};
+use crate::transcript::Transcript;
+
+#[derive(Debug)]
+pub enum HistoryError {
+    UnknownEpoch(u64),
+    NonMonotonicEpoch { latest: u64, inserted: u64 },
+    DigestMismatch(u64),
+    Frost(frost::Error),
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::UnknownEpoch(epoch) => write!(f, "no committee recorded for epoch {epoch}"),
+            HistoryError::NonMonotonicEpoch { latest, inserted } => {
+                write!(f, "epoch {inserted} is not after the latest recorded epoch {latest}")
+            }
+            HistoryError::DigestMismatch(epoch) => write!(f, "envelope committee digest does not match epoch {epoch}"),
+            HistoryError::Frost(e) => write!(f, "frost error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+impl From<frost::Error> for HistoryError {
+    fn from(e: frost::Error) -> Self {
+        HistoryError::Frost(e)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommitteeSnapshot {
+    pub epoch: u64,
+    pub digest: Vec<u8>,
+    pub public: PublicKeyPackage,
+}
+
+// A signature together with the epoch (and committee digest) it was produced
+// under; the epoch and digest are part of the signed transcript.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochEnvelope {
+    pub epoch: u64,
+    pub committee_digest: Vec<u8>,
+    pub signature: Signature,
+}
+
+impl EpochEnvelope {
+    pub fn signing_bytes(hash: HashFunction, epoch: u64, committee_digest: &[u8], payload: &[u8]) -> Vec<u8> {
+        let mut transcript = Transcript::with_hash(hash, b"epoch-envelope");
+        transcript
+            .append_u64(b"epoch", epoch)
+            .append_message(b"committee", committee_digest)
+            .append_message(b"payload", payload);
+        transcript.signing_bytes()
+    }
+}
+
+// Epoch -> committee snapshots, as a light client would keep them across
+// validator-set rotations. Certificates are checked against the committee of
+// the epoch they claim, not the current one.
+#[derive(Clone, Debug)]
+pub struct CommitteeHistory {
+    hash: HashFunction,
+    snapshots: BTreeMap<u64, CommitteeSnapshot>,
+}
+
+impl CommitteeHistory {
+    pub fn new(hash: HashFunction) -> Self {
+        CommitteeHistory {
+            hash,
+            snapshots: BTreeMap::new(),
+        }
+    }
+
+    pub fn record(&mut self, epoch: u64, public: PublicKeyPackage) -> Result<&CommitteeSnapshot, HistoryError> {
+        if let Some((&latest, _)) = self.snapshots.last_key_value()
+            && epoch <= latest
+        {
+            return Err(HistoryError::NonMonotonicEpoch {
+                latest,
+                inserted: epoch,
+            });
+        }
+        let digest = hash::committee_digest(self.hash, &public)?;
+        Ok(self.snapshots.entry(epoch).or_insert(CommitteeSnapshot { epoch, digest, public }))
+    }
+
+    pub fn snapshot(&self, epoch: u64) -> Result<&CommitteeSnapshot, HistoryError> {
+        self.snapshots.get(&epoch).ok_or(HistoryError::UnknownEpoch(epoch))
+    }
+
+    pub fn latest(&self) -> Option<&CommitteeSnapshot> {
+        self.snapshots.values().next_back()
+    }
+
+    pub fn verify(&self, envelope: &EpochEnvelope, payload: &[u8]) -> Result<(), HistoryError> {
+        let snapshot = self.snapshot(envelope.epoch)?;
+        if snapshot.digest != envelope.committee_digest {
+            return Err(HistoryError::DigestMismatch(envelope.epoch));
+        }
+        let signing_bytes = EpochEnvelope::signing_bytes(self.hash, envelope.epoch, &snapshot.digest, payload);
+        snapshot
+            .public
+            .verifying_key()
+            .verify(&signing_bytes, &envelope.signature)?;
+        Ok(())
+    }
+}
+
+// Signs `payload` with the committee recorded for `epoch`.
+pub fn sign_for_epoch(
+    history: &CommitteeHistory,
+    epoch: u64,
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    round1: &FrostRound1,
+    payload: &[u8],
+) -> Result<EpochEnvelope, HistoryError> {
+    let snapshot = history.snapshot(epoch)?;
+    let signing_bytes = EpochEnvelope::signing_bytes(history.hash, epoch, &snapshot.digest, payload);
+    let round2 = crate::frost::sign_message(settings, packages, round1, &signing_bytes)?;
+    let signature = frost::aggregate(round2.signing_package(), round2.signature_shares(), packages.public())?;
+    Ok(EpochEnvelope {
+        epoch,
+        committee_digest: snapshot.digest.clone(),
+        signature,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn envelope(history: &CommitteeHistory, epoch: u64, settings: &FrostSettings, packages: &FrostPackage) -> EpochEnvelope {
+        let round1 = crate::frost::vote_commitments(settings, packages, &mut testing::rng(epoch)).unwrap();
+        sign_for_epoch(history, epoch, settings, packages, &round1, b"payload").unwrap()
+    }
+
+    #[test]
+    fn certificates_verify_against_the_committee_of_their_epoch() {
+        let (settings, first) = testing::committee(1);
+        let (_, second) = testing::committee(2);
+        let mut history = CommitteeHistory::new(HashFunction::default());
+        history.record(1, first.public().clone()).unwrap();
+        history.record(2, second.public().clone()).unwrap();
+
+        // An old certificate stays valid after the committee rotated.
+        let old = envelope(&history, 1, &settings, &first);
+        let new = envelope(&history, 2, &settings, &second);
+        history.verify(&old, b"payload").unwrap();
+        history.verify(&new, b"payload").unwrap();
+        assert!(history.verify(&old, b"other payload").is_err());
+
+        // Claiming another epoch fails, both on the digest and on the key.
+        let relabelled = EpochEnvelope { epoch: 2, ..old.clone() };
+        assert!(matches!(history.verify(&relabelled, b"payload"), Err(HistoryError::DigestMismatch(2))));
+        let forged = EpochEnvelope {
+            epoch: 2,
+            committee_digest: new.committee_digest.clone(),
+            signature: old.signature,
+        };
+        assert!(matches!(history.verify(&forged, b"payload"), Err(HistoryError::Frost(_))));
+        let unknown = EpochEnvelope { epoch: 3, ..old };
+        assert!(matches!(history.verify(&unknown, b"payload"), Err(HistoryError::UnknownEpoch(3))));
+    }
+
+    #[test]
+    fn epochs_must_increase() {
+        let (_, packages) = testing::committee(1);
+        let mut history = CommitteeHistory::new(HashFunction::default());
+        history.record(5, packages.public().clone()).unwrap();
+        assert!(matches!(
+            history.record(5, packages.public().clone()),
+            Err(HistoryError::NonMonotonicEpoch { latest: 5, inserted: 5 })
+        ));
+        assert_eq!(history.latest().unwrap().epoch, 5);
+    }
+}
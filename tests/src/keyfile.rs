@@ -0,0 +1,160 @@
+use frost_ed25519::keys::{KeyPackage, PublicKeyPackage};
+use frost_ed25519::{self as frost, Ed25519Sha512, Signature};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use crate::artifacts::ArtifactError;
+use crate::fixtures::FixtureError;
+use crate::frost::{FrostPackage, FrostSettings};
+use crate::hash::HashFunction;
+use crate::keystore::KeystoreError;
+use crate::params::{Params, ParamsError};
+use crate::sigfile::{SigFileError, SigFormat, SignatureReader, SignatureWriter};
+use crate::verify::VerifyError;
+
+const PUBLIC_FILE: &str = "public.json";
+const SHARES_FILE: &str = "shares.json";
+
+#[derive(Debug)]
+pub enum KeyFileError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Frost(frost::Error),
+    Params(ParamsError),
+    SigFile(SigFileError),
+    Verify(VerifyError),
+    Keystore(KeystoreError),
+    Fixture(FixtureError<Ed25519Sha512>),
+    Artifact(ArtifactError),
+    NoShares,
+    NoSignature,
+}
+
+impl fmt::Display for KeyFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyFileError::Io(e) => write!(f, "io error: {e}"),
+            KeyFileError::Json(e) => write!(f, "json error: {e}"),
+            KeyFileError::Frost(e) => write!(f, "frost error: {e}"),
+            KeyFileError::Params(e) => write!(f, "invalid parameters: {e}"),
+            KeyFileError::SigFile(e) => write!(f, "{e}"),
+            KeyFileError::Verify(e) => write!(f, "{e}"),
+            KeyFileError::Keystore(e) => write!(f, "{e}"),
+            KeyFileError::Fixture(e) => write!(f, "{e}"),
+            KeyFileError::Artifact(e) => write!(f, "{e}"),
+            KeyFileError::NoShares => write!(f, "share file contains no key packages"),
+            KeyFileError::NoSignature => write!(f, "signature file contains no signatures"),
+        }
+    }
+}
+
+impl std::error::Error for KeyFileError {}
+
+impl From<io::Error> for KeyFileError {
+    fn from(e: io::Error) -> Self {
+        KeyFileError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for KeyFileError {
+    fn from(e: serde_json::Error) -> Self {
+        KeyFileError::Json(e)
+    }
+}
+
+impl From<frost::Error> for KeyFileError {
+    fn from(e: frost::Error) -> Self {
+        KeyFileError::Frost(e)
+    }
+}
+
+impl From<SigFileError> for KeyFileError {
+    fn from(e: SigFileError) -> Self {
+        KeyFileError::SigFile(e)
+    }
+}
+
+impl From<VerifyError> for KeyFileError {
+    fn from(e: VerifyError) -> Self {
+        KeyFileError::Verify(e)
+    }
+}
+
+impl From<KeystoreError> for KeyFileError {
+    fn from(e: KeystoreError) -> Self {
+        KeyFileError::Keystore(e)
+    }
+}
+
+impl From<FixtureError<Ed25519Sha512>> for KeyFileError {
+    fn from(e: FixtureError<Ed25519Sha512>) -> Self {
+        KeyFileError::Fixture(e)
+    }
+}
+
+impl From<ArtifactError> for KeyFileError {
+    fn from(e: ArtifactError) -> Self {
+        KeyFileError::Artifact(e)
+    }
+}
+
+impl From<ParamsError> for KeyFileError {
+    fn from(e: ParamsError) -> Self {
+        KeyFileError::Params(e)
+    }
+}
+
+// A key directory holds `public.json` (the PublicKeyPackage) and
+// `shares.json` (every participant's KeyPackage). Keeping all shares in one
+// file is only meant for experiments, where one process simulates everyone.
+// Returns the files written, for registering them as artifacts.
+pub fn write_keys<P: AsRef<Path>>(dir: P, packages: &FrostPackage) -> Result<Vec<PathBuf>, KeyFileError> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let public_path = dir.join(PUBLIC_FILE);
+    fs::write(&public_path, serde_json::to_vec_pretty(packages.public())?)?;
+    let shares: Vec<&KeyPackage> = packages.secret().values().collect();
+    let shares_path = dir.join(SHARES_FILE);
+    fs::write(&shares_path, serde_json::to_vec_pretty(&shares)?)?;
+    Ok(vec![public_path, shares_path])
+}
+
+pub fn read_public<P: AsRef<Path>>(path: P) -> Result<PublicKeyPackage, KeyFileError> {
+    let path = path.as_ref();
+    let path = if path.is_dir() { path.join(PUBLIC_FILE) } else { path.to_path_buf() };
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+pub fn read_keys<P: AsRef<Path>>(dir: P) -> Result<FrostPackage, KeyFileError> {
+    let dir = dir.as_ref();
+    let public = read_public(dir)?;
+    let shares: Vec<KeyPackage> = serde_json::from_slice(&fs::read(dir.join(SHARES_FILE))?)?;
+    Ok(FrostPackage {
+        secret: shares.into_iter().map(|share| (*share.identifier(), share)).collect(),
+        public,
+        commitment: None,
+    })
+}
+
+// Recovers (n, t) from the key material itself.
+pub fn settings_for(packages: &FrostPackage, hash: HashFunction) -> Result<FrostSettings, KeyFileError> {
+    let threshold = *packages.secret().values().next().ok_or(KeyFileError::NoShares)?.min_signers();
+    let params = Params::new(packages.public().verifying_shares().len(), threshold as usize)?;
+    Ok(FrostSettings::new(params, hash)?)
+}
+
+// A single signature is stored as a one-record hex signature file.
+pub fn write_signature<P: AsRef<Path>>(path: P, signature: &Signature) -> Result<(), KeyFileError> {
+    let mut writer = SignatureWriter::new(BufWriter::new(File::create(path)?), SigFormat::Hex)?;
+    writer.write_signature(signature)?;
+    writer.finish()?;
+    Ok(())
+}
+
+// Returns the first signature of a signature file in any format.
+pub fn read_signature<P: AsRef<Path>>(path: P) -> Result<Signature, KeyFileError> {
+    let reader = SignatureReader::new(BufReader::new(File::open(path)?))?;
+    Ok(reader.signatures().next().ok_or(KeyFileError::NoSignature)??)
+}
@@ -0,0 +1,319 @@
+use argon2::{Algorithm, Argon2, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use frost_ed25519::keys::{KeyPackage, PublicKeyPackage};
+use frost_ed25519::{self as frost, Identifier};
+use old_rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::frost::FrostPackage;
+
+const PUBLIC_FILE: &str = "public.json";
+const SHARE_PREFIX: &str = "share-";
+const VERSION: u8 = 1;
+
+#[derive(Debug)]
+pub enum KeystoreError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Frost(frost::Error),
+    Kdf(argon2::Error),
+    Hex(hex::FromHexError),
+    UnsupportedVersion(u8),
+    // Wrong passphrase, or the file was modified or renamed.
+    Decryption,
+    NoShares,
+}
+
+impl fmt::Display for KeystoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeystoreError::Io(e) => write!(f, "io error: {e}"),
+            KeystoreError::Json(e) => write!(f, "json error: {e}"),
+            KeystoreError::Frost(e) => write!(f, "frost error: {e}"),
+            KeystoreError::Kdf(e) => write!(f, "key derivation error: {e}"),
+            KeystoreError::Hex(e) => write!(f, "hex error: {e}"),
+            KeystoreError::UnsupportedVersion(v) => write!(f, "unsupported keystore version {v}"),
+            KeystoreError::Decryption => write!(f, "share could not be decrypted (wrong passphrase or corrupted file)"),
+            KeystoreError::NoShares => write!(f, "keystore contains no shares"),
+        }
+    }
+}
+
+impl std::error::Error for KeystoreError {}
+
+impl From<io::Error> for KeystoreError {
+    fn from(e: io::Error) -> Self {
+        KeystoreError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for KeystoreError {
+    fn from(e: serde_json::Error) -> Self {
+        KeystoreError::Json(e)
+    }
+}
+
+impl From<frost::Error> for KeystoreError {
+    fn from(e: frost::Error) -> Self {
+        KeystoreError::Frost(e)
+    }
+}
+
+impl From<argon2::Error> for KeystoreError {
+    fn from(e: argon2::Error) -> Self {
+        KeystoreError::Kdf(e)
+    }
+}
+
+impl From<hex::FromHexError> for KeystoreError {
+    fn from(e: hex::FromHexError) -> Self {
+        KeystoreError::Hex(e)
+    }
+}
+
+// Argon2id cost parameters, stored with every share so they can be raised
+// later without breaking existing keystores.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        KdfParams {
+            memory_kib: argon2::Params::DEFAULT_M_COST,
+            iterations: argon2::Params::DEFAULT_T_COST,
+            parallelism: argon2::Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl KdfParams {
+    fn derive_key(&self, passphrase: &str, salt: &[u8]) -> Result<[u8; 32], KeystoreError> {
+        let params = argon2::Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))?;
+        let mut key = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params).hash_password_into(
+            passphrase.as_bytes(),
+            salt,
+            &mut key,
+        )?;
+        Ok(key)
+    }
+}
+
+// One participant's KeyPackage, encrypted with ChaCha20-Poly1305 under a key
+// derived from the passphrase. The identifier is authenticated as associated
+// data, so a share file cannot be passed off as another participant's.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedShare {
+    pub version: u8,
+    pub identifier: String,
+    pub kdf: KdfParams,
+    pub salt: [u8; 16],
+    pub nonce: [u8; 12],
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedShare {
+    pub fn seal<RNG: RngCore + CryptoRng>(
+        key_package: &KeyPackage,
+        passphrase: &str,
+        kdf: KdfParams,
+        rng: &mut RNG,
+    ) -> Result<EncryptedShare, KeystoreError> {
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 12];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut nonce);
+        let identifier = key_package.identifier().serialize();
+        let key = kdf.derive_key(passphrase, &salt)?;
+        let plaintext = key_package.serialize()?;
+        let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key))
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: &identifier,
+                },
+            )
+            .map_err(|_| KeystoreError::Decryption)?;
+        Ok(EncryptedShare {
+            version: VERSION,
+            identifier: hex::encode(identifier),
+            kdf,
+            salt,
+            nonce,
+            ciphertext,
+        })
+    }
+
+    pub fn open(&self, passphrase: &str) -> Result<KeyPackage, KeystoreError> {
+        if self.version != VERSION {
+            return Err(KeystoreError::UnsupportedVersion(self.version));
+        }
+        let identifier = hex::decode(&self.identifier)?;
+        let key = self.kdf.derive_key(passphrase, &self.salt)?;
+        let plaintext = ChaCha20Poly1305::new(Key::from_slice(&key))
+            .decrypt(
+                Nonce::from_slice(&self.nonce),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &identifier,
+                },
+            )
+            .map_err(|_| KeystoreError::Decryption)?;
+        let key_package = KeyPackage::deserialize(&plaintext)?;
+        if key_package.identifier().serialize() != identifier {
+            return Err(KeystoreError::Decryption);
+        }
+        Ok(key_package)
+    }
+}
+
+pub fn share_path<P: AsRef<Path>>(dir: P, identifier: &Identifier) -> PathBuf {
+    dir.as_ref()
+        .join(format!("{SHARE_PREFIX}{}.json", hex::encode(identifier.serialize())))
+}
+
+// A keystore directory holds `public.json` (the PublicKeyPackage, in the
+// clear) and one `share-<identifier>.json` per participant, so each signer
+// process only needs its own file and the passphrase. Returns the files
+// written, for registering them as artifacts.
+pub fn write_keystore<P, RNG>(
+    dir: P,
+    packages: &FrostPackage,
+    passphrase: &str,
+    kdf: KdfParams,
+    rng: &mut RNG,
+) -> Result<Vec<PathBuf>, KeystoreError>
+where
+    P: AsRef<Path>,
+    RNG: RngCore + CryptoRng,
+{
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let mut written = vec![dir.join(PUBLIC_FILE)];
+    fs::write(&written[0], serde_json::to_vec_pretty(packages.public())?)?;
+    for (identifier, key_package) in packages.secret() {
+        let share = EncryptedShare::seal(key_package, passphrase, kdf, rng)?;
+        let path = share_path(dir, identifier);
+        fs::write(&path, serde_json::to_vec_pretty(&share)?)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+pub fn read_public<P: AsRef<Path>>(dir: P) -> Result<PublicKeyPackage, KeystoreError> {
+    Ok(serde_json::from_slice(&fs::read(dir.as_ref().join(PUBLIC_FILE))?)?)
+}
+
+pub fn read_share<P: AsRef<Path>>(dir: P, identifier: &Identifier, passphrase: &str) -> Result<KeyPackage, KeystoreError> {
+    let share: EncryptedShare = serde_json::from_slice(&fs::read(share_path(dir, identifier))?)?;
+    share.open(passphrase)
+}
+
+// Decrypts every share in the directory, for experiments where one process
+// plays all participants.
+pub fn read_keystore<P: AsRef<Path>>(dir: P, passphrase: &str) -> Result<FrostPackage, KeystoreError> {
+    let dir = dir.as_ref();
+    let mut secret = BTreeMap::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_share = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(SHARE_PREFIX) && name.ends_with(".json"));
+        if is_share {
+            let share: EncryptedShare = serde_json::from_slice(&fs::read(&path)?)?;
+            let key_package = share.open(passphrase)?;
+            secret.insert(*key_package.identifier(), key_package);
+        }
+    }
+    if secret.is_empty() {
+        return Err(KeystoreError::NoShares);
+    }
+    Ok(FrostPackage {
+        secret,
+        public: read_public(dir)?,
+        commitment: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    // The minimum Argon2 cost, so the tests stay fast.
+    const CHEAP: KdfParams = KdfParams {
+        memory_kib: 8,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    fn keystore(label: &str) -> (PathBuf, FrostPackage) {
+        let dir = std::env::temp_dir().join(format!("thesis-keystore-{label}-{}", std::process::id()));
+        let (_, package) = testing::committee(1);
+        write_keystore(&dir, &package, "correct horse", CHEAP, &mut testing::rng(2)).unwrap();
+        (dir, package)
+    }
+
+    #[test]
+    fn keystore_round_trips() {
+        let (dir, package) = keystore("round-trip");
+        let read = read_keystore(&dir, "correct horse");
+        let identifier = *package.secret().keys().next().unwrap();
+        let share = read_share(&dir, &identifier, "correct horse");
+        fs::remove_dir_all(&dir).unwrap();
+
+        let read = read.unwrap();
+        assert_eq!(read.secret(), package.secret());
+        assert_eq!(read.public(), package.public());
+        assert_eq!(&share.unwrap(), &package.secret()[&identifier]);
+    }
+
+    #[test]
+    fn wrong_passphrase_is_an_error() {
+        let (dir, package) = keystore("passphrase");
+        let read = read_keystore(&dir, "battery staple");
+        let identifier = *package.secret().keys().next().unwrap();
+        let share = read_share(&dir, &identifier, "");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(read, Err(KeystoreError::Decryption)));
+        assert!(matches!(share, Err(KeystoreError::Decryption)));
+    }
+
+    #[test]
+    fn tampered_shares_are_an_error() {
+        let (dir, package) = keystore("tampered");
+        let identifiers: Vec<Identifier> = package.secret().keys().copied().collect();
+        let path = share_path(&dir, &identifiers[0]);
+        let original: EncryptedShare = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+
+        let mut flipped = original.clone();
+        flipped.ciphertext[0] ^= 1;
+        let mut truncated = original.clone();
+        truncated.ciphertext.truncate(8);
+        // Claims to be another participant's share.
+        let mut renamed = original.clone();
+        renamed.identifier = hex::encode(identifiers[1].serialize());
+
+        fs::write(&path, serde_json::to_vec(&flipped).unwrap()).unwrap();
+        let read = read_keystore(&dir, "correct horse");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(read, Err(KeystoreError::Decryption)));
+        for share in [flipped, truncated, renamed] {
+            assert!(matches!(share.open("correct horse"), Err(KeystoreError::Decryption)));
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use frost_ed25519::Identifier;
+use old_rand::seq::SliceRandom;
+use old_rand::{Rng, RngCore};
+use std::path::Path;
+
+use crate::params::{Params, ParamsError};
+
+// One-way latencies in milliseconds between participants; participant `i`
+// holds FROST identifier `i + 1`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LatencyMatrix {
+    latencies: Vec<Vec<f64>>,
+}
+
+impl LatencyMatrix {
+    pub fn new(latencies: Vec<Vec<f64>>) -> Option<Self> {
+        let n = latencies.len();
+        if latencies.iter().any(|row| row.len() != n) {
+            return None;
+        }
+        Some(LatencyMatrix { latencies })
+    }
+
+    // Reads a square matrix of milliseconds, one row per line, without a header.
+    pub fn from_csv<P: AsRef<Path>>(path: P) -> Result<Option<Self>, csv::Error> {
+        let mut reader = csv::ReaderBuilder::new().has_headers(false).from_path(path)?;
+        let mut latencies = Vec::new();
+        for record in reader.deserialize() {
+            latencies.push(record?);
+        }
+        Ok(LatencyMatrix::new(latencies))
+    }
+
+    // Places participants uniformly on a plane and uses distance as latency,
+    // a rough stand-in for a geo-distributed deployment.
+    pub fn synthetic<RNG: RngCore>(system_size: usize, max_latency_ms: f64, rng: &mut RNG) -> Self {
+        let points: Vec<(f64, f64)> = (0..system_size).map(|_| (rng.r#gen(), rng.r#gen())).collect();
+        let latencies = points
+            .iter()
+            .map(|(x1, y1)| {
+                points
+                    .iter()
+                    .map(|(x2, y2)| ((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt() / 2f64.sqrt() * max_latency_ms)
+                    .collect()
+            })
+            .collect();
+        LatencyMatrix { latencies }
+    }
+
+    pub fn system_size(&self) -> usize {
+        self.latencies.len()
+    }
+
+    pub fn latency(&self, from: usize, to: usize) -> f64 {
+        self.latencies[from][to]
+    }
+
+    // Both FROST rounds wait for the slowest selected signer, so completion
+    // time is two round trips to the worst member of the subset.
+    pub fn completion_time(&self, coordinator: usize, signers: &[usize]) -> f64 {
+        let slowest_round_trip = signers
+            .iter()
+            .map(|&signer| self.round_trip(coordinator, signer))
+            .fold(0.0, f64::max);
+        2.0 * slowest_round_trip
+    }
+
+    pub fn round_trip(&self, coordinator: usize, signer: usize) -> f64 {
+        self.latency(coordinator, signer) + self.latency(signer, coordinator)
+    }
+
+    // All participants, closest (by round trip to `coordinator`) first.
+    pub fn by_round_trip(&self, coordinator: usize) -> Vec<usize> {
+        let mut candidates: Vec<usize> = (0..self.system_size()).collect();
+        candidates.sort_by(|&a, &b| {
+            self.round_trip(coordinator, a)
+                .total_cmp(&self.round_trip(coordinator, b))
+        });
+        candidates
+    }
+
+    // Minimises the t-th order statistic of the round-trip times: taking the
+    // t closest signers is optimal for the max-based completion model above.
+    pub fn select_signers(&self, coordinator: usize, threshold: usize) -> Vec<usize> {
+        let mut candidates = self.by_round_trip(coordinator);
+        candidates.truncate(threshold);
+        candidates.sort_unstable();
+        candidates
+    }
+
+    pub fn random_signers<RNG: RngCore>(&self, threshold: usize, rng: &mut RNG) -> Vec<usize> {
+        let mut signers: Vec<usize> = (0..self.system_size()).collect();
+        signers.shuffle(rng);
+        signers.truncate(threshold);
+        signers.sort_unstable();
+        signers
+    }
+
+    // Returns (optimised, mean of `trials` random selections) completion times.
+    pub fn compare_with_random<RNG: RngCore>(
+        &self,
+        coordinator: usize,
+        threshold: usize,
+        trials: usize,
+        rng: &mut RNG,
+    ) -> (f64, f64) {
+        let optimised = self.completion_time(coordinator, &self.select_signers(coordinator, threshold));
+        let random_total: f64 = (0..trials)
+            .map(|_| self.completion_time(coordinator, &self.random_signers(threshold, rng)))
+            .sum();
+        (optimised, random_total / trials.max(1) as f64)
+    }
+}
+
+pub fn to_identifiers(params: &Params, signers: &[usize]) -> Result<Vec<Identifier>, ParamsError> {
+    signers.iter().map(|&index| params.identifier(index)).collect()
+}
@@ -1,6 +1,61 @@
+pub mod aggregation;
+pub mod approval;
+pub mod artifacts;
+pub mod asserts;
+pub mod attest;
+pub mod backup;
+#[cfg(feature = "bls")]
+pub mod beacon;
+#[cfg(feature = "bls")]
+pub mod bls;
+#[cfg(feature = "secp256k1")]
+pub mod bridge;
+pub mod faults;
+pub mod fixtures;
+pub mod framing;
 pub mod frost;
+pub mod generate;
+pub mod gossip;
+pub mod hash;
+pub mod history;
+pub mod keyfile;
+pub mod keystore;
+pub mod latency;
+pub mod limits;
+pub mod metrics;
+pub mod netsim;
+pub mod nonce_pool;
+pub mod os_counters;
+pub mod paired;
+pub mod params;
+pub mod pipeline;
+pub mod planner;
+pub mod policy;
+pub mod preflight;
+pub mod queue;
+pub mod receipt;
+pub mod registry;
+pub mod results;
+pub mod rng;
+pub mod scenario;
+pub mod scheme;
+pub mod sigfile;
+pub mod speculative;
+#[cfg(feature = "taproot")]
+pub mod taproot;
+pub mod test_vectors;
+#[cfg(test)]
+mod testing;
+pub mod transcript;
+pub mod tweak;
+pub mod verify;
+pub mod wire;
+pub mod workload;
 
 pub trait Settings {
     fn system_size(&self) -> u16;
     fn threshold(&self) -> u16;
+    fn hash_function(&self) -> hash::HashFunction {
+        hash::HashFunction::default()
+    }
 }
@@ -0,0 +1,153 @@
+use frost_core::keys::CoefficientCommitment;
+use frost_ed25519::keys::{KeyPackage, SecretShare, VerifyingShare};
+use frost_ed25519::{self as frost, Ed25519Sha512};
+use frost_ed25519::round1::{SigningCommitments, SigningNonces};
+use frost_ed25519::round2::SignatureShare;
+use multisig::KeypairShare;
+use std::mem::size_of;
+
+use crate::os_counters;
+use crate::params::Params;
+use crate::preflight::PreflightError;
+
+// Rough upper bounds on peak heap usage for one protocol run, computed from
+// the in-memory type sizes before anything is allocated. BTreeMap entries are
+// charged twice their key + value size to cover node overhead.
+//
+// What the estimates imply near n = u16::MAX:
+// - multisig stays linear in n (one keypair per participant, t signatures
+//   per certificate) and remains practical at n = 65535.
+// - FROST with a trusted dealer hands every share a copy of the t-point VSS
+//   commitment, so the dealer output grows with n * t; with t close to n this
+//   is tens of GiB at n = 65535 and the run is refused on ordinary machines.
+// - FROST DKG has every participant receive a t-point commitment from every
+//   other participant, i.e. n^2 * t points, and is impractical well before
+//   n = 10000.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryEstimate {
+    pub setup_bytes: u64,
+    pub signing_bytes: u64,
+}
+
+impl MemoryEstimate {
+    pub fn peak_bytes(&self) -> u64 {
+        self.setup_bytes.max(self.signing_bytes)
+    }
+}
+
+fn map_entry<K, V>() -> u64 {
+    2 * (size_of::<K>() + size_of::<V>()) as u64
+}
+
+pub fn frost_dealer_estimate(params: Params) -> MemoryEstimate {
+    let n = params.system_size() as u64;
+    let t = params.threshold() as u64;
+    let point = size_of::<CoefficientCommitment<Ed25519Sha512>>() as u64;
+    let shares = n * (map_entry::<frost::Identifier, SecretShare>() + t * point);
+    let key_packages = n * map_entry::<frost::Identifier, KeyPackage>();
+    let verifying_shares = n * map_entry::<frost::Identifier, VerifyingShare>();
+    let signing = t
+        * (map_entry::<frost::Identifier, SigningNonces>()
+            + 2 * map_entry::<frost::Identifier, SigningCommitments>()
+            + map_entry::<frost::Identifier, SignatureShare>());
+    MemoryEstimate {
+        setup_bytes: shares + key_packages + verifying_shares,
+        signing_bytes: key_packages + verifying_shares + signing,
+    }
+}
+
+pub fn frost_dkg_estimate(params: Params) -> MemoryEstimate {
+    let n = params.system_size() as u64;
+    let t = params.threshold() as u64;
+    let point = size_of::<CoefficientCommitment<Ed25519Sha512>>() as u64;
+    // Every participant holds a round 1 package (t-point commitment) and a
+    // round 2 package from each of the other n - 1 participants.
+    let packages = n * n.saturating_sub(1) * (t * point + 2 * map_entry::<frost::Identifier, SecretShare>());
+    let dealer = frost_dealer_estimate(params);
+    MemoryEstimate {
+        setup_bytes: packages + dealer.setup_bytes,
+        signing_bytes: dealer.signing_bytes,
+    }
+}
+
+pub fn multisig_estimate(params: Params) -> MemoryEstimate {
+    let n = params.system_size() as u64;
+    let t = params.threshold() as u64;
+    let keys = n * size_of::<KeypairShare>() as u64;
+    // One ed25519 signature (64 bytes) plus bookkeeping per certificate entry.
+    let certificate = t * 2 * ed25519_dalek::SIGNATURE_LENGTH as u64;
+    MemoryEstimate {
+        setup_bytes: keys,
+        signing_bytes: keys + certificate,
+    }
+}
+
+// Outcome of a memory check that did not refuse the run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemoryCheck {
+    Fits { budget: u64 },
+    // /proc/meminfo could not be read, so nothing was checked.
+    Skipped,
+}
+
+// Refuses runs whose estimate exceeds `fraction` of the memory currently
+// available. Without /proc the check is skipped and says so.
+pub fn ensure_fits(estimate: MemoryEstimate, fraction: f64) -> Result<MemoryCheck, PreflightError> {
+    let check = check_against(estimate, os_counters::available_memory(), fraction);
+    if let Ok(MemoryCheck::Skipped) = check {
+        tracing::warn!(required = estimate.peak_bytes(), "available memory unknown, memory check skipped");
+    }
+    check
+}
+
+fn check_against(estimate: MemoryEstimate, available: Option<u64>, fraction: f64) -> Result<MemoryCheck, PreflightError> {
+    let Some(available) = available else {
+        return Ok(MemoryCheck::Skipped);
+    };
+    let budget = (available as f64 * fraction) as u64;
+    if estimate.peak_bytes() > budget {
+        return Err(PreflightError::InsufficientMemory {
+            required: estimate.peak_bytes(),
+            available: budget,
+        });
+    }
+    Ok(MemoryCheck::Fits { budget })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIB: u64 = 1 << 30;
+
+    #[test]
+    fn estimates_grow_as_documented() {
+        let small = Params::new(1_000, 999).unwrap();
+        let large = Params::new(u16::MAX as usize, u16::MAX as usize - 1).unwrap();
+        assert!(multisig_estimate(large).peak_bytes() < GIB);
+        assert!(frost_dealer_estimate(large).peak_bytes() > 10 * GIB);
+        assert!(frost_dkg_estimate(small).peak_bytes() > frost_dealer_estimate(small).peak_bytes());
+    }
+
+    #[test]
+    fn oversized_runs_are_refused() {
+        let estimate = MemoryEstimate {
+            setup_bytes: 3 * GIB,
+            signing_bytes: GIB,
+        };
+        assert_eq!(check_against(estimate, Some(4 * GIB), 1.0), Ok(MemoryCheck::Fits { budget: 4 * GIB }));
+        assert!(matches!(
+            check_against(estimate, Some(4 * GIB), 0.5),
+            Err(PreflightError::InsufficientMemory { required, available }) if required == 3 * GIB && available == 2 * GIB
+        ));
+    }
+
+    #[test]
+    fn missing_meminfo_is_reported_as_skipped() {
+        let estimate = MemoryEstimate {
+            setup_bytes: u64::MAX,
+            signing_bytes: 0,
+        };
+        assert_eq!(check_against(estimate, None, 0.8), Ok(MemoryCheck::Skipped));
+    }
+}
@@ -0,0 +1,149 @@
+use ed25519_dalek::SIGNATURE_LENGTH;
+use frost_ed25519::{self as frost, Signature};
+use old_rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::frost::{FrostPackage, FrostSettings};
+use crate::wire::{self, Encoding, WireError, WireMessage};
+
+#[derive(Debug)]
+pub enum MetricsError {
+    Frost(frost::Error),
+    Wire(WireError),
+}
+
+impl fmt::Display for MetricsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetricsError::Frost(e) => write!(f, "frost error: {e}"),
+            MetricsError::Wire(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for MetricsError {}
+
+impl From<frost::Error> for MetricsError {
+    fn from(e: frost::Error) -> Self {
+        MetricsError::Frost(e)
+    }
+}
+
+impl From<WireError> for MetricsError {
+    fn from(e: WireError) -> Self {
+        MetricsError::Wire(e)
+    }
+}
+
+// Communication cost of one protocol execution, counted as the messages are
+// produced. A round is one request/response exchange between the
+// coordinator and the signers; distributing the final signature is counted
+// in messages and bytes but not as a round.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metrics {
+    pub scheme: String,
+    pub signers: usize,
+    pub messages: u64,
+    pub bytes: u64,
+    pub rounds: u32,
+}
+
+impl Metrics {
+    pub fn new(scheme: &str, signers: usize) -> Self {
+        Metrics {
+            scheme: scheme.to_string(),
+            signers,
+            ..Metrics::default()
+        }
+    }
+
+    pub fn round(&mut self) {
+        self.rounds += 1;
+    }
+
+    pub fn send(&mut self, bytes: usize) {
+        self.messages += 1;
+        self.bytes += bytes as u64;
+    }
+
+    fn send_all(&mut self, messages: &[WireMessage], encoding: Encoding) -> Result<(), WireError> {
+        for message in messages {
+            self.send(message.encode(encoding)?.len());
+        }
+        Ok(())
+    }
+}
+
+// One FROST session, recording every wire message in `encoding`: the
+// commitments, the signing package and the shares, then the final signature
+// to every signer.
+pub fn frost_session<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    message: &[u8],
+    encoding: Encoding,
+    rng: &mut RNG,
+) -> Result<(Signature, Metrics), MetricsError>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let mut metrics = Metrics::new("frost", settings.threshold() as usize);
+    let session = 0;
+
+    metrics.round();
+    let round1 = crate::frost::vote_commitments(settings, packages, rng)?;
+    metrics.send_all(&wire::commitment_messages(session, &round1), encoding)?;
+
+    metrics.round();
+    let round2 = crate::frost::sign_message(settings, packages, &round1, message)?;
+    let nonce_set = wire::nonce_set_message(session, &round2);
+    metrics.send_all(&vec![nonce_set; round2.signature_shares().len()], encoding)?;
+    metrics.send_all(&wire::share_messages(session, &round2), encoding)?;
+
+    let signature = frost::aggregate(round2.signing_package(), round2.signature_shares(), packages.public())?;
+    let final_signature = wire::final_signature_message(session, &round2, signature);
+    metrics.send_all(&vec![final_signature; round2.signature_shares().len()], encoding)?;
+    Ok((signature, metrics))
+}
+
+// Multisig needs no coordinator request beyond the message itself: each of
+// the t signers sends one raw ed25519 signature, and the certificate of t
+// signatures goes back to each of them.
+pub fn multisig_session(threshold: usize) -> Metrics {
+    let mut metrics = Metrics::new("multisig", threshold);
+    metrics.round();
+    for _ in 0..threshold {
+        metrics.send(SIGNATURE_LENGTH);
+    }
+    for _ in 0..threshold {
+        metrics.send(threshold * SIGNATURE_LENGTH);
+    }
+    metrics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn frost_counts_every_wire_message() {
+        let (settings, package) = testing::committee(1);
+        for encoding in Encoding::ALL {
+            let (signature, metrics) =
+                frost_session(&settings, &package, b"metrics", encoding, &mut testing::rng(2)).unwrap();
+            package.public().verifying_key().verify(b"metrics", &signature).unwrap();
+            // Commitments, nonce sets, shares and final signatures, one per signer each.
+            assert_eq!((metrics.signers, metrics.messages, metrics.rounds), (5, 20, 2));
+            assert!(metrics.bytes > 20 * 64);
+        }
+    }
+
+    #[test]
+    fn multisig_is_one_round_of_raw_signatures() {
+        let metrics = multisig_session(5);
+        assert_eq!((metrics.messages, metrics.rounds), (10, 1));
+        assert_eq!(metrics.bytes, (5 * 64 + 5 * 5 * 64) as u64);
+    }
+}
@@ -0,0 +1,792 @@
+use old_rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+
+use crate::latency::LatencyMatrix;
+
+// Discrete-event simulation of one signing run over a lossy network. Only
+// message flow is simulated, no cryptography: the point is to compare how
+// the protocols' round structure reacts to the same latencies and losses.
+// Times are simulated milliseconds.
+
+// Per-link one-way delay is the matrix latency plus uniform jitter in
+// [0, jitter_ms]; every message is independently lost with
+// `drop_probability`.
+#[derive(Clone, Debug)]
+pub struct NetworkModel {
+    pub latencies: LatencyMatrix,
+    pub jitter_ms: f64,
+    pub drop_probability: f64,
+    // Signer-side time to produce a response.
+    pub processing_ms: f64,
+}
+
+impl NetworkModel {
+    fn delay<RNG: RngCore>(&self, from: usize, to: usize, rng: &mut RNG) -> Option<f64> {
+        if rng.r#gen::<f64>() < self.drop_probability {
+            return None;
+        }
+        Some(self.latencies.latency(from, to) + rng.r#gen::<f64>() * self.jitter_ms)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimProtocol {
+    // Two rounds with a fixed set of the t closest signers.
+    Frost,
+    // Commitments from everyone; a new session for every t ready signers, and
+    // each share comes with the signer's next commitment.
+    Roast,
+    // One round; the first t signatures win.
+    Multisig,
+}
+
+impl SimProtocol {
+    pub const ALL: [SimProtocol; 3] = [SimProtocol::Frost, SimProtocol::Roast, SimProtocol::Multisig];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SimProtocol::Frost => "frost",
+            SimProtocol::Roast => "roast",
+            SimProtocol::Multisig => "multisig",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Message {
+    CommitRequest,
+    Commitment,
+    SigningPackage { session: usize },
+    Share { session: usize },
+    SignRequest,
+    Signature,
+    // Coordinator-local retransmission timer; timers of an earlier epoch
+    // are ignored after a restart.
+    Timer { epoch: usize },
+    // The coordinator comes back after a restart.
+    Restart { epoch: usize },
+    // Scenario event with this index.
+    Script(usize),
+}
+
+#[derive(Debug)]
+struct Event {
+    time: f64,
+    seq: u64,
+    from: usize,
+    to: usize,
+    message: Message,
+}
+
+// Earliest event first; `seq` keeps equal times in send order.
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.time.total_cmp(&self.time).then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Event {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimReport {
+    pub protocol: SimProtocol,
+    // None if the run did not finish before the deadline.
+    pub completion_ms: Option<f64>,
+    pub messages_sent: u64,
+    pub messages_dropped: u64,
+    pub sessions: usize,
+    // Communication rounds on the critical path to the signature: 2 for
+    // FROST, 1 for multisig, and for ROAST the round of the session that
+    // completed. The initial commitments are round 1; a ROAST session opened
+    // on commitments from round r or earlier runs in round r + 1, so a run
+    // without faults takes 2. Retransmissions do not add rounds.
+    pub rounds: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SimConfig {
+    pub coordinator: usize,
+    pub threshold: usize,
+    // Requests still owed a response are re-sent this often.
+    pub retransmit_ms: f64,
+    pub deadline_ms: f64,
+}
+
+// Changes to the run at a given time, for scripted adversarial scenarios.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ScenarioAction {
+    // Messages to the signer are lost until it comes back online.
+    Offline { signer: usize },
+    Online { signer: usize },
+    // The signer behaves like a crashed, slow, silent or invalid one of
+    // `SignerFaults` from now on, until it turns honest again.
+    Crash { signer: usize },
+    Slow { signer: usize, delay_ms: f64 },
+    Silent { signer: usize },
+    Invalid { signer: usize },
+    Honest { signer: usize },
+    // The coordinator loses all protocol state, misses everything sent to
+    // it for `downtime_ms` and then starts over.
+    CoordinatorRestart { downtime_ms: f64 },
+    // Messages between `signers` and everyone else are lost until `until_ms`.
+    Partition { signers: BTreeSet<usize>, until_ms: f64 },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioEvent {
+    pub at_ms: f64,
+    #[serde(flatten)]
+    pub action: ScenarioAction,
+}
+
+// Misbehaving signers. Crashed signers never answer anything; slow ones
+// answer every request this many milliseconds late. Silent signers answer
+// commitment requests but never send a share (or, under multisig, a
+// signature); invalid signers send one that fails verification. FROST has no
+// way around a crashed, silent or invalid signer within a run, so it only
+// completes if none of its fixed signers is one; ROAST stops using a signer
+// once it has sent an invalid share.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SignerFaults {
+    pub crashed: BTreeSet<usize>,
+    pub slow: BTreeMap<usize, f64>,
+    pub silent: BTreeSet<usize>,
+    pub invalid: BTreeSet<usize>,
+}
+
+// Signers from the highest index down, never the coordinator.
+fn highest_signers(system_size: usize, coordinator: usize) -> impl Iterator<Item = usize> {
+    (0..system_size).rev().filter(move |&signer| signer != coordinator)
+}
+
+impl SignerFaults {
+    // `silent` then `invalid` signers taken from the highest indices, never
+    // the coordinator.
+    pub fn highest(system_size: usize, coordinator: usize, silent: usize, invalid: usize) -> SignerFaults {
+        let mut candidates = highest_signers(system_size, coordinator);
+        SignerFaults {
+            silent: candidates.by_ref().take(silent).collect(),
+            invalid: candidates.take(invalid).collect(),
+            ..SignerFaults::default()
+        }
+    }
+
+    // `count` crashed signers, chosen like in `highest`.
+    pub fn crashed(system_size: usize, coordinator: usize, count: usize) -> SignerFaults {
+        SignerFaults {
+            crashed: highest_signers(system_size, coordinator).take(count).collect(),
+            ..SignerFaults::default()
+        }
+    }
+
+    // `count` signers answering `delay_ms` late, chosen like in `highest`.
+    pub fn slow(system_size: usize, coordinator: usize, count: usize, delay_ms: f64) -> SignerFaults {
+        SignerFaults {
+            slow: highest_signers(system_size, coordinator)
+                .take(count)
+                .map(|signer| (signer, delay_ms))
+                .collect(),
+            ..SignerFaults::default()
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.crashed.len() + self.slow.len() + self.silent.len() + self.invalid.len()
+    }
+
+    fn response_delay(&self, signer: usize) -> f64 {
+        self.slow.get(&signer).copied().unwrap_or(0.0)
+    }
+
+    // Makes `signer` honest again.
+    fn forget(&mut self, signer: usize) {
+        self.crashed.remove(&signer);
+        self.slow.remove(&signer);
+        self.silent.remove(&signer);
+        self.invalid.remove(&signer);
+    }
+}
+
+struct Simulator<'a, RNG> {
+    network: &'a NetworkModel,
+    rng: &'a mut RNG,
+    queue: BinaryHeap<Event>,
+    now: f64,
+    seq: u64,
+    sent: u64,
+    dropped: u64,
+    // Active partitions: until when, and one side of the cut.
+    partitions: Vec<(f64, BTreeSet<usize>)>,
+}
+
+impl<RNG: RngCore> Simulator<'_, RNG> {
+    fn push(&mut self, time: f64, from: usize, to: usize, message: Message) {
+        self.seq += 1;
+        self.queue.push(Event {
+            time,
+            seq: self.seq,
+            from,
+            to,
+            message,
+        });
+    }
+
+    fn send(&mut self, from: usize, to: usize, message: Message) {
+        self.sent += 1;
+        let cut = self
+            .partitions
+            .iter()
+            .any(|(until, side)| self.now < *until && side.contains(&from) != side.contains(&to));
+        match self.network.delay(from, to, self.rng) {
+            Some(delay) if !cut => self.push(self.now + delay, from, to, message),
+            _ => self.dropped += 1,
+        }
+    }
+
+    fn timer(&mut self, coordinator: usize, after_ms: f64, epoch: usize) {
+        self.push(self.now + after_ms, coordinator, coordinator, Message::Timer { epoch });
+    }
+}
+
+// Coordinator state; signers are stateless responders.
+#[derive(Default)]
+struct Coordinator {
+    // Signers asked in the current phase, and those that answered.
+    asked: BTreeSet<usize>,
+    answered: BTreeSet<usize>,
+    // ROAST: signers with an unused commitment and the round it was sent in,
+    // and members, shares and round per session.
+    ready: Vec<(usize, usize)>,
+    sessions: BTreeMap<usize, (Vec<usize>, BTreeSet<usize>, usize)>,
+    // FROST: whether round 2 has started.
+    signing: bool,
+    // ROAST: signers caught sending an invalid share.
+    malicious: BTreeSet<usize>,
+    // Restarts so far; FROST runs session `epoch`. ROAST session ids
+    // continue after the `abandoned` sessions opened before the last restart.
+    epoch: usize,
+    abandoned: usize,
+    down: bool,
+}
+
+// Sends the first request of `protocol` and arms the retransmission timer,
+// at the start and again after a coordinator restart.
+fn start<RNG: RngCore>(protocol: SimProtocol, sim: &mut Simulator<'_, RNG>, state: &mut Coordinator, config: &SimConfig) {
+    let everyone = 0..sim.network.latencies.system_size();
+    let request = match protocol {
+        SimProtocol::Frost => {
+            state.asked = sim.network.latencies.select_signers(config.coordinator, config.threshold).into_iter().collect();
+            Message::CommitRequest
+        }
+        SimProtocol::Roast => {
+            state.asked = everyone.collect();
+            Message::CommitRequest
+        }
+        SimProtocol::Multisig => {
+            state.asked = everyone.collect();
+            Message::SignRequest
+        }
+    };
+    for signer in state.asked.clone() {
+        sim.send(config.coordinator, signer, request);
+    }
+    sim.timer(config.coordinator, config.retransmit_ms, state.epoch);
+}
+
+pub fn simulate<RNG: RngCore>(
+    protocol: SimProtocol,
+    network: &NetworkModel,
+    config: &SimConfig,
+    rng: &mut RNG,
+) -> SimReport {
+    simulate_with_faults(protocol, network, config, &SignerFaults::default(), rng)
+}
+
+pub fn simulate_with_faults<RNG: RngCore>(
+    protocol: SimProtocol,
+    network: &NetworkModel,
+    config: &SimConfig,
+    faults: &SignerFaults,
+    rng: &mut RNG,
+) -> SimReport {
+    simulate_scenario(protocol, network, config, faults, &[], rng)
+}
+
+// Same, with `events` applied at their times. Events at time zero take
+// effect before the first request is sent.
+pub fn simulate_scenario<RNG: RngCore>(
+    protocol: SimProtocol,
+    network: &NetworkModel,
+    config: &SimConfig,
+    faults: &SignerFaults,
+    events: &[ScenarioEvent],
+    rng: &mut RNG,
+) -> SimReport {
+    let mut sim = Simulator {
+        network,
+        rng,
+        queue: BinaryHeap::new(),
+        now: 0.0,
+        seq: 0,
+        sent: 0,
+        dropped: 0,
+        partitions: Vec::new(),
+    };
+    let mut state = Coordinator::default();
+    let mut faults = faults.clone();
+    let mut offline = BTreeSet::new();
+    let coordinator = config.coordinator;
+    let threshold = config.threshold;
+
+    for (index, event) in events.iter().enumerate() {
+        if event.at_ms <= 0.0 {
+            apply(&event.action, &mut sim, &mut state, &mut faults, &mut offline, coordinator);
+        } else {
+            sim.push(event.at_ms, coordinator, coordinator, Message::Script(index));
+        }
+    }
+    start(protocol, &mut sim, &mut state, config);
+
+    let mut completion_ms = None;
+    let mut rounds = match protocol {
+        SimProtocol::Frost => 2,
+        SimProtocol::Roast => 0,
+        SimProtocol::Multisig => 1,
+    };
+    while let Some(event) = sim.queue.pop() {
+        if event.time > config.deadline_ms {
+            break;
+        }
+        sim.now = event.time;
+        let processing = network.processing_ms;
+        match event.message {
+            Message::Script(index) => {
+                apply(&events[index].action, &mut sim, &mut state, &mut faults, &mut offline, coordinator);
+            }
+            Message::Restart { epoch } => {
+                if epoch == state.epoch {
+                    state.down = false;
+                    start(protocol, &mut sim, &mut state, config);
+                }
+            }
+            Message::CommitRequest | Message::SigningPackage { .. } | Message::SignRequest
+                if offline.contains(&event.to) =>
+            {
+                sim.dropped += 1;
+            }
+            // Requests reach a crashed signer, which never answers them.
+            Message::CommitRequest | Message::SigningPackage { .. } | Message::SignRequest
+                if faults.crashed.contains(&event.to) => {}
+            Message::Commitment | Message::Share { .. } | Message::Signature if state.down => {
+                sim.dropped += 1;
+            }
+
+            // Signer side.
+            Message::CommitRequest => {
+                sim.now += processing + faults.response_delay(event.to);
+                sim.send(event.to, event.from, Message::Commitment);
+            }
+            Message::SigningPackage { session } => {
+                if !faults.silent.contains(&event.to) {
+                    sim.now += processing + faults.response_delay(event.to);
+                    sim.send(event.to, event.from, Message::Share { session });
+                }
+            }
+            Message::SignRequest => {
+                if !faults.silent.contains(&event.to) {
+                    sim.now += processing + faults.response_delay(event.to);
+                    sim.send(event.to, event.from, Message::Signature);
+                }
+            }
+
+            // Coordinator side.
+            Message::Timer { epoch } => {
+                if epoch != state.epoch {
+                    continue;
+                }
+                let request = match (protocol, state.signing) {
+                    (SimProtocol::Frost, true) => Message::SigningPackage { session: state.epoch },
+                    (SimProtocol::Multisig, _) => Message::SignRequest,
+                    _ => Message::CommitRequest,
+                };
+                let silent: Vec<usize> = state.asked.difference(&state.answered).copied().collect();
+                for signer in silent {
+                    sim.send(coordinator, signer, request);
+                }
+                sim.timer(coordinator, config.retransmit_ms, state.epoch);
+            }
+            Message::Commitment => match protocol {
+                SimProtocol::Frost if !state.signing => {
+                    state.answered.insert(event.from);
+                    if state.answered.len() == threshold {
+                        state.signing = true;
+                        state.answered.clear();
+                        for signer in state.asked.clone() {
+                            sim.send(coordinator, signer, Message::SigningPackage { session: state.epoch });
+                        }
+                    }
+                }
+                SimProtocol::Roast if state.answered.insert(event.from) => {
+                    state.ready.push((event.from, 1));
+                    open_roast_sessions(&mut sim, &mut state, coordinator, threshold);
+                }
+                _ => {}
+            },
+            Message::Share { session } => {
+                let invalid = faults.invalid.contains(&event.from);
+                if protocol == SimProtocol::Roast {
+                    if invalid {
+                        state.malicious.insert(event.from);
+                        continue;
+                    }
+                    let Some((members, shares, round)) = state.sessions.get_mut(&session) else { continue };
+                    if !members.contains(&event.from) || !shares.insert(event.from) {
+                        continue;
+                    }
+                    if shares.len() == threshold {
+                        completion_ms = Some(sim.now);
+                        rounds = *round;
+                        break;
+                    }
+                    // The share carries the signer's next commitment.
+                    let round = *round;
+                    state.ready.push((event.from, round));
+                    open_roast_sessions(&mut sim, &mut state, coordinator, threshold);
+                } else if !invalid
+                    && state.signing
+                    && session == state.epoch
+                    && state.answered.insert(event.from)
+                    && state.answered.len() == threshold
+                {
+                    completion_ms = Some(sim.now);
+                    break;
+                }
+            }
+            Message::Signature => {
+                if faults.invalid.contains(&event.from) {
+                    continue;
+                }
+                if state.answered.insert(event.from) && state.answered.len() == threshold {
+                    completion_ms = Some(sim.now);
+                    break;
+                }
+            }
+        }
+    }
+
+    SimReport {
+        protocol,
+        completion_ms,
+        messages_sent: sim.sent,
+        messages_dropped: sim.dropped,
+        sessions: match protocol {
+            SimProtocol::Roast => state.abandoned + state.sessions.len(),
+            SimProtocol::Frost => state.epoch + 1,
+            SimProtocol::Multisig => 1,
+        },
+        rounds,
+    }
+}
+
+fn open_roast_sessions<RNG: RngCore>(
+    sim: &mut Simulator<'_, RNG>,
+    state: &mut Coordinator,
+    coordinator: usize,
+    threshold: usize,
+) {
+    while state.ready.len() >= threshold {
+        let ready: Vec<(usize, usize)> = state.ready.drain(..threshold).collect();
+        let round = ready.iter().map(|&(_, round)| round).max().unwrap_or(0) + 1;
+        let members: Vec<usize> = ready.into_iter().map(|(signer, _)| signer).collect();
+        let session = state.abandoned + state.sessions.len();
+        for &signer in &members {
+            sim.send(coordinator, signer, Message::SigningPackage { session });
+        }
+        state.sessions.insert(session, (members, BTreeSet::new(), round));
+    }
+}
+
+fn apply<RNG: RngCore>(
+    action: &ScenarioAction,
+    sim: &mut Simulator<'_, RNG>,
+    state: &mut Coordinator,
+    faults: &mut SignerFaults,
+    offline: &mut BTreeSet<usize>,
+    coordinator: usize,
+) {
+    match action {
+        ScenarioAction::Offline { signer } => {
+            offline.insert(*signer);
+        }
+        ScenarioAction::Online { signer } => {
+            offline.remove(signer);
+        }
+        ScenarioAction::Crash { signer } => {
+            faults.forget(*signer);
+            faults.crashed.insert(*signer);
+        }
+        ScenarioAction::Slow { signer, delay_ms } => {
+            faults.forget(*signer);
+            faults.slow.insert(*signer, *delay_ms);
+        }
+        ScenarioAction::Silent { signer } => {
+            faults.forget(*signer);
+            faults.silent.insert(*signer);
+        }
+        ScenarioAction::Invalid { signer } => {
+            faults.forget(*signer);
+            faults.invalid.insert(*signer);
+        }
+        ScenarioAction::Honest { signer } => {
+            faults.forget(*signer);
+        }
+        ScenarioAction::CoordinatorRestart { downtime_ms } => {
+            let abandoned = state.abandoned + state.sessions.len();
+            *state = Coordinator {
+                epoch: state.epoch + 1,
+                abandoned,
+                down: true,
+                ..Coordinator::default()
+            };
+            sim.push(sim.now + downtime_ms, coordinator, coordinator, Message::Restart { epoch: state.epoch });
+        }
+        ScenarioAction::Partition { signers, until_ms } => {
+            sim.partitions.push((*until_ms, signers.clone()));
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FallbackReport {
+    pub roast: SimReport,
+    // The multisig run, started when ROAST missed its deadline.
+    pub multisig: Option<SimReport>,
+    // From the start of the ROAST run, whichever protocol finished.
+    pub completion_ms: Option<f64>,
+}
+
+impl FallbackReport {
+    pub fn fell_back(&self) -> bool {
+        self.multisig.is_some()
+    }
+}
+
+// A hybrid deployment within one epoch: ROAST gets `roast_deadline_ms`, and
+// if it has not produced a signature by then the coordinator abandons it and
+// collects a plain multisig certificate from whoever answers, until
+// `config.deadline_ms`. The signers keep their faults across the switch.
+pub fn simulate_fallback<RNG: RngCore>(
+    network: &NetworkModel,
+    config: &SimConfig,
+    roast_deadline_ms: f64,
+    faults: &SignerFaults,
+    rng: &mut RNG,
+) -> FallbackReport {
+    let roast_config = SimConfig {
+        deadline_ms: roast_deadline_ms.min(config.deadline_ms),
+        ..*config
+    };
+    let roast = simulate_with_faults(SimProtocol::Roast, network, &roast_config, faults, rng);
+    if roast.completion_ms.is_some() || roast_config.deadline_ms >= config.deadline_ms {
+        return FallbackReport {
+            roast,
+            multisig: None,
+            completion_ms: roast.completion_ms,
+        };
+    }
+    let multisig_config = SimConfig {
+        deadline_ms: config.deadline_ms - roast_config.deadline_ms,
+        ..*config
+    };
+    let multisig = simulate_with_faults(SimProtocol::Multisig, network, &multisig_config, faults, rng);
+    FallbackReport {
+        roast,
+        multisig: Some(multisig),
+        completion_ms: multisig.completion_ms.map(|ms| roast_config.deadline_ms + ms),
+    }
+}
+
+// Runs every protocol `trials` times under the same network.
+pub fn compare<RNG: RngCore>(network: &NetworkModel, config: &SimConfig, trials: usize, rng: &mut RNG) -> Vec<SimReport> {
+    SimProtocol::ALL
+        .iter()
+        .flat_map(|&protocol| (0..trials).map(move |_| protocol))
+        .map(|protocol| simulate(protocol, network, config, rng))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    // 5-of-7 with 10 ms between any two participants and no jitter, so every
+    // completion time can be worked out by hand.
+    fn uniform(drop_probability: f64) -> (NetworkModel, SimConfig) {
+        let latencies = (0..7)
+            .map(|from| (0..7).map(|to| if from == to { 0.0 } else { 10.0 }).collect())
+            .collect();
+        let network = NetworkModel {
+            latencies: LatencyMatrix::new(latencies).unwrap(),
+            jitter_ms: 0.0,
+            drop_probability,
+            processing_ms: 1.0,
+        };
+        let config = SimConfig {
+            coordinator: 0,
+            threshold: 5,
+            retransmit_ms: 500.0,
+            deadline_ms: 10_000.0,
+        };
+        (network, config)
+    }
+
+    #[test]
+    fn round_structure_sets_completion_time() {
+        let (network, config) = uniform(0.0);
+        let mut rng = testing::rng(1);
+        let mut run = |protocol| simulate(protocol, &network, &config, &mut rng);
+
+        let multisig = run(SimProtocol::Multisig);
+        assert_eq!((multisig.completion_ms, multisig.rounds), (Some(21.0), 1));
+        let frost = run(SimProtocol::Frost);
+        assert_eq!((frost.completion_ms, frost.rounds), (Some(42.0), 2));
+        let roast = run(SimProtocol::Roast);
+        assert_eq!((roast.completion_ms, roast.rounds), (Some(42.0), 2));
+        assert_eq!(roast.messages_dropped, 0);
+    }
+
+    #[test]
+    fn roast_routes_around_faulty_signers() {
+        let (network, config) = uniform(0.0);
+        let faults = SignerFaults {
+            silent: BTreeSet::from([1]),
+            invalid: BTreeSet::from([2]),
+            ..SignerFaults::default()
+        };
+        let mut rng = testing::rng(2);
+        let roast = simulate_with_faults(SimProtocol::Roast, &network, &config, &faults, &mut rng);
+        assert_eq!((roast.completion_ms, roast.rounds, roast.sessions), (Some(63.0), 3, 2));
+        // FROST's fixed signer set includes the silent signer.
+        let frost = simulate_with_faults(SimProtocol::Frost, &network, &config, &faults, &mut rng);
+        assert_eq!(frost.completion_ms, None);
+    }
+
+    #[test]
+    fn crashed_signers_stall_frost_but_not_roast() {
+        let (network, config) = uniform(0.0);
+        let mut rng = testing::rng(5);
+        // Up to n - t crashed signers leave ROAST's completion time untouched.
+        for crashed in [BTreeSet::from([1]), BTreeSet::from([1, 2])] {
+            let faults = SignerFaults {
+                crashed,
+                ..SignerFaults::default()
+            };
+            let roast = simulate_with_faults(SimProtocol::Roast, &network, &config, &faults, &mut rng);
+            assert_eq!(roast.completion_ms, Some(42.0));
+            // Signer 1 is one of FROST's t closest.
+            let frost = simulate_with_faults(SimProtocol::Frost, &network, &config, &faults, &mut rng);
+            assert_eq!(frost.completion_ms, None);
+        }
+        let faults = SignerFaults::crashed(7, config.coordinator, 3);
+        let roast = simulate_with_faults(SimProtocol::Roast, &network, &config, &faults, &mut rng);
+        assert_eq!((roast.completion_ms, roast.sessions), (None, 0));
+    }
+
+    #[test]
+    fn slow_signers_delay_completion() {
+        let (network, config) = uniform(0.0);
+        let mut rng = testing::rng(6);
+        // Two slow signers are never needed.
+        let faults = SignerFaults::slow(7, config.coordinator, 2, 100.0);
+        let roast = simulate_with_faults(SimProtocol::Roast, &network, &config, &faults, &mut rng);
+        assert_eq!(roast.completion_ms, Some(42.0));
+        // With three, the first session waits for a commitment at 121 ms and
+        // the slow signer's share at 242 ms. The second session, opened on the
+        // fast signers' next commitments, cannot finish before that.
+        let faults = SignerFaults::slow(7, config.coordinator, 3, 100.0);
+        let roast = simulate_with_faults(SimProtocol::Roast, &network, &config, &faults, &mut rng);
+        assert_eq!((roast.completion_ms, roast.rounds, roast.sessions), (Some(242.0), 2, 2));
+        let multisig = simulate_with_faults(SimProtocol::Multisig, &network, &config, &faults, &mut rng);
+        assert_eq!(multisig.completion_ms, Some(121.0));
+    }
+
+    #[test]
+    fn roast_completes_with_up_to_n_minus_t_faulty_signers() {
+        let mut rng = testing::rng(4);
+        let network = NetworkModel {
+            latencies: LatencyMatrix::synthetic(7, 150.0, &mut rng),
+            jitter_ms: 20.0,
+            drop_probability: 0.0,
+            processing_ms: 1.0,
+        };
+        let (_, config) = uniform(0.0);
+        for faulty in 0..=2 {
+            for silent in 0..=faulty {
+                let faults = SignerFaults::highest(7, config.coordinator, silent, faulty - silent);
+                for _ in 0..20 {
+                    let report = simulate_with_faults(SimProtocol::Roast, &network, &config, &faults, &mut rng);
+                    assert!(report.completion_ms.is_some(), "{silent} silent, {} invalid", faulty - silent);
+                    assert!(report.sessions >= 1 && report.rounds >= 2);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fallback_triggers_only_when_roast_misses_its_deadline() {
+        let (network, config) = uniform(0.0);
+        let mut rng = testing::rng(7);
+        let report = simulate_fallback(&network, &config, 100.0, &SignerFaults::default(), &mut rng);
+        assert!(!report.fell_back());
+        assert_eq!(report.completion_ms, Some(42.0));
+
+        // ROAST would wait for a slow signer until 242 ms; multisig, started
+        // at 100 ms, has its fifth signature 121 ms later.
+        let faults = SignerFaults::slow(7, config.coordinator, 3, 100.0);
+        let report = simulate_fallback(&network, &config, 100.0, &faults, &mut rng);
+        assert!(report.fell_back());
+        assert_eq!(report.roast.completion_ms, None);
+        assert_eq!(report.completion_ms, Some(221.0));
+
+        // Without time left after ROAST there is nothing to fall back to.
+        let report = simulate_fallback(&network, &config, config.deadline_ms, &faults, &mut rng);
+        assert!(!report.fell_back());
+        assert_eq!(report.completion_ms, Some(242.0));
+    }
+
+    #[test]
+    fn nothing_completes_when_every_message_is_lost() {
+        let (network, config) = uniform(1.0);
+        for report in compare(&network, &config, 2, &mut testing::rng(3)) {
+            assert_eq!(report.completion_ms, None);
+            assert_eq!(report.messages_dropped, report.messages_sent);
+        }
+    }
+
+    #[test]
+    fn highest_faults_skip_the_coordinator() {
+        let faults = SignerFaults::highest(7, 6, 1, 2);
+        assert_eq!(faults.silent, BTreeSet::from([5]));
+        assert_eq!(faults.invalid, BTreeSet::from([3, 4]));
+        assert_eq!(faults.count(), 3);
+        assert_eq!(SignerFaults::crashed(7, 6, 2).crashed, BTreeSet::from([4, 5]));
+        assert_eq!(SignerFaults::slow(7, 0, 1, 50.0).slow, BTreeMap::from([(6, 50.0)]));
+    }
+}
@@ -0,0 +1,91 @@
+use frost_ed25519::round1::{SigningCommitments, SigningNonces};
+use frost_ed25519::{self as frost, Identifier};
+use old_rand::{CryptoRng, RngCore};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+
+use crate::frost::{FrostPackage, FrostRound1};
+
+#[derive(Debug)]
+pub enum NoncePoolError {
+    Exhausted(Identifier),
+    Frost(frost::Error),
+}
+
+impl fmt::Display for NoncePoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NoncePoolError::Exhausted(id) => write!(f, "no precomputed nonces left for signer {id:?}"),
+            NoncePoolError::Frost(e) => write!(f, "frost error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for NoncePoolError {}
+
+impl From<frost::Error> for NoncePoolError {
+    fn from(e: frost::Error) -> Self {
+        NoncePoolError::Frost(e)
+    }
+}
+
+// Round 1 preprocessing: nonce/commitment pairs generated ahead of time, so
+// the online part of a session is only round 2. Pairs are moved out of the
+// pool when handed to a session, so no nonce can be used twice.
+#[derive(Default)]
+pub struct NoncePool {
+    pairs: BTreeMap<Identifier, VecDeque<(SigningNonces, SigningCommitments)>>,
+}
+
+impl NoncePool {
+    pub fn new() -> Self {
+        NoncePool::default()
+    }
+
+    // Adds `per_signer` fresh pairs for each of `signers`.
+    pub fn precompute<RNG>(
+        &mut self,
+        packages: &FrostPackage,
+        signers: &[Identifier],
+        per_signer: usize,
+        rng: &mut RNG,
+    ) -> Result<(), NoncePoolError>
+    where
+        RNG: RngCore + CryptoRng,
+    {
+        for signer in signers {
+            let key_package = packages.secret().get(signer).ok_or(frost::Error::UnknownIdentifier)?;
+            let queue = self.pairs.entry(*signer).or_default();
+            for _ in 0..per_signer {
+                queue.push_back(frost::round1::commit(key_package.signing_share(), rng));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn remaining(&self, signer: &Identifier) -> usize {
+        self.pairs.get(signer).map_or(0, VecDeque::len)
+    }
+
+    // Hands out one pair per signer for a new session. Either every signer
+    // gets a pair or the pool is left untouched.
+    pub fn take_round1(&mut self, signers: &[Identifier]) -> Result<FrostRound1, NoncePoolError> {
+        let signers: BTreeSet<Identifier> = signers.iter().copied().collect();
+        if let Some(signer) = signers.iter().find(|signer| self.remaining(signer) == 0) {
+            return Err(NoncePoolError::Exhausted(*signer));
+        }
+
+        let mut nonces = BTreeMap::new();
+        let mut commitments = BTreeMap::new();
+        for signer in signers {
+            let (signer_nonces, signer_commitments) = self
+                .pairs
+                .get_mut(&signer)
+                .and_then(VecDeque::pop_front)
+                .expect("availability was checked above");
+            nonces.insert(signer, signer_nonces);
+            commitments.insert(signer, signer_commitments);
+        }
+        Ok(FrostRound1 { nonces, commitments })
+    }
+}
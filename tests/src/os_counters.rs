@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+// Process-wide scheduler and memory counters read from /proc. They are used
+// to explain run-to-run variance (preemption, page faults, migrations between
+// cores) in large-n measurements; on systems without /proc nothing is sampled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OsCounters {
+    pub voluntary_context_switches: u64,
+    pub involuntary_context_switches: u64,
+    pub minor_page_faults: u64,
+    pub major_page_faults: u64,
+    pub cpu_migrations: u64,
+}
+
+impl OsCounters {
+    pub fn sample() -> Option<OsCounters> {
+        let status = fs::read_to_string("/proc/self/status").ok()?;
+        let stat = fs::read_to_string("/proc/self/stat").ok()?;
+        // /proc/self/sched needs CONFIG_SCHED_DEBUG; migrations read as 0 without it.
+        let sched = fs::read_to_string("/proc/self/sched").unwrap_or_default();
+
+        // Fields after the parenthesised command name start at field 3 (state),
+        // so minflt (field 10) and majflt (field 12) are at offsets 7 and 9.
+        let stat_fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+
+        Some(OsCounters {
+            voluntary_context_switches: keyed_value(&status, "voluntary_ctxt_switches:")?,
+            involuntary_context_switches: keyed_value(&status, "nonvoluntary_ctxt_switches:")?,
+            minor_page_faults: stat_fields.get(7)?.parse().ok()?,
+            major_page_faults: stat_fields.get(9)?.parse().ok()?,
+            cpu_migrations: keyed_value(&sched, "se.nr_migrations").unwrap_or(0),
+        })
+    }
+
+    pub fn since(&self, earlier: &OsCounters) -> OsCounters {
+        OsCounters {
+            voluntary_context_switches: self.voluntary_context_switches.saturating_sub(earlier.voluntary_context_switches),
+            involuntary_context_switches: self
+                .involuntary_context_switches
+                .saturating_sub(earlier.involuntary_context_switches),
+            minor_page_faults: self.minor_page_faults.saturating_sub(earlier.minor_page_faults),
+            major_page_faults: self.major_page_faults.saturating_sub(earlier.major_page_faults),
+            cpu_migrations: self.cpu_migrations.saturating_sub(earlier.cpu_migrations),
+        }
+    }
+}
+
+// Runs `f` and returns its result with the counters accumulated meanwhile.
+pub fn sample_during<F, R>(f: F) -> (R, Option<OsCounters>)
+where
+    F: FnOnce() -> R,
+{
+    let before = OsCounters::sample();
+    let result = f();
+    let after = OsCounters::sample();
+    let delta = match (before, after) {
+        (Some(before), Some(after)) => Some(after.since(&before)),
+        _ => None,
+    };
+    (result, delta)
+}
+
+fn keyed_value(contents: &str, key: &str) -> Option<u64> {
+    contents
+        .lines()
+        .find(|line| line.trim_start().starts_with(key))?
+        .rsplit(|c: char| c == ':' || c.is_whitespace())
+        .find(|field| !field.is_empty())?
+        .parse()
+        .ok()
+}
+
+// Memory the kernel considers available for new allocations, in bytes.
+pub fn available_memory() -> Option<u64> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    let kib: u64 = meminfo
+        .lines()
+        .find(|line| line.starts_with("MemAvailable:"))?
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
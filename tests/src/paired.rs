@@ -0,0 +1,179 @@
+use frost_ed25519 as frost;
+use multisig::{Committee, KeypairShare};
+use old_rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::time::Instant;
+
+use crate::frost::{FrostPackage, FrostSettings};
+
+// The same logical participants hold both an ed25519 multisig keypair and a
+// FROST share: participant `i` owns `multisig_keys[i]` and the FROST share of
+// `params.identifier(i)`. Every message is signed by the same signer subset
+// under both schemes, so per-message timings form matched pairs.
+pub struct PairedCommittee {
+    multisig_keys: Vec<KeypairShare>,
+    committee: Committee,
+    frost: FrostPackage,
+}
+
+impl PairedCommittee {
+    pub fn new<RNG>(settings: &FrostSettings, rng: &mut RNG) -> Result<Self, frost::Error>
+    where
+        RNG: RngCore + CryptoRng,
+    {
+        let multisig_keys: Vec<KeypairShare> = (0..settings.system_size()).map(|_| KeypairShare::default()).collect();
+        let mut committee = Committee::new();
+        for keypair in &multisig_keys {
+            committee.add_key(keypair.verifying_share.clone());
+        }
+        let frost = crate::frost::setup(settings, rng)?;
+        Ok(PairedCommittee { multisig_keys, committee, frost })
+    }
+
+    pub fn frost(&self) -> &FrostPackage {
+        &self.frost
+    }
+}
+
+// Timings of one message under both schemes, in microseconds.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PairedSample {
+    pub message: usize,
+    pub multisig_sign_us: f64,
+    pub multisig_verify_us: f64,
+    pub frost_sign_us: f64,
+    pub frost_verify_us: f64,
+}
+
+impl PairedSample {
+    // Positive when FROST is slower end to end for this message.
+    pub fn total_difference_us(&self) -> f64 {
+        (self.frost_sign_us + self.frost_verify_us) - (self.multisig_sign_us + self.multisig_verify_us)
+    }
+}
+
+pub fn run_paired<RNG>(
+    settings: &FrostSettings,
+    committee: &PairedCommittee,
+    messages: &[Vec<u8>],
+    rng: &mut RNG,
+) -> Result<Vec<PairedSample>, frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let threshold = settings.threshold() as usize;
+    let mut samples = Vec::with_capacity(messages.len());
+
+    for (index, message) in messages.iter().enumerate() {
+        let run_multisig = || {
+            let start = Instant::now();
+            let certificate = committee
+                .multisig_keys
+                .iter()
+                .take(threshold)
+                .map(|keypair| keypair.sign(message))
+                .collect::<Vec<_>>();
+            let sign = start.elapsed();
+            let start = Instant::now();
+            let _ = committee.committee.verify(message, &certificate, threshold);
+            (sign, start.elapsed())
+        };
+        let run_frost = |rng: &mut RNG| -> Result<_, frost::Error> {
+            let start = Instant::now();
+            let round1 = crate::frost::vote_commitments(settings, &committee.frost, rng)?;
+            let round2 = crate::frost::sign_message(settings, &committee.frost, &round1, message)?;
+            let sign = start.elapsed();
+            let start = Instant::now();
+            crate::frost::aggregate_verify(settings, &committee.frost, &round1, &round2, message)?;
+            Ok((sign, start.elapsed()))
+        };
+
+        // Alternate which scheme goes first so cache and frequency effects
+        // do not consistently favour one of them.
+        let (multisig_times, frost_times) = if index % 2 == 0 {
+            let m = run_multisig();
+            (m, run_frost(rng)?)
+        } else {
+            let f = run_frost(rng)?;
+            (run_multisig(), f)
+        };
+
+        samples.push(PairedSample {
+            message: index,
+            multisig_sign_us: multisig_times.0.as_secs_f64() * 1e6,
+            multisig_verify_us: multisig_times.1.as_secs_f64() * 1e6,
+            frost_sign_us: frost_times.0.as_secs_f64() * 1e6,
+            frost_verify_us: frost_times.1.as_secs_f64() * 1e6,
+        });
+    }
+    Ok(samples)
+}
+
+// Mean and sample standard deviation of the per-message differences.
+pub fn paired_difference(samples: &[PairedSample]) -> (f64, f64) {
+    let n = samples.len() as f64;
+    if samples.is_empty() {
+        return (0.0, 0.0);
+    }
+    let mean = samples.iter().map(PairedSample::total_difference_us).sum::<f64>() / n;
+    if samples.len() < 2 {
+        return (mean, 0.0);
+    }
+    let variance = samples
+        .iter()
+        .map(|s| (s.total_difference_us() - mean).powi(2))
+        .sum::<f64>()
+        / (n - 1.0);
+    (mean, variance.sqrt())
+}
+
+pub fn write_paired_csv<W: io::Write>(samples: &[PairedSample], writer: W) -> Result<(), csv::Error> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for sample in samples {
+        writer.serialize(sample)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn sample(message: usize, frost_sign_us: f64) -> PairedSample {
+        PairedSample {
+            message,
+            multisig_sign_us: 10.0,
+            multisig_verify_us: 5.0,
+            frost_sign_us,
+            frost_verify_us: 5.0,
+        }
+    }
+
+    #[test]
+    fn difference_is_mean_and_sample_deviation() {
+        assert_eq!(paired_difference(&[]), (0.0, 0.0));
+        assert_eq!(paired_difference(&[sample(0, 20.0)]), (10.0, 0.0));
+        let (mean, std_dev) = paired_difference(&[sample(0, 20.0), sample(1, 30.0), sample(2, 40.0)]);
+        assert_eq!(mean, 20.0);
+        assert_eq!(std_dev, 10.0);
+    }
+
+    #[test]
+    fn every_message_gives_one_sample() {
+        let settings = testing::settings(7, 5);
+        let mut rng = testing::rng(1);
+        let committee = PairedCommittee::new(&settings, &mut rng).unwrap();
+        let messages: Vec<Vec<u8>> = (0..3u8).map(|index| vec![index]).collect();
+        let samples = run_paired(&settings, &committee, &messages, &mut rng).unwrap();
+        assert_eq!(samples.iter().map(|sample| sample.message).collect::<Vec<_>>(), [0, 1, 2]);
+
+        let mut csv = Vec::new();
+        write_paired_csv(&samples, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.starts_with("message,multisig_sign_us,multisig_verify_us,frost_sign_us,frost_verify_us\n"));
+    }
+}
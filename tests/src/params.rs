@@ -0,0 +1,132 @@
+use frost_ed25519::Identifier;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamsError {
+    EmptySystem,
+    ZeroThreshold,
+    ThresholdExceedsSystemSize { system_size: usize, threshold: usize },
+    SystemSizeTooLarge(usize),
+    // FROST requires at least two signers (frost_core rejects min_signers < 2).
+    ThresholdTooSmallForFrost(u16),
+    IndexOutOfRange(usize),
+}
+
+impl fmt::Display for ParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamsError::EmptySystem => write!(f, "system size must be at least 1"),
+            ParamsError::ZeroThreshold => write!(f, "threshold must be at least 1"),
+            ParamsError::ThresholdExceedsSystemSize {
+                system_size,
+                threshold,
+            } => write!(f, "threshold {threshold} exceeds system size {system_size}"),
+            ParamsError::SystemSizeTooLarge(n) => write!(f, "system size {n} exceeds {}", u16::MAX),
+            ParamsError::ThresholdTooSmallForFrost(t) => write!(f, "FROST needs a threshold of at least 2, got {t}"),
+            ParamsError::IndexOutOfRange(i) => write!(f, "participant index {i} is outside the committee"),
+        }
+    }
+}
+
+impl std::error::Error for ParamsError {}
+
+// A validated (n, t) pair: 1 <= t <= n <= u16::MAX. Every settings struct
+// and the command-line tools go through this instead of converting raw
+// integers in place.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Params {
+    system_size: u16,
+    threshold: u16,
+}
+
+impl Params {
+    pub fn new(system_size: usize, threshold: usize) -> Result<Params, ParamsError> {
+        if system_size == 0 {
+            return Err(ParamsError::EmptySystem);
+        }
+        if threshold == 0 {
+            return Err(ParamsError::ZeroThreshold);
+        }
+        if threshold > system_size {
+            return Err(ParamsError::ThresholdExceedsSystemSize {
+                system_size,
+                threshold,
+            });
+        }
+        let system_size = u16::try_from(system_size).map_err(|_| ParamsError::SystemSizeTooLarge(system_size))?;
+        Ok(Params {
+            system_size,
+            threshold: threshold as u16,
+        })
+    }
+
+    // The usual BFT sizing: n = 3f + 1, t = 2f + 1.
+    pub fn bft(max_faulty: usize) -> Result<Params, ParamsError> {
+        Params::new(3 * max_faulty + 1, 2 * max_faulty + 1)
+    }
+
+    pub fn system_size(&self) -> u16 {
+        self.system_size
+    }
+
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    pub fn check_frost(self) -> Result<Params, ParamsError> {
+        if self.threshold < 2 {
+            return Err(ParamsError::ThresholdTooSmallForFrost(self.threshold));
+        }
+        Ok(self)
+    }
+
+    // FROST identifier of the participant at zero-based `index`.
+    pub fn identifier(&self, index: usize) -> Result<Identifier, ParamsError> {
+        if index >= self.system_size as usize {
+            return Err(ParamsError::IndexOutOfRange(index));
+        }
+        // index + 1 is in 1..=u16::MAX here, so the conversion cannot fail.
+        Identifier::try_from(index as u16 + 1).map_err(|_| ParamsError::IndexOutOfRange(index))
+    }
+
+    pub fn signer_identifiers(&self) -> Vec<Identifier> {
+        (0..self.threshold as usize)
+            .filter_map(|index| self.identifier(index).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_sizes_are_rejected() {
+        assert_eq!(Params::new(5, 0), Err(ParamsError::ZeroThreshold));
+        assert_eq!(Params::new(0, 0), Err(ParamsError::EmptySystem));
+        assert_eq!(Params::bft(0).map(|params| (params.system_size(), params.threshold())), Ok((1, 1)));
+    }
+
+    #[test]
+    fn threshold_above_system_size_is_rejected() {
+        assert_eq!(
+            Params::new(3, 4),
+            Err(ParamsError::ThresholdExceedsSystemSize {
+                system_size: 3,
+                threshold: 4
+            })
+        );
+        assert!(Params::new(4, 4).is_ok());
+        let too_large = u16::MAX as usize + 1;
+        assert_eq!(Params::new(too_large, 2), Err(ParamsError::SystemSizeTooLarge(too_large)));
+    }
+
+    #[test]
+    fn single_member_committee_is_valid_but_not_for_frost() {
+        let single = Params::new(1, 1).unwrap();
+        assert_eq!((single.system_size(), single.threshold()), (1, 1));
+        assert_eq!(single.signer_identifiers(), [Identifier::try_from(1).unwrap()]);
+        assert_eq!(single.identifier(1), Err(ParamsError::IndexOutOfRange(1)));
+        assert_eq!(single.check_frost(), Err(ParamsError::ThresholdTooSmallForFrost(1)));
+    }
+}
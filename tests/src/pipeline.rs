@@ -0,0 +1,109 @@
+use frost_ed25519::{self as frost, Signature};
+use old_rand::{CryptoRng, RngCore};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::frost::{FrostPackage, FrostRound1, FrostSettings};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BatchReport {
+    pub signatures: usize,
+    pub elapsed: Duration,
+}
+
+impl BatchReport {
+    pub fn signatures_per_second(&self) -> f64 {
+        self.signatures as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+// Signs a queue of messages with one FROST session each. Round 1 does not
+// depend on the message, so the pipelined mode collects commitments for the
+// next sessions on a second thread while the current one is signed and
+// aggregated; `depth` bounds how many commitment sets are buffered ahead.
+pub struct BatchCoordinator<'a> {
+    settings: &'a FrostSettings,
+    packages: &'a FrostPackage,
+    depth: usize,
+}
+
+impl<'a> BatchCoordinator<'a> {
+    pub fn new(settings: &'a FrostSettings, packages: &'a FrostPackage) -> Self {
+        BatchCoordinator {
+            settings,
+            packages,
+            depth: 1,
+        }
+    }
+
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth.max(1);
+        self
+    }
+
+    fn finish(&self, round1: &FrostRound1, message: &[u8]) -> Result<Signature, frost::Error> {
+        let _session = tracing::info_span!("session", signers = round1.commitments().len()).entered();
+        let round2 = crate::frost::sign_message(self.settings, self.packages, round1, message)?;
+        frost::aggregate(round2.signing_package(), round2.signature_shares(), self.packages.public())
+    }
+
+    // One session after the other, for comparison.
+    pub fn sign_sequential<RNG>(
+        &self,
+        messages: &[Vec<u8>],
+        rng: &mut RNG,
+    ) -> Result<(Vec<Signature>, BatchReport), frost::Error>
+    where
+        RNG: RngCore + CryptoRng,
+    {
+        let start = Instant::now();
+        let signatures = messages
+            .iter()
+            .map(|message| {
+                let round1 = crate::frost::vote_commitments(self.settings, self.packages, rng)?;
+                self.finish(&round1, message)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let report = BatchReport {
+            signatures: signatures.len(),
+            elapsed: start.elapsed(),
+        };
+        Ok((signatures, report))
+    }
+
+    pub fn sign_pipelined<RNG>(
+        &self,
+        messages: &[Vec<u8>],
+        rng: &mut RNG,
+    ) -> Result<(Vec<Signature>, BatchReport), frost::Error>
+    where
+        RNG: RngCore + CryptoRng + Send,
+    {
+        let start = Instant::now();
+        let signatures = thread::scope(|scope| {
+            let (sender, receiver) = mpsc::sync_channel(self.depth);
+            scope.spawn(move || {
+                for _ in messages {
+                    let round1 = crate::frost::vote_commitments(self.settings, self.packages, rng);
+                    // The receiver only hangs up after an error on its side.
+                    if sender.send(round1).is_err() {
+                        break;
+                    }
+                }
+            });
+            messages
+                .iter()
+                .map(|message| {
+                    let round1 = receiver.recv().expect("commitment thread stopped early")?;
+                    self.finish(&round1, message)
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })?;
+        let report = BatchReport {
+            signatures: signatures.len(),
+            elapsed: start.elapsed(),
+        };
+        Ok((signatures, report))
+    }
+}
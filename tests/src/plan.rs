@@ -0,0 +1,59 @@
+use std::env;
+use std::io;
+use std::process;
+use thesis::planner::{self, Budget};
+
+// Calibration committees: f = 3, 10, 33 gives n = 10, 31, 100.
+const CALIBRATION_SIZES: [usize; 3] = [3, 10, 33];
+const CALIBRATION_RUNS: usize = 20;
+
+fn usage() -> ! {
+    eprintln!("Usage: plan <max-latency-ms> [min-sessions-per-second]");
+    process::exit(2);
+}
+
+fn main() {
+    let args: Vec<f64> = env::args()
+        .skip(1)
+        .map(|arg| arg.parse().unwrap_or_else(|_| usage()))
+        .collect();
+    let budget = match args.as_slice() {
+        [latency] => Budget {
+            max_latency_ms: *latency,
+            min_throughput: None,
+        },
+        [latency, throughput] => Budget {
+            max_latency_ms: *latency,
+            min_throughput: Some(*throughput),
+        },
+        _ => usage(),
+    };
+
+    let mut rng = old_rand::thread_rng();
+    let models = [
+        planner::calibrate_multisig(&CALIBRATION_SIZES, CALIBRATION_RUNS),
+        planner::calibrate_frost(&CALIBRATION_SIZES, CALIBRATION_RUNS, &mut rng),
+    ];
+
+    let mut recommendations = Vec::new();
+    for model in models {
+        match model {
+            Ok(Some(model)) => {
+                let [a, b, c] = model.coefficients;
+                println!("{}: latency_ms(t) = {:.4} + {:.6} t + {:.9} t^2", model.scheme, a, b, c);
+                recommendations.push(planner::recommend(&model, &budget));
+            }
+            Ok(None) => eprintln!("Calibration points are degenerate; cannot fit a model"),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    println!();
+    if let Err(e) = planner::write_recommendations_csv(&recommendations, io::stdout()) {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,188 @@
+use multisig::{Committee, KeypairShare};
+use old_rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::time::Instant;
+
+use crate::frost::FrostSettings;
+use crate::hash::HashFunction;
+use crate::params::{Params, ParamsError};
+
+// Session latency as a function of the threshold, fitted to calibration
+// runs: latency_ms(t) = a + b * t + c * t^2. The quadratic term covers the
+// per-signer O(t) work in FROST round 2 (binding factors over all
+// commitments); for multisig it comes out near zero.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CostModel {
+    pub scheme: String,
+    pub coefficients: [f64; 3],
+    // (t, measured latency in ms) the model was fitted to.
+    pub samples: Vec<(f64, f64)>,
+}
+
+impl CostModel {
+    pub fn fit(scheme: &str, samples: Vec<(f64, f64)>) -> Option<CostModel> {
+        if samples.len() < 3 {
+            return None;
+        }
+        // Normal equations for least squares over the basis (1, t, t^2).
+        let mut lhs = [[0.0f64; 3]; 3];
+        let mut rhs = [0.0f64; 3];
+        for &(t, latency) in &samples {
+            let basis = [1.0, t, t * t];
+            for (row, lhs_row) in lhs.iter_mut().enumerate() {
+                for (col, cell) in lhs_row.iter_mut().enumerate() {
+                    *cell += basis[row] * basis[col];
+                }
+                rhs[row] += basis[row] * latency;
+            }
+        }
+        let coefficients = solve3(lhs, rhs)?;
+        Some(CostModel {
+            scheme: scheme.to_string(),
+            coefficients,
+            samples,
+        })
+    }
+
+    pub fn predict_ms(&self, threshold: u16) -> f64 {
+        let t = threshold as f64;
+        let [a, b, c] = self.coefficients;
+        a + b * t + c * t * t
+    }
+}
+
+// Gaussian elimination with partial pivoting; None if the system is singular.
+fn solve3(mut lhs: [[f64; 3]; 3], mut rhs: [f64; 3]) -> Option<[f64; 3]> {
+    for col in 0..3 {
+        let pivot = (col..3).max_by(|&a, &b| lhs[a][col].abs().total_cmp(&lhs[b][col].abs()))?;
+        if lhs[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        lhs.swap(col, pivot);
+        rhs.swap(col, pivot);
+        for row in col + 1..3 {
+            let factor = lhs[row][col] / lhs[col][col];
+            let pivot_row = lhs[col];
+            for (k, entry) in lhs[row].iter_mut().enumerate().skip(col) {
+                *entry -= factor * pivot_row[k];
+            }
+            rhs[row] -= factor * rhs[col];
+        }
+    }
+    let mut x = [0.0; 3];
+    for row in (0..3).rev() {
+        let sum: f64 = (row + 1..3).map(|k| lhs[row][k] * x[k]).sum();
+        x[row] = (rhs[row] - sum) / lhs[row][row];
+    }
+    Some(x)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Budget {
+    pub max_latency_ms: f64,
+    // Sessions per second when run back to back on this machine.
+    pub min_throughput: Option<f64>,
+}
+
+impl Budget {
+    pub fn admits(&self, latency_ms: f64) -> bool {
+        latency_ms <= self.max_latency_ms
+            && self
+                .min_throughput
+                .is_none_or(|throughput| 1000.0 / latency_ms.max(f64::EPSILON) >= throughput)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Recommendation {
+    pub scheme: String,
+    // None if even the smallest committee misses the budget.
+    pub system_size: Option<u16>,
+    pub threshold: Option<u16>,
+    pub predicted_latency_ms: Option<f64>,
+}
+
+// Largest BFT-sized committee (n = 3f + 1, t = 2f + 1) whose predicted
+// session latency fits the budget. Committees are scanned up to u16::MAX
+// rather than bisected, since a fitted model need not be monotonic.
+pub fn recommend(model: &CostModel, budget: &Budget) -> Recommendation {
+    let mut best = None;
+    for max_faulty in 1..=(u16::MAX as usize - 1) / 3 {
+        let Ok(params) = Params::bft(max_faulty) else { break };
+        let latency = model.predict_ms(params.threshold());
+        if budget.admits(latency) {
+            best = Some((params, latency));
+        }
+    }
+    Recommendation {
+        scheme: model.scheme.clone(),
+        system_size: best.map(|(params, _)| params.system_size()),
+        threshold: best.map(|(params, _)| params.threshold()),
+        predicted_latency_ms: best.map(|(_, latency)| latency),
+    }
+}
+
+fn mean_ms(runs: usize, mut session: impl FnMut()) -> f64 {
+    let start = Instant::now();
+    for _ in 0..runs {
+        session();
+    }
+    start.elapsed().as_secs_f64() * 1e3 / runs.max(1) as f64
+}
+
+// Measures one full FROST signing session (both rounds, aggregation and
+// verification) for each BFT size in `max_faulty`.
+pub fn calibrate_frost<RNG>(max_faulty: &[usize], runs: usize, rng: &mut RNG) -> Result<Option<CostModel>, ParamsError>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let message = b"planner calibration";
+    let mut samples = Vec::with_capacity(max_faulty.len());
+    for &f in max_faulty {
+        let params = Params::bft(f)?;
+        let settings = FrostSettings::new(params, HashFunction::default())?;
+        let package = crate::frost::setup(&settings, rng).expect("calibration key generation");
+        let latency = mean_ms(runs, || {
+            let round1 = crate::frost::vote_commitments(&settings, &package, rng).expect("calibration round 1");
+            let round2 = crate::frost::sign_message(&settings, &package, &round1, message).expect("calibration round 2");
+            crate::frost::aggregate_verify(&settings, &package, &round1, &round2, message).expect("calibration verify");
+        });
+        samples.push((params.threshold() as f64, latency));
+    }
+    Ok(CostModel::fit("frost", samples))
+}
+
+// Same for a multisig certificate: t signatures plus committee verification.
+pub fn calibrate_multisig(max_faulty: &[usize], runs: usize) -> Result<Option<CostModel>, ParamsError> {
+    let message = b"planner calibration".to_vec();
+    let mut samples = Vec::with_capacity(max_faulty.len());
+    for &f in max_faulty {
+        let params = Params::bft(f)?;
+        let threshold = params.threshold() as usize;
+        let participants: Vec<KeypairShare> = (0..params.system_size()).map(|_| KeypairShare::default()).collect();
+        let mut committee = Committee::new();
+        for keypair in &participants {
+            committee.add_key(keypair.verifying_share.clone());
+        }
+        let latency = mean_ms(runs, || {
+            let certificate = participants
+                .iter()
+                .take(threshold)
+                .map(|keypair| keypair.sign(&message))
+                .collect::<Vec<_>>();
+            let _ = committee.verify(&message, &certificate, threshold);
+        });
+        samples.push((threshold as f64, latency));
+    }
+    Ok(CostModel::fit("multisig", samples))
+}
+
+pub fn write_recommendations_csv<W: io::Write>(rows: &[Recommendation], writer: W) -> Result<(), csv::Error> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,523 @@
+use frost_ed25519::{self as frost, Signature};
+use old_rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::frost::{FrostPackage, FrostSettings};
+use crate::hash::HashFunction;
+use crate::params::{Params, ParamsError};
+
+#[derive(Debug)]
+pub enum PolicyError {
+    Frost(frost::Error),
+    Params(ParamsError),
+    // An inner node lists fewer children than its threshold.
+    TooFewChildren { name: String, children: usize, threshold: u16 },
+    // The keys or certificate do not have the shape of the policy.
+    ShapeMismatch(String),
+    // Fewer than `threshold` valid child certificates.
+    Unsatisfied { name: String, valid: usize, threshold: u16 },
+    // A weighted node whose weights do not match its members or cannot add
+    // up to its threshold.
+    InvalidWeights(String),
+    // The valid child certificates of a weighted node carry too little weight.
+    InsufficientWeight { name: String, weight: u64, threshold: u64 },
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyError::Frost(e) => write!(f, "frost error: {e}"),
+            PolicyError::Params(e) => write!(f, "invalid parameters: {e}"),
+            PolicyError::TooFewChildren { name, children, threshold } => {
+                write!(f, "{name} needs {threshold} of its members but has only {children}")
+            }
+            PolicyError::ShapeMismatch(name) => write!(f, "{name} does not match the policy"),
+            PolicyError::Unsatisfied { name, valid, threshold } => {
+                write!(f, "{name} has {valid} valid members, {threshold} required")
+            }
+            PolicyError::InvalidWeights(name) => write!(f, "{name} has weights that do not fit its members"),
+            PolicyError::InsufficientWeight { name, weight, threshold } => {
+                write!(f, "{name} has valid members of weight {weight}, {threshold} required")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+impl From<frost::Error> for PolicyError {
+    fn from(e: frost::Error) -> Self {
+        PolicyError::Frost(e)
+    }
+}
+
+impl From<ParamsError> for PolicyError {
+    fn from(e: ParamsError) -> Self {
+        PolicyError::Params(e)
+    }
+}
+
+// A committee described as a tree. Leaves are FROST groups that produce one
+// ordinary signature under their own group key; inner nodes combine their
+// children the way multisig combines signers, i.e. a certificate is at least
+// `threshold` independently valid child certificates. "2-of-3 organizations,
+// each 3-of-5" is a threshold node of three 3-of-5 groups. Weighted nodes
+// model stake instead: each member carries a weight and the valid members'
+// weights must add up to `threshold`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum Policy {
+    Group {
+        name: String,
+        system_size: u16,
+        threshold: u16,
+    },
+    Threshold {
+        name: String,
+        threshold: u16,
+        members: Vec<Policy>,
+    },
+    Weighted {
+        name: String,
+        threshold: u64,
+        members: Vec<Policy>,
+        weights: Vec<u64>,
+    },
+}
+
+impl Policy {
+    pub fn group(name: &str, params: Params) -> Policy {
+        Policy::Group {
+            name: name.to_string(),
+            system_size: params.system_size(),
+            threshold: params.threshold(),
+        }
+    }
+
+    pub fn threshold(name: &str, threshold: u16, members: Vec<Policy>) -> Policy {
+        Policy::Threshold {
+            name: name.to_string(),
+            threshold,
+            members,
+        }
+    }
+
+    pub fn weighted(name: &str, threshold: u64, members: Vec<(u64, Policy)>) -> Policy {
+        let (weights, members) = members.into_iter().unzip();
+        Policy::Weighted {
+            name: name.to_string(),
+            threshold,
+            members,
+            weights,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Policy::Group { name, .. } | Policy::Threshold { name, .. } | Policy::Weighted { name, .. } => name,
+        }
+    }
+
+    // Total number of individual signers in the tree.
+    pub fn signers(&self) -> usize {
+        match self {
+            Policy::Group { system_size, .. } => *system_size as usize,
+            Policy::Threshold { members, .. } | Policy::Weighted { members, .. } => {
+                members.iter().map(Policy::signers).sum()
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), PolicyError> {
+        match self {
+            Policy::Group {
+                system_size, threshold, ..
+            } => {
+                Params::new(*system_size as usize, *threshold as usize)?.check_frost()?;
+                Ok(())
+            }
+            Policy::Threshold {
+                name,
+                threshold,
+                members,
+            } => {
+                if *threshold == 0 {
+                    return Err(ParamsError::ZeroThreshold.into());
+                }
+                if members.len() < *threshold as usize {
+                    return Err(PolicyError::TooFewChildren {
+                        name: name.clone(),
+                        children: members.len(),
+                        threshold: *threshold,
+                    });
+                }
+                members.iter().try_for_each(Policy::validate)
+            }
+            Policy::Weighted {
+                name,
+                threshold,
+                members,
+                weights,
+            } => {
+                if *threshold == 0 {
+                    return Err(ParamsError::ZeroThreshold.into());
+                }
+                let total = weights.iter().try_fold(0u64, |total, weight| total.checked_add(*weight));
+                if weights.len() != members.len() || total.is_none_or(|total| total < *threshold) {
+                    return Err(PolicyError::InvalidWeights(name.clone()));
+                }
+                members.iter().try_for_each(Policy::validate)
+            }
+        }
+    }
+
+    fn settings(system_size: u16, threshold: u16, hash: HashFunction) -> Result<FrostSettings, PolicyError> {
+        Ok(FrostSettings::new(
+            Params::new(system_size as usize, threshold as usize)?,
+            hash,
+        )?)
+    }
+}
+
+// Key material mirroring the policy tree; weighted nodes use `Threshold` too.
+#[derive(Debug)]
+pub enum PolicyKeys {
+    Group {
+        settings: FrostSettings,
+        package: Box<FrostPackage>,
+    },
+    Threshold(Vec<PolicyKeys>),
+}
+
+// A composed certificate. Inner nodes, weighted or not, keep the index of
+// each child that signed, so verification knows which subtree a certificate
+// belongs to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyCertificate {
+    Group(Signature),
+    Threshold(BTreeMap<usize, PolicyCertificate>),
+}
+
+// Dealer key generation for every leaf group.
+pub fn setup<RNG>(policy: &Policy, hash: HashFunction, rng: &mut RNG) -> Result<PolicyKeys, PolicyError>
+where
+    RNG: RngCore + CryptoRng,
+{
+    policy.validate()?;
+    setup_node(policy, hash, rng)
+}
+
+fn setup_node<RNG>(policy: &Policy, hash: HashFunction, rng: &mut RNG) -> Result<PolicyKeys, PolicyError>
+where
+    RNG: RngCore + CryptoRng,
+{
+    match policy {
+        Policy::Group {
+            system_size, threshold, ..
+        } => {
+            let settings = Policy::settings(*system_size, *threshold, hash)?;
+            let package = Box::new(crate::frost::setup(&settings, rng)?);
+            Ok(PolicyKeys::Group { settings, package })
+        }
+        Policy::Threshold { members, .. } | Policy::Weighted { members, .. } => Ok(PolicyKeys::Threshold(
+            members
+                .iter()
+                .map(|member| setup_node(member, hash, rng))
+                .collect::<Result<_, _>>()?,
+        )),
+    }
+}
+
+// Signs `message` with the first `threshold` members (or members of total
+// weight `threshold`) of every inner node whose index is not in
+// `unavailable`. Paths are child indices from the root,
+// e.g. `[1]` is the second organization and `[1, 0]` its first subgroup.
+pub fn sign<RNG>(
+    policy: &Policy,
+    keys: &PolicyKeys,
+    message: &[u8],
+    unavailable: &BTreeSet<Vec<usize>>,
+    rng: &mut RNG,
+) -> Result<PolicyCertificate, PolicyError>
+where
+    RNG: RngCore + CryptoRng,
+{
+    sign_node(policy, keys, message, unavailable, &mut Vec::new(), rng)
+}
+
+fn sign_node<RNG>(
+    policy: &Policy,
+    keys: &PolicyKeys,
+    message: &[u8],
+    unavailable: &BTreeSet<Vec<usize>>,
+    path: &mut Vec<usize>,
+    rng: &mut RNG,
+) -> Result<PolicyCertificate, PolicyError>
+where
+    RNG: RngCore + CryptoRng,
+{
+    match (policy, keys) {
+        (Policy::Group { .. }, PolicyKeys::Group { settings, package }) => {
+            let round1 = crate::frost::vote_commitments(settings, package, rng)?;
+            let round2 = crate::frost::sign_message(settings, package, &round1, message)?;
+            let signature = frost::aggregate(round2.signing_package(), round2.signature_shares(), package.public())?;
+            Ok(PolicyCertificate::Group(signature))
+        }
+        (
+            Policy::Threshold {
+                name,
+                threshold,
+                members,
+            },
+            PolicyKeys::Threshold(member_keys),
+        ) if members.len() == member_keys.len() => {
+            let mut signed = BTreeMap::new();
+            for (index, (member, member_keys)) in members.iter().zip(member_keys).enumerate() {
+                if signed.len() == *threshold as usize {
+                    break;
+                }
+                path.push(index);
+                if !unavailable.contains(path) {
+                    signed.insert(index, sign_node(member, member_keys, message, unavailable, path, rng)?);
+                }
+                path.pop();
+            }
+            if signed.len() < *threshold as usize {
+                return Err(PolicyError::Unsatisfied {
+                    name: name.clone(),
+                    valid: signed.len(),
+                    threshold: *threshold,
+                });
+            }
+            Ok(PolicyCertificate::Threshold(signed))
+        }
+        (
+            Policy::Weighted {
+                name,
+                threshold,
+                members,
+                weights,
+            },
+            PolicyKeys::Threshold(member_keys),
+        ) if members.len() == member_keys.len() && members.len() == weights.len() => {
+            let mut signed = BTreeMap::new();
+            let mut weight = 0u64;
+            for (index, ((member, member_keys), member_weight)) in members.iter().zip(member_keys).zip(weights).enumerate() {
+                if weight >= *threshold {
+                    break;
+                }
+                path.push(index);
+                if !unavailable.contains(path) {
+                    signed.insert(index, sign_node(member, member_keys, message, unavailable, path, rng)?);
+                    weight = weight.saturating_add(*member_weight);
+                }
+                path.pop();
+            }
+            if weight < *threshold {
+                return Err(PolicyError::InsufficientWeight {
+                    name: name.clone(),
+                    weight,
+                    threshold: *threshold,
+                });
+            }
+            Ok(PolicyCertificate::Threshold(signed))
+        }
+        _ => Err(PolicyError::ShapeMismatch(policy.name().to_string())),
+    }
+}
+
+// Checks a composed certificate bottom-up. Invalid or misplaced child
+// certificates are not fatal on their own; the node fails only if fewer than
+// `threshold` children (or children of that total weight) verify.
+pub fn verify(
+    policy: &Policy,
+    keys: &PolicyKeys,
+    message: &[u8],
+    certificate: &PolicyCertificate,
+) -> Result<(), PolicyError> {
+    match (policy, keys, certificate) {
+        (Policy::Group { .. }, PolicyKeys::Group { package, .. }, PolicyCertificate::Group(signature)) => {
+            Ok(package.public().verifying_key().verify(message, signature)?)
+        }
+        (
+            Policy::Threshold {
+                name,
+                threshold,
+                members,
+            },
+            PolicyKeys::Threshold(member_keys),
+            PolicyCertificate::Threshold(children),
+        ) => {
+            let valid = children
+                .iter()
+                .filter(|(index, child)| match (members.get(**index), member_keys.get(**index)) {
+                    (Some(member), Some(member_keys)) => verify(member, member_keys, message, child).is_ok(),
+                    _ => false,
+                })
+                .count();
+            if valid < *threshold as usize {
+                return Err(PolicyError::Unsatisfied {
+                    name: name.clone(),
+                    valid,
+                    threshold: *threshold,
+                });
+            }
+            Ok(())
+        }
+        (
+            Policy::Weighted {
+                name,
+                threshold,
+                members,
+                weights,
+            },
+            PolicyKeys::Threshold(member_keys),
+            PolicyCertificate::Threshold(children),
+        ) => {
+            let weight = children
+                .iter()
+                .filter_map(|(index, child)| {
+                    match (members.get(*index), member_keys.get(*index), weights.get(*index)) {
+                        (Some(member), Some(member_keys), Some(weight)) => {
+                            verify(member, member_keys, message, child).is_ok().then_some(*weight)
+                        }
+                        _ => None,
+                    }
+                })
+                .fold(0u64, u64::saturating_add);
+            if weight < *threshold {
+                return Err(PolicyError::InsufficientWeight {
+                    name: name.clone(),
+                    weight,
+                    threshold: *threshold,
+                });
+            }
+            Ok(())
+        }
+        _ => Err(PolicyError::ShapeMismatch(policy.name().to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    // 2-of-3 organizations, each a 3-of-5 FROST group.
+    fn consortium() -> (Policy, PolicyKeys) {
+        let organizations = (0..3)
+            .map(|index| Policy::group(&format!("org-{index}"), Params::new(5, 3).unwrap()))
+            .collect();
+        let tree = Policy::threshold("consortium", 2, organizations);
+        let keys = setup(&tree, HashFunction::default(), &mut testing::rng(1)).unwrap();
+        (tree, keys)
+    }
+
+    #[test]
+    fn any_two_organizations_satisfy_the_policy() {
+        let (tree, keys) = consortium();
+        let mut rng = testing::rng(2);
+        assert_eq!(tree.signers(), 15);
+        for offline in 0..3 {
+            let unavailable = BTreeSet::from([vec![offline]]);
+            let certificate = sign(&tree, &keys, b"message", &unavailable, &mut rng).unwrap();
+            let PolicyCertificate::Threshold(children) = &certificate else {
+                panic!("not a threshold certificate");
+            };
+            assert!(!children.contains_key(&offline));
+            verify(&tree, &keys, b"message", &certificate).unwrap();
+            assert!(verify(&tree, &keys, b"other", &certificate).is_err());
+        }
+    }
+
+    #[test]
+    fn one_organization_is_not_enough() {
+        let (tree, keys) = consortium();
+        let mut rng = testing::rng(3);
+        let unavailable = BTreeSet::from([vec![0], vec![1]]);
+        assert!(matches!(
+            sign(&tree, &keys, b"message", &unavailable, &mut rng),
+            Err(PolicyError::Unsatisfied { valid: 1, threshold: 2, .. })
+        ));
+
+        let mut certificate = sign(&tree, &keys, b"message", &BTreeSet::new(), &mut rng).unwrap();
+        if let PolicyCertificate::Threshold(children) = &mut certificate {
+            // Moving a child to another index does not make it count.
+            let (_, last) = children.pop_last().unwrap();
+            assert!(verify(&tree, &keys, b"message", &PolicyCertificate::Threshold(children.clone())).is_err());
+            children.insert(2, last);
+        }
+        assert!(matches!(
+            verify(&tree, &keys, b"message", &certificate),
+            Err(PolicyError::Unsatisfied { valid: 1, threshold: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn malformed_policies_are_rejected() {
+        let group = Policy::group("org", Params::new(5, 3).unwrap());
+        let too_few = Policy::threshold("consortium", 2, vec![group.clone()]);
+        assert!(matches!(too_few.validate(), Err(PolicyError::TooFewChildren { children: 1, threshold: 2, .. })));
+        let zero = Policy::threshold("consortium", 0, vec![group.clone()]);
+        assert!(matches!(zero.validate(), Err(PolicyError::Params(ParamsError::ZeroThreshold))));
+
+        let unreachable = Policy::weighted("stake", 5, vec![(2, group.clone()), (2, group.clone())]);
+        assert!(matches!(unreachable.validate(), Err(PolicyError::InvalidWeights(_))));
+        let overflowing = Policy::weighted("stake", 5, vec![(u64::MAX, group.clone()), (1, group.clone())]);
+        assert!(matches!(overflowing.validate(), Err(PolicyError::InvalidWeights(_))));
+        let mismatched = Policy::Weighted {
+            name: "stake".to_string(),
+            threshold: 1,
+            members: vec![group],
+            weights: vec![1, 1],
+        };
+        assert!(matches!(mismatched.validate(), Err(PolicyError::InvalidWeights(_))));
+    }
+
+    // Stake 5, 3 and 2 out of 10, each a 3-of-5 group; 6 is a majority.
+    fn validators() -> (Policy, PolicyKeys) {
+        let members = [5, 3, 2]
+            .into_iter()
+            .enumerate()
+            .map(|(index, stake)| (stake, Policy::group(&format!("validator-{index}"), Params::new(5, 3).unwrap())))
+            .collect();
+        let tree = Policy::weighted("stake", 6, members);
+        let keys = setup(&tree, HashFunction::default(), &mut testing::rng(4)).unwrap();
+        (tree, keys)
+    }
+
+    #[test]
+    fn weighted_nodes_count_stake_not_members() {
+        let (tree, keys) = validators();
+        let mut rng = testing::rng(5);
+
+        // Without the largest validator the other two hold only 5.
+        let unavailable = BTreeSet::from([vec![0]]);
+        assert!(matches!(
+            sign(&tree, &keys, b"message", &unavailable, &mut rng),
+            Err(PolicyError::InsufficientWeight { weight: 5, threshold: 6, .. })
+        ));
+        // With it, either of the others is enough.
+        for offline in 1..3 {
+            let unavailable = BTreeSet::from([vec![offline]]);
+            let certificate = sign(&tree, &keys, b"message", &unavailable, &mut rng).unwrap();
+            verify(&tree, &keys, b"message", &certificate).unwrap();
+            assert!(verify(&tree, &keys, b"other", &certificate).is_err());
+        }
+
+        // Two of three members sign, but not enough of the stake.
+        let mut certificate = sign(&tree, &keys, b"message", &BTreeSet::new(), &mut rng).unwrap();
+        if let PolicyCertificate::Threshold(children) = &mut certificate {
+            assert_eq!(children.keys().copied().collect::<Vec<_>>(), [0, 1]);
+            let largest = children.remove(&0).unwrap();
+            children.insert(2, largest);
+        }
+        assert!(matches!(
+            verify(&tree, &keys, b"message", &certificate),
+            Err(PolicyError::InsufficientWeight { weight: 3, threshold: 6, .. })
+        ));
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Fail(String),
+    // The check could not be performed here (e.g. no cpufreq in a VM).
+    Unknown(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.status {
+            CheckStatus::Pass => write!(f, "[PASS] {}", self.name),
+            CheckStatus::Fail(reason) => write!(f, "[FAIL] {}: {}", self.name, reason),
+            CheckStatus::Unknown(reason) => write!(f, "[ ?? ] {}: {}", self.name, reason),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreflightReport {
+    pub checks: Vec<Check>,
+}
+
+impl PreflightReport {
+    // Unknown checks count as failures: a measured run should only start in
+    // an environment known to be controlled.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.status == CheckStatus::Pass)
+    }
+}
+
+// A run refused before it starts because the machine cannot hold it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PreflightError {
+    InsufficientMemory { required: u64, available: u64 },
+}
+
+impl fmt::Display for PreflightError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreflightError::InsufficientMemory { required, available } => {
+                write!(f, "estimated {required} bytes exceed the {available} bytes available")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PreflightError {}
+
+// Free space every measured run asks for before it starts writing artifacts.
+pub const MIN_FREE_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+
+pub fn run_checks(artifact_dir: &Path, min_free_bytes: u64) -> PreflightReport {
+    PreflightReport {
+        checks: vec![
+            Check {
+                name: "cpu frequency governor pinned to performance",
+                status: check_governor(),
+            },
+            Check {
+                name: "turbo boost disabled",
+                status: check_turbo(),
+            },
+            Check {
+                name: "process pinned to isolated cores",
+                status: check_isolation(),
+            },
+            Check {
+                name: "free disk space for artifacts",
+                status: check_disk_space(artifact_dir, min_free_bytes),
+            },
+        ],
+    }
+}
+
+fn check_governor() -> CheckStatus {
+    let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") else {
+        return CheckStatus::Unknown("/sys/devices/system/cpu not available".to_string());
+    };
+    let mut governors = BTreeSet::new();
+    for entry in entries.flatten() {
+        let path = entry.path().join("cpufreq/scaling_governor");
+        if let Ok(governor) = fs::read_to_string(path) {
+            governors.insert(governor.trim().to_string());
+        }
+    }
+    match governors.len() {
+        0 => CheckStatus::Unknown("no cpufreq scaling_governor found".to_string()),
+        1 if governors.contains("performance") => CheckStatus::Pass,
+        _ => CheckStatus::Fail(format!("governors in use: {:?}", governors)),
+    }
+}
+
+fn check_turbo() -> CheckStatus {
+    if let Ok(no_turbo) = fs::read_to_string("/sys/devices/system/cpu/intel_pstate/no_turbo") {
+        return match no_turbo.trim() {
+            "1" => CheckStatus::Pass,
+            _ => CheckStatus::Fail("intel_pstate/no_turbo is 0".to_string()),
+        };
+    }
+    if let Ok(boost) = fs::read_to_string("/sys/devices/system/cpu/cpufreq/boost") {
+        return match boost.trim() {
+            "0" => CheckStatus::Pass,
+            _ => CheckStatus::Fail("cpufreq/boost is enabled".to_string()),
+        };
+    }
+    CheckStatus::Unknown("neither intel_pstate/no_turbo nor cpufreq/boost found".to_string())
+}
+
+fn check_isolation() -> CheckStatus {
+    let isolated = fs::read_to_string("/sys/devices/system/cpu/isolated")
+        .map(|list| parse_cpu_list(&list))
+        .unwrap_or_default();
+    if isolated.is_empty() {
+        return CheckStatus::Fail("no isolated cores (boot with isolcpus=...)".to_string());
+    }
+    let allowed = fs::read_to_string("/proc/self/status").ok().and_then(|status| {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))
+            .map(parse_cpu_list)
+    });
+    match allowed {
+        None => CheckStatus::Unknown("Cpus_allowed_list not available".to_string()),
+        Some(allowed) if allowed.is_subset(&isolated) => CheckStatus::Pass,
+        Some(allowed) => CheckStatus::Fail(format!(
+            "allowed cpus {:?} are not all isolated ({:?}); run under taskset",
+            allowed, isolated
+        )),
+    }
+}
+
+fn check_disk_space(artifact_dir: &Path, min_free_bytes: u64) -> CheckStatus {
+    let output = match Command::new("df").arg("-Pk").arg(artifact_dir).output() {
+        Ok(output) if output.status.success() => output,
+        _ => return CheckStatus::Unknown(format!("could not run df on {}", artifact_dir.display())),
+    };
+    // POSIX df output: header line, then "fs blocks used available capacity mount".
+    let available_kib = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .nth(1)
+        .and_then(|line| line.split_whitespace().nth(3).map(str::to_string))
+        .and_then(|field| field.parse::<u64>().ok());
+    match available_kib {
+        None => CheckStatus::Unknown("could not parse df output".to_string()),
+        Some(kib) if kib * 1024 >= min_free_bytes => CheckStatus::Pass,
+        Some(kib) => CheckStatus::Fail(format!(
+            "{} MiB free, {} MiB required",
+            kib / 1024,
+            min_free_bytes / (1024 * 1024)
+        )),
+    }
+}
+
+// Parses kernel cpu lists such as "0-3,8,10-11".
+pub fn parse_cpu_list(list: &str) -> BTreeSet<usize> {
+    let mut cpus = BTreeSet::new();
+    for part in list.trim().split(',').filter(|part| !part.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.trim().parse::<usize>(), end.trim().parse::<usize>()) {
+                    cpus.extend(start..=end);
+                }
+            }
+            None => {
+                if let Ok(cpu) = part.trim().parse() {
+                    cpus.insert(cpu);
+                }
+            }
+        }
+    }
+    cpus
+}
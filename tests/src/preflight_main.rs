@@ -0,0 +1,28 @@
+use std::env;
+use std::path::PathBuf;
+use std::process;
+use thesis::preflight;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let force = args.iter().any(|arg| arg == "--force");
+    let artifact_dir = args
+        .iter()
+        .find(|arg| !arg.starts_with("--"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let report = preflight::run_checks(&artifact_dir, preflight::MIN_FREE_BYTES);
+    for check in &report.checks {
+        println!("{}", check);
+    }
+
+    if report.passed() {
+        println!("Environment is ready for measured runs.");
+    } else if force {
+        println!("Preflight failed, continuing because of --force.");
+    } else {
+        eprintln!("Preflight failed; refusing measured runs (use --force to override).");
+        process::exit(1);
+    }
+}
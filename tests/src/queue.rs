@@ -0,0 +1,379 @@
+use frost_ed25519 as frost;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::artifacts::{ArtifactError, ArtifactKind, ArtifactManager};
+use crate::frost::FrostSettings;
+use crate::hash::HashFunction;
+use crate::limits;
+use crate::os_counters::{self, OsCounters};
+use crate::params::{Params, ParamsError};
+use crate::preflight::PreflightError;
+use crate::rng::{RngProvider, SeededRngProvider, ThreadRngProvider};
+
+#[derive(Debug)]
+pub enum QueueError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Frost(frost::Error),
+    Params(ParamsError),
+    Preflight(PreflightError),
+    Artifact(ArtifactError),
+    // The name is used as a file name under `results/`.
+    InvalidName(String),
+    // Two pending configs would write the same result file.
+    DuplicateName(String),
+}
+
+impl fmt::Display for QueueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueueError::Io(e) => write!(f, "io error: {e}"),
+            QueueError::Json(e) => write!(f, "json error: {e}"),
+            QueueError::Frost(e) => write!(f, "frost error: {e}"),
+            QueueError::Params(e) => write!(f, "invalid parameters: {e}"),
+            QueueError::Preflight(e) => write!(f, "{e}"),
+            QueueError::Artifact(e) => write!(f, "{e}"),
+            QueueError::InvalidName(name) => write!(f, "invalid experiment name {name:?}"),
+            QueueError::DuplicateName(name) => write!(f, "more than one pending experiment is named {name:?}"),
+        }
+    }
+}
+
+impl std::error::Error for QueueError {}
+
+impl From<io::Error> for QueueError {
+    fn from(e: io::Error) -> Self {
+        QueueError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for QueueError {
+    fn from(e: serde_json::Error) -> Self {
+        QueueError::Json(e)
+    }
+}
+
+impl From<ParamsError> for QueueError {
+    fn from(e: ParamsError) -> Self {
+        QueueError::Params(e)
+    }
+}
+
+impl From<PreflightError> for QueueError {
+    fn from(e: PreflightError) -> Self {
+        QueueError::Preflight(e)
+    }
+}
+
+impl From<ArtifactError> for QueueError {
+    fn from(e: ArtifactError) -> Self {
+        QueueError::Artifact(e)
+    }
+}
+
+impl From<frost::Error> for QueueError {
+    fn from(e: frost::Error) -> Self {
+        QueueError::Frost(e)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExperimentConfig {
+    pub name: String,
+    pub system_size: u16,
+    pub threshold: u16,
+    #[serde(default = "default_runs")]
+    pub runs: usize,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub hash: HashFunction,
+    #[serde(default)]
+    pub sample_os_counters: bool,
+}
+
+// Share of available memory a single experiment may plan to use.
+const MEMORY_FRACTION: f64 = 0.8;
+
+fn default_runs() -> usize {
+    10
+}
+
+fn check_name(name: &str) -> Result<(), QueueError> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+        return Err(QueueError::InvalidName(name.to_string()));
+    }
+    Ok(())
+}
+
+// Mean wall-clock time per phase, in microseconds.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentResult {
+    pub config: ExperimentConfig,
+    pub setup_us: f64,
+    pub commitments_us: f64,
+    pub signing_us: f64,
+    pub aggregate_verify_us: f64,
+    // Totals over all runs, only present when `sample_os_counters` is set.
+    #[serde(default)]
+    pub os_counters: Option<OsCounters>,
+}
+
+pub fn run_experiment(config: &ExperimentConfig) -> Result<ExperimentResult, QueueError> {
+    let run = || match config.seed {
+        Some(seed) => run_with_provider(config, &mut SeededRngProvider::new(seed)),
+        None => run_with_provider(config, &mut ThreadRngProvider),
+    };
+    if !config.sample_os_counters {
+        return run();
+    }
+    let (result, counters) = os_counters::sample_during(run);
+    let mut result = result?;
+    result.os_counters = counters;
+    Ok(result)
+}
+
+fn run_with_provider<P: RngProvider>(config: &ExperimentConfig, provider: &mut P) -> Result<ExperimentResult, QueueError> {
+    let params = Params::new(config.system_size as usize, config.threshold as usize)?;
+    let settings = FrostSettings::new(params, config.hash)?;
+    limits::ensure_fits(limits::frost_dealer_estimate(params), MEMORY_FRACTION)?;
+    let message = config.name.as_bytes();
+    let mut totals = [0u128; 4];
+
+    for _ in 0..config.runs {
+        let mut rng = provider.rng();
+
+        let start = Instant::now();
+        let package = crate::frost::setup(&settings, &mut rng)?;
+        totals[0] += start.elapsed().as_micros();
+
+        let start = Instant::now();
+        let round1 = crate::frost::vote_commitments(&settings, &package, &mut rng)?;
+        totals[1] += start.elapsed().as_micros();
+
+        let start = Instant::now();
+        let round2 = crate::frost::sign_message(&settings, &package, &round1, message)?;
+        totals[2] += start.elapsed().as_micros();
+
+        let start = Instant::now();
+        crate::frost::aggregate_verify(&settings, &package, &round1, &round2, message)?;
+        totals[3] += start.elapsed().as_micros();
+    }
+
+    let runs = config.runs.max(1) as f64;
+    Ok(ExperimentResult {
+        config: config.clone(),
+        setup_us: totals[0] as f64 / runs,
+        commitments_us: totals[1] as f64 / runs,
+        signing_us: totals[2] as f64 / runs,
+        aggregate_verify_us: totals[3] as f64 / runs,
+        os_counters: None,
+    })
+}
+
+// Written to `failed/<name>.json` for a config that cannot run, e.g. a
+// threshold above the committee size.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FailedExperiment {
+    pub config: ExperimentConfig,
+    pub error: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QueueSummary {
+    pub completed: usize,
+    pub failed: usize,
+}
+
+// A queue directory holds `pending/*.json` experiment configs and
+// `results/<name>.json` outputs. A job is done exactly when its result file,
+// or its `failed/<name>.json` record, exists, so rerunning after a crash
+// skips everything already done; delete the record to retry a failed job.
+// Results are registered in the queue directory's artifact manifest, so
+// `clean` can list and expire them.
+pub struct ExperimentQueue {
+    root: PathBuf,
+    artifacts: ArtifactManager,
+}
+
+impl ExperimentQueue {
+    pub fn open<P: AsRef<Path>>(root: P) -> Result<Self, QueueError> {
+        let root = root.as_ref().to_path_buf();
+        fs::create_dir_all(root.join("pending"))?;
+        fs::create_dir_all(root.join("results"))?;
+        fs::create_dir_all(root.join("failed"))?;
+        let artifacts = ArtifactManager::open(&root)?;
+        Ok(ExperimentQueue { root, artifacts })
+    }
+
+    pub fn pending(&self) -> Result<Vec<ExperimentConfig>, QueueError> {
+        let mut paths = fs::read_dir(self.root.join("pending"))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "json"));
+        // Deterministic order, so the queue runs the same way after a resume.
+        paths.sort();
+
+        let mut names = BTreeSet::new();
+        let mut configs = Vec::new();
+        for path in paths {
+            let config: ExperimentConfig = serde_json::from_slice(&fs::read(&path)?)?;
+            check_name(&config.name)?;
+            if !names.insert(config.name.clone()) {
+                return Err(QueueError::DuplicateName(config.name));
+            }
+            if !self.result_path(&config).exists() && !self.failure_path(&config).exists() {
+                configs.push(config);
+            }
+        }
+        Ok(configs)
+    }
+
+    pub fn result_path(&self, config: &ExperimentConfig) -> PathBuf {
+        self.root.join("results").join(format!("{}.json", config.name))
+    }
+
+    pub fn failure_path(&self, config: &ExperimentConfig) -> PathBuf {
+        self.root.join("failed").join(format!("{}.json", config.name))
+    }
+
+    pub fn write_result(&mut self, result: &ExperimentResult) -> Result<(), QueueError> {
+        check_name(&result.config.name)?;
+        let path = self.result_path(&result.config);
+        write_atomically(&path, &serde_json::to_vec_pretty(result)?)?;
+        self.artifacts.register(&path, ArtifactKind::Result)?;
+        Ok(())
+    }
+
+    pub fn write_failure(&self, failure: &FailedExperiment) -> Result<(), QueueError> {
+        check_name(&failure.config.name)?;
+        write_atomically(&self.failure_path(&failure.config), &serde_json::to_vec_pretty(failure)?)
+    }
+
+    // Runs every pending job in order. A job whose config is invalid or does
+    // not fit in memory is recorded as failed and the sweep moves on; any
+    // other error stops it.
+    // `on_start` is called with the job's position, the number of pending
+    // jobs and its config before each job starts.
+    pub fn run<F>(&mut self, mut on_start: F) -> Result<QueueSummary, QueueError>
+    where
+        F: FnMut(usize, usize, &ExperimentConfig),
+    {
+        let pending = self.pending()?;
+        let mut summary = QueueSummary::default();
+        for (index, config) in pending.iter().enumerate() {
+            on_start(index, pending.len(), config);
+            match run_experiment(config) {
+                Ok(result) => {
+                    self.write_result(&result)?;
+                    summary.completed += 1;
+                }
+                Err(e @ (QueueError::Params(_) | QueueError::Preflight(_))) => {
+                    tracing::warn!(experiment = %config.name, error = %e, "experiment failed");
+                    self.write_failure(&FailedExperiment {
+                        config: config.clone(),
+                        error: e.to_string(),
+                    })?;
+                    summary.failed += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(summary)
+    }
+}
+
+// Writes to a temporary file, syncs it, then renames over the final path,
+// so a crash never leaves a half-written file that looks complete.
+fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), QueueError> {
+    let tmp_path = path.with_extension("json.tmp");
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::{self, AssertUnwindSafe};
+
+    fn temp_queue(label: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("thesis-queue-{label}-{}", std::process::id()))
+    }
+
+    fn config(name: &str, system_size: u16, threshold: u16) -> ExperimentConfig {
+        ExperimentConfig {
+            name: name.to_string(),
+            system_size,
+            threshold,
+            runs: 1,
+            seed: Some(1),
+            hash: HashFunction::default(),
+            sample_os_counters: false,
+        }
+    }
+
+    fn enqueue(dir: &Path, file: &str, config: &ExperimentConfig) {
+        fs::write(dir.join("pending").join(file), serde_json::to_vec(config).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn rerun_skips_completed_and_failed_jobs() {
+        let dir = temp_queue("resume");
+        let mut queue = ExperimentQueue::open(&dir).unwrap();
+        enqueue(&dir, "1.json", &config("a", 3, 2));
+        enqueue(&dir, "2.json", &config("b", 3, 4));
+        enqueue(&dir, "3.json", &config("c", 4, 3));
+
+        // Interrupted while starting the third job.
+        let interrupted = panic::catch_unwind(AssertUnwindSafe(|| {
+            queue.run(|index, _, _| assert!(index < 2, "interrupted"))
+        }));
+        assert!(interrupted.is_err());
+        let first = fs::read(dir.join("results/a.json")).unwrap();
+        let failure: FailedExperiment = serde_json::from_slice(&fs::read(dir.join("failed/b.json")).unwrap()).unwrap();
+        assert_eq!(failure.config.name, "b");
+        assert!(!dir.join("results/c.json").exists());
+
+        let mut started = Vec::new();
+        let summary = queue.run(|_, total, config| started.push((config.name.clone(), total))).unwrap();
+        assert_eq!(started, [("c".to_string(), 1)]);
+        assert_eq!(summary, QueueSummary { completed: 1, failed: 0 });
+        assert_eq!(fs::read(dir.join("results/a.json")).unwrap(), first);
+        assert!(queue.pending().unwrap().is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn duplicate_names_are_rejected_on_load() {
+        let dir = temp_queue("duplicates");
+        let queue = ExperimentQueue::open(&dir).unwrap();
+        enqueue(&dir, "1.json", &config("same", 3, 2));
+        enqueue(&dir, "2.json", &config("same", 4, 3));
+        let pending = queue.pending();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(pending, Err(QueueError::DuplicateName(name)) if name == "same"));
+    }
+
+    #[test]
+    fn names_that_leave_the_results_directory_are_rejected() {
+        let dir = temp_queue("names");
+        let queue = ExperimentQueue::open(&dir).unwrap();
+        enqueue(&dir, "escaped.json", &config("../escaped", 7, 5));
+        let pending = queue.pending();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(pending, Err(QueueError::InvalidName(name)) if name == "../escaped"));
+        for name in ["", "a/b", "a\\b", "..", "ok_n7-t5"] {
+            assert_eq!(check_name(name).is_ok(), name == "ok_n7-t5", "{name:?}");
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use frost_ed25519::{self as frost, Identifier, Signature, keys::PublicKeyPackage};
+use old_rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Instant;
+
+use crate::Settings;
+use crate::attest::{self, AttestError};
+use crate::frost::{FrostPackage, FrostSettings};
+use crate::hash::{self, HashFunction};
+use crate::transcript::Transcript;
+
+#[derive(Debug)]
+pub enum ReceiptError {
+    Frost(frost::Error),
+    Attest(AttestError),
+    Encoding(bincode::Error),
+    UnexpectedCoordinator,
+    InputsMismatch,
+    // Participation receipts: a listed signer did not endorse it, or an
+    // endorsement came from outside the signer set.
+    MissingEndorsement(Identifier),
+    UnexpectedEndorsement(Identifier),
+    TooFewSigners { signers: usize, threshold: usize },
+}
+
+impl fmt::Display for ReceiptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReceiptError::Frost(e) => write!(f, "frost error: {e}"),
+            ReceiptError::Attest(e) => write!(f, "{e}"),
+            ReceiptError::Encoding(e) => write!(f, "encoding error: {e}"),
+            ReceiptError::UnexpectedCoordinator => write!(f, "receipt was signed by an unexpected coordinator"),
+            ReceiptError::InputsMismatch => write!(f, "receipt inputs digest does not match the message and committee"),
+            ReceiptError::MissingEndorsement(id) => write!(f, "signer {id:?} did not endorse the receipt"),
+            ReceiptError::UnexpectedEndorsement(id) => write!(f, "{id:?} endorsed the receipt without being a signer"),
+            ReceiptError::TooFewSigners { signers, threshold } => {
+                write!(f, "{signers} signers is below the threshold {threshold}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReceiptError {}
+
+impl From<frost::Error> for ReceiptError {
+    fn from(e: frost::Error) -> Self {
+        ReceiptError::Frost(e)
+    }
+}
+
+impl From<AttestError> for ReceiptError {
+    fn from(e: AttestError) -> Self {
+        ReceiptError::Attest(e)
+    }
+}
+
+impl From<bincode::Error> for ReceiptError {
+    fn from(e: bincode::Error) -> Self {
+        ReceiptError::Encoding(e)
+    }
+}
+
+// Wall-clock time per phase of the session, in microseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptTimings {
+    pub commitments_us: f64,
+    pub signing_us: f64,
+    pub aggregate_us: f64,
+    pub verify_us: f64,
+}
+
+// What the coordinator attests to after a session: which inputs were signed,
+// by whom, the result, and whether it verified against the group key.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SessionReceipt {
+    pub session: u64,
+    pub hash: HashFunction,
+    pub inputs_digest: Vec<u8>,
+    pub signers: Vec<Identifier>,
+    pub signature: Signature,
+    pub verified: bool,
+    pub timings: ReceiptTimings,
+}
+
+impl SessionReceipt {
+    // Binds the message to the committee it was signed by.
+    pub fn inputs_digest(hash: HashFunction, public: &PublicKeyPackage, message: &[u8]) -> Result<Vec<u8>, frost::Error> {
+        let mut transcript = Transcript::with_hash(hash, b"receipt-inputs");
+        transcript
+            .append_message(b"committee", &hash::committee_digest(hash, public)?)
+            .append_message(b"message", message);
+        Ok(transcript.signing_bytes())
+    }
+
+    fn signing_bytes(&self) -> Result<Vec<u8>, ReceiptError> {
+        let mut transcript = Transcript::with_hash(self.hash, b"session-receipt");
+        transcript.append_message(b"receipt", &bincode::serialize(self)?);
+        Ok(transcript.signing_bytes())
+    }
+
+    pub fn sign(self, coordinator: &SigningKey) -> Result<SignedReceipt, ReceiptError> {
+        let signature = coordinator.sign(&self.signing_bytes()?);
+        Ok(SignedReceipt {
+            receipt: self,
+            coordinator_key: hex::encode(coordinator.verifying_key().to_bytes()),
+            coordinator_signature: hex::encode(signature.to_bytes()),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedReceipt {
+    pub receipt: SessionReceipt,
+    pub coordinator_key: String,
+    pub coordinator_signature: String,
+}
+
+impl SignedReceipt {
+    pub fn coordinator_key(&self) -> Result<VerifyingKey, ReceiptError> {
+        Ok(attest::parse_verifying_key(&self.coordinator_key)?)
+    }
+
+    // Checks the coordinator signature and, if the committee and message are
+    // given, that the receipt is about them and its signature is valid.
+    pub fn verify(
+        &self,
+        expected_coordinator: Option<&VerifyingKey>,
+        session_inputs: Option<(&PublicKeyPackage, &[u8])>,
+    ) -> Result<(), ReceiptError> {
+        let key = self.coordinator_key()?;
+        if expected_coordinator.is_some_and(|expected| expected != &key) {
+            return Err(ReceiptError::UnexpectedCoordinator);
+        }
+        let signature_bytes: [u8; 64] = hex::decode(&self.coordinator_signature)
+            .map_err(AttestError::from)?
+            .try_into()
+            .map_err(|_| AttestError::MalformedSignature)?;
+        key.verify(
+            &self.receipt.signing_bytes()?,
+            &ed25519_dalek::Signature::from_bytes(&signature_bytes),
+        )
+        .map_err(AttestError::from)?;
+
+        if let Some((public, message)) = session_inputs {
+            if SessionReceipt::inputs_digest(self.receipt.hash, public, message)? != self.receipt.inputs_digest {
+                return Err(ReceiptError::InputsMismatch);
+            }
+            public.verifying_key().verify(message, &self.receipt.signature)?;
+        }
+        Ok(())
+    }
+}
+
+// Runs one signing session and returns the coordinator-signed receipt. A
+// signature that fails verification is still receipted, with `verified`
+// set to false, so failed sessions leave an audit trail too.
+pub fn sign_with_receipt<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    coordinator: &SigningKey,
+    session: u64,
+    message: &[u8],
+    rng: &mut RNG,
+) -> Result<SignedReceipt, ReceiptError>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let start = Instant::now();
+    let round1 = crate::frost::vote_commitments(settings, packages, rng)?;
+    let commitments = start.elapsed();
+
+    let start = Instant::now();
+    let round2 = crate::frost::sign_message(settings, packages, &round1, message)?;
+    let signing = start.elapsed();
+
+    let start = Instant::now();
+    let signature = frost::aggregate(round2.signing_package(), round2.signature_shares(), packages.public())?;
+    let aggregate = start.elapsed();
+
+    let start = Instant::now();
+    let verified = packages.public().verifying_key().verify(message, &signature).is_ok();
+    let verify = start.elapsed();
+
+    SessionReceipt {
+        session,
+        hash: settings.hash_function(),
+        inputs_digest: SessionReceipt::inputs_digest(settings.hash_function(), packages.public(), message)?,
+        signers: round2.signature_shares().keys().copied().collect(),
+        signature,
+        verified,
+        timings: ReceiptTimings {
+            commitments_us: commitments.as_secs_f64() * 1e6,
+            signing_us: signing.as_secs_f64() * 1e6,
+            aggregate_us: aggregate.as_secs_f64() * 1e6,
+            verify_us: verify.as_secs_f64() * 1e6,
+        },
+    }
+    .sign(coordinator)
+}
+
+// Attribution that does not rely on the coordinator: after aggregation every
+// contributing signer endorses the group signature and the signer set with a
+// Schnorr signature under its own share, one extra round of t signatures
+// like a multisig certificate. Auditors check the endorsements against the
+// verifying shares in the PublicKeyPackage, so no further keys are needed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ParticipationReceipt {
+    pub session: u64,
+    pub hash: HashFunction,
+    pub signature: Signature,
+    pub signers: Vec<Identifier>,
+    pub endorsements: BTreeMap<Identifier, Signature>,
+}
+
+impl ParticipationReceipt {
+    // Separate protocol label, so an endorsement can never pass as a
+    // signature over a message the committee signs.
+    fn endorsed_bytes(
+        hash: HashFunction,
+        session: u64,
+        signature: &Signature,
+        signers: &[Identifier],
+    ) -> Result<Vec<u8>, ReceiptError> {
+        let mut transcript = Transcript::with_hash(hash, b"participation-receipt");
+        transcript
+            .append_u64(b"session", session)
+            .append_message(b"signature", &signature.serialize()?);
+        for signer in signers {
+            transcript.append_message(b"signer", &signer.serialize());
+        }
+        Ok(transcript.signing_bytes())
+    }
+
+    // Collects the endorsement of every signer in `signers`. In practice
+    // each signer checks the group signature and the set before endorsing.
+    pub fn endorse<RNG>(
+        packages: &FrostPackage,
+        hash: HashFunction,
+        session: u64,
+        signature: Signature,
+        signers: Vec<Identifier>,
+        rng: &mut RNG,
+    ) -> Result<ParticipationReceipt, ReceiptError>
+    where
+        RNG: RngCore + CryptoRng,
+    {
+        let bytes = ParticipationReceipt::endorsed_bytes(hash, session, &signature, &signers)?;
+        let mut endorsements = BTreeMap::new();
+        for signer in &signers {
+            let key_package = packages.secret().get(signer).ok_or(frost::Error::UnknownIdentifier)?;
+            let key = frost::SigningKey::deserialize(&key_package.signing_share().serialize())?;
+            endorsements.insert(*signer, key.sign(&mut *rng, &bytes));
+        }
+        Ok(ParticipationReceipt {
+            session,
+            hash,
+            signature,
+            signers,
+            endorsements,
+        })
+    }
+
+    // Checks that at least `threshold` distinct committee members endorsed
+    // exactly this signer set and signature, and, if given, that the
+    // signature is valid for `message`.
+    pub fn verify(&self, public: &PublicKeyPackage, threshold: usize, message: Option<&[u8]>) -> Result<(), ReceiptError> {
+        let mut distinct = self.signers.clone();
+        distinct.sort();
+        distinct.dedup();
+        if distinct.len() != self.signers.len() || distinct.len() < threshold {
+            return Err(ReceiptError::TooFewSigners {
+                signers: distinct.len(),
+                threshold,
+            });
+        }
+        if let Some(extra) = self.endorsements.keys().find(|id| !self.signers.contains(id)) {
+            return Err(ReceiptError::UnexpectedEndorsement(*extra));
+        }
+
+        let bytes = ParticipationReceipt::endorsed_bytes(self.hash, self.session, &self.signature, &self.signers)?;
+        for signer in &self.signers {
+            let endorsement = self
+                .endorsements
+                .get(signer)
+                .ok_or(ReceiptError::MissingEndorsement(*signer))?;
+            let share = public
+                .verifying_shares()
+                .get(signer)
+                .ok_or(frost::Error::UnknownIdentifier)?;
+            frost::VerifyingKey::deserialize(&share.serialize()?)?.verify(&bytes, endorsement)?;
+        }
+        if let Some(message) = message {
+            public.verifying_key().verify(message, &self.signature)?;
+        }
+        Ok(())
+    }
+}
+
+// One signing session followed by the endorsement round.
+pub fn sign_with_participation<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    session: u64,
+    message: &[u8],
+    rng: &mut RNG,
+) -> Result<ParticipationReceipt, ReceiptError>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let round1 = crate::frost::vote_commitments(settings, packages, rng)?;
+    let round2 = crate::frost::sign_message(settings, packages, &round1, message)?;
+    let signature = frost::aggregate(round2.signing_package(), round2.signature_shares(), packages.public())?;
+    let signers = round2.signature_shares().keys().copied().collect();
+    ParticipationReceipt::endorse(packages, settings.hash_function(), session, signature, signers, rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn coordinator_receipt_binds_message_and_committee() {
+        let (settings, package) = testing::committee(1);
+        let mut rng = testing::rng(2);
+        let coordinator = SigningKey::from_bytes(&[1; 32]);
+        let receipt = sign_with_receipt(&settings, &package, &coordinator, 3, b"message", &mut rng).unwrap();
+        assert!(receipt.receipt.verified);
+        receipt
+            .verify(Some(&coordinator.verifying_key()), Some((package.public(), b"message")))
+            .unwrap();
+
+        let other = SigningKey::from_bytes(&[2; 32]);
+        assert!(matches!(
+            receipt.verify(Some(&other.verifying_key()), None),
+            Err(ReceiptError::UnexpectedCoordinator)
+        ));
+        assert!(matches!(
+            receipt.verify(None, Some((package.public(), b"other"))),
+            Err(ReceiptError::InputsMismatch)
+        ));
+        let mut altered = receipt.clone();
+        altered.receipt.session += 1;
+        assert!(matches!(altered.verify(None, None), Err(ReceiptError::Attest(_))));
+    }
+
+    #[test]
+    fn participation_receipt_names_the_signers() {
+        let (settings, package) = testing::committee(3);
+        let mut rng = testing::rng(4);
+        let receipt = sign_with_participation(&settings, &package, 0, b"message", &mut rng).unwrap();
+        receipt.verify(package.public(), 5, Some(b"message")).unwrap();
+        assert!(receipt.verify(package.public(), 5, Some(b"other")).is_err());
+
+        let outsider = *package.secret().keys().find(|id| !receipt.signers.contains(id)).unwrap();
+        let mut swapped = receipt.clone();
+        swapped.signers[0] = outsider;
+        assert!(swapped.verify(package.public(), 5, None).is_err());
+
+        let mut dropped = receipt.clone();
+        let (signer, _) = dropped.endorsements.pop_first().unwrap();
+        assert!(matches!(
+            dropped.verify(package.public(), 5, None),
+            Err(ReceiptError::MissingEndorsement(id)) if id == signer
+        ));
+
+        let mut duplicated = receipt.clone();
+        duplicated.signers[1] = duplicated.signers[0];
+        assert!(matches!(
+            duplicated.verify(package.public(), 5, None),
+            Err(ReceiptError::TooFewSigners { signers: 4, threshold: 5 })
+        ));
+    }
+}
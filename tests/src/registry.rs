@@ -0,0 +1,219 @@
+use frost_ed25519::{self as frost, Identifier};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum RegistryError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Frost(frost::Error),
+    DuplicateName(String),
+    // Two names derived the same identifier, or a file maps two names to one.
+    DuplicateIdentifier(Identifier),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::Io(e) => write!(f, "io error: {e}"),
+            RegistryError::Json(e) => write!(f, "json error: {e}"),
+            RegistryError::Frost(e) => write!(f, "frost error: {e}"),
+            RegistryError::DuplicateName(name) => write!(f, "participant {name} is registered twice"),
+            RegistryError::DuplicateIdentifier(id) => write!(f, "identifier {id:?} is registered twice"),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+impl From<io::Error> for RegistryError {
+    fn from(e: io::Error) -> Self {
+        RegistryError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for RegistryError {
+    fn from(e: serde_json::Error) -> Self {
+        RegistryError::Json(e)
+    }
+}
+
+impl From<frost::Error> for RegistryError {
+    fn from(e: frost::Error) -> Self {
+        RegistryError::Frost(e)
+    }
+}
+
+// Identifier of a participant known by an arbitrary label (host name, email,
+// ...). Anyone holding the label recomputes the same identifier.
+pub fn identifier_from_label(label: &str) -> Result<Identifier, frost::Error> {
+    Identifier::derive(label.as_bytes())
+}
+
+// Identifier bound to a participant's long-term public key, given in its
+// serialized form.
+pub fn identifier_from_public_key(public_key: &[u8]) -> Result<Identifier, frost::Error> {
+    Identifier::derive(public_key)
+}
+
+// Maps human-readable participant names to FROST identifiers and back, so
+// logs and reports can name the participants instead of printing scalars.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "BTreeMap<String, Identifier>", into = "BTreeMap<String, Identifier>")]
+pub struct ParticipantRegistry {
+    by_name: BTreeMap<String, Identifier>,
+    by_identifier: BTreeMap<Identifier, String>,
+}
+
+impl ParticipantRegistry {
+    pub fn new() -> Self {
+        ParticipantRegistry::default()
+    }
+
+    pub fn insert(&mut self, name: &str, identifier: Identifier) -> Result<(), RegistryError> {
+        if self.by_name.contains_key(name) {
+            return Err(RegistryError::DuplicateName(name.to_string()));
+        }
+        if self.by_identifier.contains_key(&identifier) {
+            return Err(RegistryError::DuplicateIdentifier(identifier));
+        }
+        self.by_name.insert(name.to_string(), identifier);
+        self.by_identifier.insert(identifier, name.to_string());
+        Ok(())
+    }
+
+    // Every name is its own label.
+    pub fn from_labels<'a, I>(names: I) -> Result<Self, RegistryError>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut registry = ParticipantRegistry::new();
+        for name in names {
+            registry.insert(name, identifier_from_label(name)?)?;
+        }
+        Ok(registry)
+    }
+
+    pub fn from_public_keys<'a, I>(participants: I) -> Result<Self, RegistryError>
+    where
+        I: IntoIterator<Item = (&'a str, &'a [u8])>,
+    {
+        let mut registry = ParticipantRegistry::new();
+        for (name, public_key) in participants {
+            registry.insert(name, identifier_from_public_key(public_key)?)?;
+        }
+        Ok(registry)
+    }
+
+    // Names for the default identifiers 1..=n, for keys generated without a
+    // registry.
+    pub fn numbered(system_size: u16) -> Result<Self, RegistryError> {
+        let mut registry = ParticipantRegistry::new();
+        for index in 1..=system_size {
+            registry.insert(&format!("participant-{index}"), Identifier::try_from(index)?)?;
+        }
+        Ok(registry)
+    }
+
+    pub fn identifier(&self, name: &str) -> Option<Identifier> {
+        self.by_name.get(name).copied()
+    }
+
+    pub fn name(&self, identifier: &Identifier) -> Option<&str> {
+        self.by_identifier.get(identifier).map(String::as_str)
+    }
+
+    // The registered name, or the hex identifier for unknown participants.
+    pub fn display(&self, identifier: &Identifier) -> String {
+        match self.name(identifier) {
+            Some(name) => name.to_string(),
+            None => hex::encode(identifier.serialize()),
+        }
+    }
+
+    // Identifiers in their canonical order, as `setup_with_identifiers` and
+    // the signing functions iterate them.
+    pub fn identifiers(&self) -> Vec<Identifier> {
+        self.by_identifier.keys().copied().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_name.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_name.is_empty()
+    }
+}
+
+impl TryFrom<BTreeMap<String, Identifier>> for ParticipantRegistry {
+    type Error = RegistryError;
+
+    fn try_from(by_name: BTreeMap<String, Identifier>) -> Result<Self, RegistryError> {
+        let mut registry = ParticipantRegistry::new();
+        for (name, identifier) in by_name {
+            registry.insert(&name, identifier)?;
+        }
+        Ok(registry)
+    }
+}
+
+impl From<ParticipantRegistry> for BTreeMap<String, Identifier> {
+    fn from(registry: ParticipantRegistry) -> Self {
+        registry.by_name
+    }
+}
+
+pub fn write_registry<P: AsRef<Path>>(path: P, registry: &ParticipantRegistry) -> Result<(), RegistryError> {
+    fs::write(path, serde_json::to_vec_pretty(registry)?)?;
+    Ok(())
+}
+
+pub fn read_registry<P: AsRef<Path>>(path: P) -> Result<ParticipantRegistry, RegistryError> {
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frost::{self as harness, FrostSettings};
+    use crate::hash::HashFunction;
+    use crate::params::Params;
+    use crate::rng::{RngProvider, SeededRngProvider};
+
+    #[test]
+    fn name_derived_identifiers_sign_with_the_default_path() {
+        let settings = FrostSettings::new(Params::new(7, 5).unwrap(), HashFunction::default()).unwrap();
+        let mut rng = SeededRngProvider::new(1).rng();
+        let names: Vec<String> = (0..settings.system_size()).map(|index| format!("node-{index}.thesis.test")).collect();
+        let registry = ParticipantRegistry::from_labels(names.iter().map(String::as_str)).unwrap();
+        let package = harness::setup_with_identifiers(&settings, &registry.identifiers(), &mut rng).unwrap();
+
+        let round1 = harness::vote_commitments(&settings, &package, &mut rng).unwrap();
+        let round2 = harness::sign_message(&settings, &package, &round1, b"message").unwrap();
+        let signature = frost::aggregate(round2.signing_package(), round2.signature_shares(), package.public()).unwrap();
+        package.public().verifying_key().verify(b"message", &signature).unwrap();
+        for identifier in round2.signature_shares().keys() {
+            assert!(registry.name(identifier).is_some());
+        }
+    }
+
+    #[test]
+    fn duplicate_names_are_rejected() {
+        assert!(matches!(
+            ParticipantRegistry::from_labels(["alice", "alice"]),
+            Err(RegistryError::DuplicateName(_))
+        ));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let registry = ParticipantRegistry::numbered(4).unwrap();
+        let json = serde_json::to_string(&registry).unwrap();
+        assert_eq!(serde_json::from_str::<ParticipantRegistry>(&json).unwrap(), registry);
+    }
+}
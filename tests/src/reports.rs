@@ -0,0 +1,272 @@
+use std::env;
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+use thesis::frost::{self, FrostSettings};
+use thesis::hash::HashFunction;
+use thesis::latency::LatencyMatrix;
+use thesis::metrics;
+use thesis::netsim::{self, NetworkModel, SignerFaults, SimConfig, SimProtocol};
+use thesis::paired::{self, PairedCommittee};
+use thesis::params::Params;
+use thesis::results;
+use thesis::rng::{RngProvider, SeededRngProvider, ThreadRngProvider};
+use thesis::transcript;
+use thesis::wire::Encoding;
+use thesis::workload::{self, WorkloadMode};
+
+// Measurements that do not fit Criterion's per-iteration timing: sustained
+// throughput, paired differences, counts and simulated runs. They use the
+// committee of the benchmarks and print one line per result. Without
+// arguments every report runs; otherwise only the named ones.
+const SYSTEM_SIZE: usize = 30;
+const THRESHOLD: usize = (2 * SYSTEM_SIZE + 1).div_ceil(3);
+
+const MESSAGE: &[u8] = b"HELLO WORLD";
+
+const REPORTS: &[(&str, fn())] = &[
+    ("mixed_workload", mixed_workload),
+    ("paired", paired_timings),
+    ("netsim", network_simulation),
+    ("metrics", session_metrics),
+    ("roast_faults", roast_faults),
+    ("fallback", fallback),
+    ("sizes", sizes),
+];
+
+fn frost_settings() -> FrostSettings {
+    let params = Params::new(SYSTEM_SIZE, THRESHOLD).expect("report parameters are valid");
+    FrostSettings::new(params, HashFunction::default()).expect("report parameters are valid for FROST")
+}
+
+fn signed_message() -> Vec<u8> {
+    transcript::payload_transcript(HashFunction::default(), MESSAGE)
+}
+
+// Sustained throughput of signing and verification, alone and running
+// concurrently on the same machine.
+fn mixed_workload() {
+    let settings = frost_settings();
+    let mut rng = ThreadRngProvider.rng();
+    let package = frost::setup(&settings, &mut rng).unwrap();
+    let message = signed_message();
+    let signatures = workload::produce_signatures(&settings, &package, &message, 100, &mut rng).unwrap();
+
+    for mode in [WorkloadMode::SignOnly, WorkloadMode::VerifyOnly, WorkloadMode::Mixed] {
+        let stats =
+            workload::run_workload(mode, &settings, &package, &message, &signatures, Duration::from_secs(5), &mut rng)
+                .unwrap();
+        println!(
+            "Workload {:?}: {:.1} signatures/s, {:.1} verifications/s",
+            mode,
+            stats.signatures_per_second(),
+            stats.verifications_per_second()
+        );
+    }
+}
+
+// Signs every message under both schemes with the same participants and
+// reports the mean per-message difference.
+fn paired_timings() {
+    let settings = frost_settings();
+    let mut rng = ThreadRngProvider.rng();
+    let committee = PairedCommittee::new(&settings, &mut rng).unwrap();
+    let messages: Vec<Vec<u8>> = (0..200u32)
+        .map(|i| transcript::payload_transcript(HashFunction::default(), &i.to_le_bytes()))
+        .collect();
+    let samples = paired::run_paired(&settings, &committee, &messages, &mut rng).unwrap();
+    let (mean, std_dev) = paired::paired_difference(&samples);
+    println!(
+        "Paired ({} messages): FROST - multisig = {:.1} us (sd {:.1} us) per message",
+        samples.len(),
+        mean,
+        std_dev
+    );
+}
+
+// Simulated completion time and message counts of FROST, ROAST and multisig
+// over the same lossy network.
+fn network_simulation() {
+    let mut rng = ThreadRngProvider.rng();
+    let config = SimConfig {
+        coordinator: 0,
+        threshold: THRESHOLD,
+        retransmit_ms: 500.0,
+        deadline_ms: 60_000.0,
+    };
+    for drop_probability in [0.0, 0.01, 0.1] {
+        let network = NetworkModel {
+            latencies: LatencyMatrix::synthetic(SYSTEM_SIZE, 150.0, &mut rng),
+            jitter_ms: 20.0,
+            drop_probability,
+            processing_ms: 1.0,
+        };
+        for protocol in SimProtocol::ALL {
+            let reports: Vec<_> = (0..100).map(|_| netsim::simulate(protocol, &network, &config, &mut rng)).collect();
+            let completed: Vec<f64> = reports.iter().filter_map(|report| report.completion_ms).collect();
+            let messages: u64 = reports.iter().map(|report| report.messages_sent).sum();
+            println!(
+                "Netsim drop {:.0}% {}: {}/{} completed, mean {:.1} ms, {:.1} messages per run",
+                drop_probability * 100.0,
+                protocol.name(),
+                completed.len(),
+                reports.len(),
+                completed.iter().sum::<f64>() / completed.len().max(1) as f64,
+                messages as f64 / reports.len() as f64
+            );
+        }
+    }
+}
+
+// Messages, bytes and rounds of one execution of each scheme, counted as the
+// messages are produced.
+fn session_metrics() {
+    let settings = frost_settings();
+    let mut rng = ThreadRngProvider.rng();
+    let message = signed_message();
+    let package = frost::setup(&settings, &mut rng).unwrap();
+    let mut runs = vec![("canonical", metrics::multisig_session(THRESHOLD))];
+    for encoding in Encoding::ALL {
+        let (_, frost_metrics) = metrics::frost_session(&settings, &package, &message, encoding, &mut rng).unwrap();
+        runs.push((encoding.name(), frost_metrics));
+    }
+    for (encoding, run) in runs {
+        println!(
+            "Metrics {} ({}): {} messages, {} bytes, {} rounds for t = {}",
+            run.scheme, encoding, run.messages, run.bytes, run.rounds, run.signers
+        );
+    }
+}
+
+// ROAST at 5-of-7 with up to n - t faulty signers, some silent after round 1
+// and some sending invalid shares, on the synthetic network of the ROAST
+// benchmarks. The cost shows up as extra sessions, rounds and latency.
+fn roast_faults() {
+    let mut rng = ThreadRngProvider.rng();
+    let (system_size, threshold) = (7, 5);
+    let config = SimConfig {
+        coordinator: 0,
+        threshold,
+        retransmit_ms: 500.0,
+        deadline_ms: 60_000.0,
+    };
+    let network = NetworkModel {
+        latencies: LatencyMatrix::synthetic(system_size, 150.0, &mut SeededRngProvider::new(7).rng()),
+        jitter_ms: 20.0,
+        drop_probability: 0.0,
+        processing_ms: 1.0,
+    };
+    for faulty in 0..=system_size - threshold {
+        for silent in 0..=faulty {
+            let faults = SignerFaults::highest(system_size, config.coordinator, silent, faulty - silent);
+            let reports: Vec<_> = (0..100)
+                .map(|_| netsim::simulate_with_faults(SimProtocol::Roast, &network, &config, &faults, &mut rng))
+                .collect();
+            let completed: Vec<f64> = reports.iter().filter_map(|report| report.completion_ms).collect();
+            let sessions: usize = reports.iter().map(|report| report.sessions).sum();
+            let rounds: usize = reports.iter().map(|report| report.rounds).sum();
+            println!(
+                "ROAST {}-of-{} with {} silent, {} invalid: {}/{} completed, mean {:.1} ms, {:.2} sessions, {:.2} rounds per run",
+                threshold,
+                system_size,
+                faults.silent.len(),
+                faults.invalid.len(),
+                completed.len(),
+                reports.len(),
+                completed.iter().sum::<f64>() / completed.len().max(1) as f64,
+                sessions as f64 / reports.len() as f64,
+                rounds as f64 / reports.len() as f64
+            );
+        }
+    }
+}
+
+// How often ROAST at 5-of-7 misses a 1 s deadline and falls back to a
+// multisig certificate, with message loss and up to n - t slow signers
+// answering 1 s late, on the synthetic network of the ROAST benchmarks.
+fn fallback() {
+    let mut rng = ThreadRngProvider.rng();
+    let (system_size, threshold) = (7, 5);
+    let config = SimConfig {
+        coordinator: 0,
+        threshold,
+        retransmit_ms: 500.0,
+        deadline_ms: 60_000.0,
+    };
+    let latencies = LatencyMatrix::synthetic(system_size, 150.0, &mut SeededRngProvider::new(7).rng());
+    for drop_probability in [0.0, 0.1, 0.3] {
+        let network = NetworkModel {
+            latencies: latencies.clone(),
+            jitter_ms: 20.0,
+            drop_probability,
+            processing_ms: 1.0,
+        };
+        for slow in 0..=system_size - threshold {
+            let faults = SignerFaults::slow(system_size, config.coordinator, slow, 1000.0);
+            let reports: Vec<_> = (0..100)
+                .map(|_| netsim::simulate_fallback(&network, &config, 1000.0, &faults, &mut rng))
+                .collect();
+            let fell_back = reports.iter().filter(|report| report.fell_back()).count();
+            let completed: Vec<f64> = reports.iter().filter_map(|report| report.completion_ms).collect();
+            println!(
+                "Fallback drop {:.0}%, {} slow: {}/{} fell back to multisig, {}/{} completed, mean {:.1} ms",
+                drop_probability * 100.0,
+                slow,
+                fell_back,
+                reports.len(),
+                completed.len(),
+                reports.len(),
+                completed.iter().sum::<f64>() / completed.len().max(1) as f64
+            );
+        }
+    }
+}
+
+// Serialized sizes of every protocol object per scheme and (n, t), and the
+// randomness each FROST phase draws, exported next to Criterion's output for
+// the thesis tables and `export_results`.
+fn sizes() {
+    let mut rng = ThreadRngProvider.rng();
+    let message = signed_message();
+    let mut records = Vec::new();
+    let mut entropy = Vec::new();
+    for max_faulty in [3, 10, 33] {
+        let params = Params::bft(max_faulty).unwrap();
+        let settings = FrostSettings::new(params, HashFunction::default()).unwrap();
+        let package = frost::setup(&settings, &mut rng).unwrap();
+        let round1 = frost::vote_commitments(&settings, &package, &mut rng).unwrap();
+        let round2 = frost::sign_message(&settings, &package, &round1, &message).unwrap();
+        let signature =
+            frost_ed25519::aggregate(round2.signing_package(), round2.signature_shares(), package.public()).unwrap();
+        records.extend(results::frost_sizes(&settings, &round1, &round2, &signature).unwrap());
+        records.extend(results::frost_key_sizes(&settings, package.public()).unwrap());
+        records.extend(results::multisig_sizes(params.system_size(), params.threshold()));
+        #[cfg(feature = "bls")]
+        records.extend(results::bls_sizes(params.system_size(), params.threshold()));
+        entropy.extend(results::frost_entropy(&settings, &message, ThreadRngProvider.rng()).unwrap());
+    }
+    results::export_sizes(Path::new("target/criterion"), &records).unwrap();
+    println!("Sizes: wrote {} records to target/criterion/sizes.{{csv,json,md}}", records.len());
+    print!("{}", results::size_table(&records));
+    for record in entropy.iter().filter(|r| r.expected_bytes.is_some_and(|expected| expected != r.bytes)) {
+        println!(
+            "Entropy: {} {} at n = {} drew {} bytes, expected {:?}",
+            record.scheme, record.phase, record.system_size, record.bytes, record.expected_bytes
+        );
+    }
+    results::export_entropy(Path::new("target/criterion"), &entropy).unwrap();
+}
+
+fn main() {
+    let names: Vec<String> = env::args().skip(1).collect();
+    if let Some(unknown) = names.iter().find(|name| !REPORTS.iter().any(|(report, _)| report == name)) {
+        let available: Vec<&str> = REPORTS.iter().map(|(report, _)| *report).collect();
+        eprintln!("Unknown report {unknown}; available: {}", available.join(", "));
+        process::exit(2);
+    }
+    for (name, report) in REPORTS {
+        if names.is_empty() || names.iter().any(|selected| selected == name) {
+            report();
+        }
+    }
+}
@@ -0,0 +1,515 @@
+use frost_ed25519::keys::PublicKeyPackage;
+use frost_ed25519::{self as frost, Signature};
+use old_rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use crate::frost::{FrostRound1, FrostRound2, FrostSettings};
+use crate::rng::CountingRng;
+
+#[derive(Debug)]
+pub enum ResultsError {
+    Io(io::Error),
+    Csv(csv::Error),
+    Json(serde_json::Error),
+    Encoding(bincode::Error),
+    Frost(frost::Error),
+}
+
+impl fmt::Display for ResultsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResultsError::Io(e) => write!(f, "io error: {e}"),
+            ResultsError::Csv(e) => write!(f, "csv error: {e}"),
+            ResultsError::Json(e) => write!(f, "json error: {e}"),
+            ResultsError::Encoding(e) => write!(f, "encoding error: {e}"),
+            ResultsError::Frost(e) => write!(f, "frost error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ResultsError {}
+
+impl From<io::Error> for ResultsError {
+    fn from(e: io::Error) -> Self {
+        ResultsError::Io(e)
+    }
+}
+
+impl From<csv::Error> for ResultsError {
+    fn from(e: csv::Error) -> Self {
+        ResultsError::Csv(e)
+    }
+}
+
+impl From<serde_json::Error> for ResultsError {
+    fn from(e: serde_json::Error) -> Self {
+        ResultsError::Json(e)
+    }
+}
+
+impl From<bincode::Error> for ResultsError {
+    fn from(e: bincode::Error) -> Self {
+        ResultsError::Encoding(e)
+    }
+}
+
+impl From<frost::Error> for ResultsError {
+    fn from(e: frost::Error) -> Self {
+        ResultsError::Frost(e)
+    }
+}
+
+// Serialized size of one protocol object. `encoding` is "canonical" for the
+// scheme's own byte encoding (frost-core's `serialize`, raw ed25519 bytes for
+// multisig), or "bincode"/"json" for the serde encodings used by thesis::wire.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SizeRecord {
+    pub scheme: String,
+    pub system_size: u16,
+    pub threshold: u16,
+    pub object: String,
+    pub encoding: String,
+    pub bytes: usize,
+}
+
+fn encoded_sizes<T: Serialize>(canonical: usize, value: &T) -> Result<[(&'static str, usize); 3], ResultsError> {
+    Ok([
+        ("canonical", canonical),
+        ("bincode", bincode::serialize(value)?.len()),
+        ("json", serde_json::to_vec(value)?.len()),
+    ])
+}
+
+// Sizes of one FROST session's objects. Commitments and shares are per
+// signer; the signing package and signature are per session.
+pub fn frost_sizes(
+    settings: &FrostSettings,
+    round1: &FrostRound1,
+    round2: &FrostRound2,
+    signature: &Signature,
+) -> Result<Vec<SizeRecord>, ResultsError> {
+    let mut objects = Vec::new();
+    if let Some(commitments) = round1.commitments().values().next() {
+        objects.push(("commitment", encoded_sizes(commitments.serialize()?.len(), commitments)?));
+    }
+    if let Some(share) = round2.signature_shares().values().next() {
+        objects.push(("signature_share", encoded_sizes(share.serialize().len(), share)?));
+    }
+    let package = round2.signing_package();
+    objects.push(("signing_package", encoded_sizes(package.serialize()?.len(), package)?));
+    objects.push(("final_signature", encoded_sizes(signature.serialize()?.len(), signature)?));
+
+    Ok(objects
+        .into_iter()
+        .flat_map(|(object, sizes)| {
+            sizes.into_iter().map(move |(encoding, bytes)| SizeRecord {
+                scheme: "frost".to_string(),
+                system_size: settings.system_size(),
+                threshold: settings.threshold(),
+                object: object.to_string(),
+                encoding: encoding.to_string(),
+                bytes,
+            })
+        })
+        .collect())
+}
+
+// Canonical sizes of the key material a verifier needs: the group key alone,
+// and the full public key package with every verifying share.
+pub fn frost_key_sizes(settings: &FrostSettings, public: &PublicKeyPackage) -> Result<Vec<SizeRecord>, ResultsError> {
+    let record = |object: &str, bytes| SizeRecord {
+        scheme: "frost".to_string(),
+        system_size: settings.system_size(),
+        threshold: settings.threshold(),
+        object: object.to_string(),
+        encoding: "canonical".to_string(),
+        bytes,
+    };
+    Ok(vec![
+        record("group_key", public.verifying_key().serialize()?.len()),
+        record("public_key_package", public.serialize()?.len()),
+    ])
+}
+
+// A multisig certificate is t ed25519 signatures; its canonical size is the
+// raw signature bytes, without any signer indices.
+pub fn multisig_sizes(system_size: u16, threshold: u16) -> Vec<SizeRecord> {
+    let record = |object: &str, bytes| SizeRecord {
+        scheme: "multisig".to_string(),
+        system_size,
+        threshold,
+        object: object.to_string(),
+        encoding: "canonical".to_string(),
+        bytes,
+    };
+    vec![
+        record("signature_share", ed25519_dalek::SIGNATURE_LENGTH),
+        record("certificate", threshold as usize * ed25519_dalek::SIGNATURE_LENGTH),
+        // The verifier holds all n keys.
+        record("group_key", system_size as usize * ed25519_dalek::PUBLIC_KEY_LENGTH),
+    ]
+}
+
+// Compressed BLS12-381 points: partial and group signatures in G1, the group
+// key in G2.
+#[cfg(feature = "bls")]
+pub fn bls_sizes(system_size: u16, threshold: u16) -> Vec<SizeRecord> {
+    let record = |object: &str, bytes| SizeRecord {
+        scheme: "bls".to_string(),
+        system_size,
+        threshold,
+        object: object.to_string(),
+        encoding: "canonical".to_string(),
+        bytes,
+    };
+    let g1 = bls12_381::G1Affine::generator().to_compressed().len();
+    let g2 = bls12_381::G2Affine::generator().to_compressed().len();
+    vec![
+        record("signature_share", g1),
+        record("final_signature", g1),
+        record("group_key", g2),
+    ]
+}
+
+// Markdown table of the canonical sizes: one row per scheme and (n, t), one
+// column per object. Objects a scheme does not have are left empty.
+pub fn size_table(records: &[SizeRecord]) -> String {
+    let canonical: Vec<&SizeRecord> = records.iter().filter(|r| r.encoding == "canonical").collect();
+    let mut objects: Vec<&str> = Vec::new();
+    let mut rows: Vec<(&str, u16, u16)> = Vec::new();
+    for record in &canonical {
+        if !objects.contains(&record.object.as_str()) {
+            objects.push(&record.object);
+        }
+        let row = (record.scheme.as_str(), record.system_size, record.threshold);
+        if !rows.contains(&row) {
+            rows.push(row);
+        }
+    }
+
+    let mut table = format!("| scheme | n | t | {} |\n", objects.join(" | "));
+    table.push_str(&format!("|---|---:|---:|{}\n", "---:|".repeat(objects.len())));
+    for (scheme, system_size, threshold) in rows {
+        let cells: Vec<String> = objects
+            .iter()
+            .map(|object| {
+                canonical
+                    .iter()
+                    .find(|r| {
+                        r.scheme == scheme
+                            && r.system_size == system_size
+                            && r.threshold == threshold
+                            && r.object == *object
+                    })
+                    .map_or(String::new(), |r| r.bytes.to_string())
+            })
+            .collect();
+        table.push_str(&format!("| {scheme} | {system_size} | {threshold} | {} |\n", cells.join(" | ")));
+    }
+    table
+}
+
+// Bytes drawn from the RNG in one protocol phase. Only FROST is covered:
+// multisig key generation uses its own internal RNG, which cannot be wrapped.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntropyRecord {
+    pub scheme: String,
+    pub system_size: u16,
+    pub threshold: u16,
+    pub phase: String,
+    pub bytes: u64,
+    // What the protocol needs for this phase, where it is fixed.
+    pub expected_bytes: Option<u64>,
+}
+
+// Every FROST round 1 nonce is derived from 32 fresh random bytes, and each
+// signer draws a hiding and a binding nonce.
+const FROST_NONCE_RANDOM_BYTES: u64 = 32;
+
+pub fn frost_entropy<R>(settings: &FrostSettings, message: &[u8], rng: R) -> Result<Vec<EntropyRecord>, ResultsError>
+where
+    R: RngCore + CryptoRng,
+{
+    let mut rng = CountingRng::new(rng);
+    let package = crate::frost::setup(settings, &mut rng)?;
+    let keygen = rng.take_bytes();
+    let round1 = crate::frost::vote_commitments(settings, &package, &mut rng)?;
+    let commit = rng.take_bytes();
+    crate::frost::sign_message(settings, &package, &round1, message)?;
+    let sign = rng.take_bytes();
+
+    let record = |phase: &str, bytes, expected_bytes| EntropyRecord {
+        scheme: "frost".to_string(),
+        system_size: settings.system_size(),
+        threshold: settings.threshold(),
+        phase: phase.to_string(),
+        bytes,
+        expected_bytes,
+    };
+    Ok(vec![
+        record("keygen", keygen, None),
+        record("commit", commit, Some(settings.threshold() as u64 * 2 * FROST_NONCE_RANDOM_BYTES)),
+        record("sign", sign, Some(0)),
+    ])
+}
+
+// Writes `sizes.csv`, `sizes.json` and the canonical `sizes.md` table into
+// `dir`, e.g. next to Criterion's own output in target/criterion.
+pub fn export_sizes<P: AsRef<Path>>(dir: P, records: &[SizeRecord]) -> Result<(), ResultsError> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let mut writer = csv::Writer::from_path(dir.join("sizes.csv"))?;
+    for record in records {
+        writer.serialize(record)?;
+    }
+    writer.flush()?;
+    serde_json::to_writer_pretty(BufWriter::new(File::create(dir.join("sizes.json"))?), records)?;
+    fs::write(dir.join("sizes.md"), size_table(records))?;
+    Ok(())
+}
+
+// Same for `entropy.csv` and `entropy.json`.
+pub fn export_entropy<P: AsRef<Path>>(dir: P, records: &[EntropyRecord]) -> Result<(), ResultsError> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let mut writer = csv::Writer::from_path(dir.join("entropy.csv"))?;
+    for record in records {
+        writer.serialize(record)?;
+    }
+    writer.flush()?;
+    serde_json::to_writer_pretty(BufWriter::new(File::create(dir.join("entropy.json"))?), records)?;
+    Ok(())
+}
+
+// One number from a benchmark run, for comparing runs against each other.
+// Every metric is lower-is-better: Criterion mean times in ns, protocol
+// rounds, sizes in bytes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Metric {
+    pub name: String,
+    pub value: f64,
+    pub unit: String,
+}
+
+// Benchmark groups measured in protocol rounds rather than wall-clock time.
+pub const ROUND_GROUPS: [&str; 1] = ["roast_rounds"];
+
+// Criterion keeps the latest estimates of each benchmark in
+// `<dir>/<group>/<bench>/new/estimates.json`; the metric is named after the
+// path between `dir` and `new`.
+pub fn criterion_metrics<P: AsRef<Path>>(dir: P) -> Result<Vec<Metric>, ResultsError> {
+    let dir = dir.as_ref();
+    let mut metrics = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let estimates = current.join("new").join("estimates.json");
+        if estimates.is_file() {
+            let value: serde_json::Value = serde_json::from_reader(File::open(&estimates)?)?;
+            if let Some(mean) = value.pointer("/mean/point_estimate").and_then(|mean| mean.as_f64()) {
+                let name = current.strip_prefix(dir).unwrap_or(&current).to_string_lossy().replace('\\', "/");
+                let rounds = ROUND_GROUPS.iter().any(|group| name.split('/').next() == Some(*group));
+                metrics.push(Metric {
+                    name,
+                    value: mean,
+                    unit: if rounds { "rounds" } else { "ns" }.to_string(),
+                });
+            }
+            continue;
+        }
+        for entry in fs::read_dir(&current)? {
+            let path = entry?.path();
+            // Criterion's HTML reports live in `report` directories.
+            if path.is_dir() && path.file_name().is_some_and(|name| name != "report") {
+                pending.push(path);
+            }
+        }
+    }
+    metrics.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(metrics)
+}
+
+pub fn size_metrics(records: &[SizeRecord]) -> Vec<Metric> {
+    records
+        .iter()
+        .map(|record| Metric {
+            name: format!(
+                "sizes/{}/n{}_t{}/{}/{}",
+                record.scheme, record.system_size, record.threshold, record.object, record.encoding
+            ),
+            value: record.bytes as f64,
+            unit: "bytes".to_string(),
+        })
+        .collect()
+}
+
+pub fn read_sizes<P: AsRef<Path>>(dir: P) -> Result<Vec<SizeRecord>, ResultsError> {
+    Ok(serde_json::from_reader(BufReader::new(File::open(dir.as_ref().join("sizes.json"))?))?)
+}
+
+pub fn write_results<P: AsRef<Path>>(path: P, metrics: &[Metric]) -> Result<(), ResultsError> {
+    serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), metrics)?;
+    Ok(())
+}
+
+pub fn read_results<P: AsRef<Path>>(path: P) -> Result<Vec<Metric>, ResultsError> {
+    Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetricDelta {
+    pub name: String,
+    pub unit: String,
+    pub baseline: f64,
+    pub current: f64,
+    // (current - baseline) / baseline; positive means slower or larger.
+    pub relative_change: f64,
+    pub regression: bool,
+}
+
+// Deltas for every metric present in both runs; a metric regresses if it
+// grew by more than `threshold` (e.g. 0.05 for 5%). Metrics in only one run
+// are returned separately, since renamed benchmarks should not pass silently.
+pub fn compare(baseline: &[Metric], current: &[Metric], threshold: f64) -> (Vec<MetricDelta>, Vec<String>) {
+    let mut unmatched = Vec::new();
+    let mut deltas = Vec::new();
+    for metric in current {
+        let Some(old) = baseline.iter().find(|old| old.name == metric.name && old.unit == metric.unit) else {
+            unmatched.push(metric.name.clone());
+            continue;
+        };
+        let relative_change = if old.value == 0.0 {
+            if metric.value == 0.0 { 0.0 } else { f64::INFINITY }
+        } else {
+            (metric.value - old.value) / old.value
+        };
+        deltas.push(MetricDelta {
+            name: metric.name.clone(),
+            unit: metric.unit.clone(),
+            baseline: old.value,
+            current: metric.value,
+            relative_change,
+            regression: relative_change > threshold,
+        });
+    }
+    unmatched.extend(
+        baseline
+            .iter()
+            .filter(|old| !current.iter().any(|metric| metric.name == old.name))
+            .map(|old| old.name.clone()),
+    );
+    (deltas, unmatched)
+}
+
+// Default location of the exported results, next to Criterion's own output.
+pub fn results_path<P: AsRef<Path>>(dir: P) -> PathBuf {
+    dir.as_ref().join("results.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn canonical<'a>(records: &'a [SizeRecord], object: &str) -> &'a SizeRecord {
+        records
+            .iter()
+            .find(|record| record.object == object && record.encoding == "canonical")
+            .unwrap()
+    }
+
+    #[test]
+    fn frost_sizes_cover_every_object_and_encoding() {
+        let (settings, package) = testing::committee(1);
+        let mut rng = testing::rng(2);
+        let round1 = crate::frost::vote_commitments(&settings, &package, &mut rng).unwrap();
+        let round2 = crate::frost::sign_message(&settings, &package, &round1, b"sizes").unwrap();
+        let signature =
+            frost::aggregate(round2.signing_package(), round2.signature_shares(), package.public()).unwrap();
+
+        let records = frost_sizes(&settings, &round1, &round2, &signature).unwrap();
+        assert_eq!(records.len(), 4 * 3);
+        assert_eq!(canonical(&records, "final_signature").bytes, 64);
+        assert_eq!(canonical(&records, "signature_share").bytes, 32);
+        let keys = frost_key_sizes(&settings, package.public()).unwrap();
+        assert_eq!(canonical(&keys, "group_key").bytes, 32);
+        // Every verifying share is in the package.
+        assert!(canonical(&keys, "public_key_package").bytes > 7 * 32);
+    }
+
+    #[test]
+    fn multisig_certificate_is_t_signatures() {
+        let records = multisig_sizes(7, 5);
+        assert_eq!(canonical(&records, "certificate").bytes, 5 * 64);
+        assert_eq!(canonical(&records, "group_key").bytes, 7 * 32);
+    }
+
+    #[test]
+    fn size_table_has_a_row_per_committee_and_a_column_per_object() {
+        let mut records = multisig_sizes(7, 5);
+        records.extend(multisig_sizes(10, 7));
+        records.push(SizeRecord {
+            scheme: "frost".to_string(),
+            system_size: 7,
+            threshold: 5,
+            object: "final_signature".to_string(),
+            encoding: "canonical".to_string(),
+            bytes: 64,
+        });
+        assert_eq!(
+            size_table(&records),
+            "| scheme | n | t | signature_share | certificate | group_key | final_signature |\n\
+             |---|---:|---:|---:|---:|---:|---:|\n\
+             | multisig | 7 | 5 | 64 | 320 | 224 |  |\n\
+             | multisig | 10 | 7 | 64 | 448 | 320 |  |\n\
+             | frost | 7 | 5 |  |  |  | 64 |\n"
+        );
+    }
+
+    #[test]
+    fn signing_draws_only_the_nonce_randomness() {
+        let settings = testing::settings(7, 5);
+        for record in frost_entropy(&settings, b"entropy", testing::rng(3)).unwrap() {
+            if let Some(expected) = record.expected_bytes {
+                assert_eq!(record.bytes, expected, "{}", record.phase);
+            }
+        }
+    }
+
+    fn metric(name: &str, value: f64) -> Metric {
+        Metric {
+            name: name.to_string(),
+            value,
+            unit: "ns".to_string(),
+        }
+    }
+
+    #[test]
+    fn compare_flags_growth_beyond_the_threshold() {
+        let baseline = [metric("a", 100.0), metric("b", 100.0), metric("gone", 1.0)];
+        let current = [metric("a", 104.0), metric("b", 110.0), metric("new", 1.0)];
+        let (deltas, unmatched) = compare(&baseline, &current, 0.05);
+        let regressed: Vec<&str> = deltas.iter().filter(|d| d.regression).map(|d| d.name.as_str()).collect();
+        assert_eq!(regressed, ["b"]);
+        assert_eq!(unmatched, ["new", "gone"]);
+    }
+
+    #[test]
+    fn exported_sizes_read_back_as_metrics() {
+        let dir = std::env::temp_dir().join(format!("thesis-results-{}", std::process::id()));
+        let records = multisig_sizes(7, 5);
+        export_sizes(&dir, &records).unwrap();
+        let read = read_sizes(&dir);
+        let table = fs::read_to_string(dir.join("sizes.md"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(read.unwrap(), records);
+        assert_eq!(table.unwrap(), size_table(&records));
+        let metrics = size_metrics(&records);
+        assert_eq!(metrics[1].name, "sizes/multisig/n7_t5/certificate/canonical");
+        assert_eq!((metrics[1].value, metrics[1].unit.as_str()), (320.0, "bytes"));
+    }
+}
@@ -0,0 +1,99 @@
+use old_rand::rngs::ThreadRng;
+use old_rand::{CryptoRng, RngCore};
+use rand_chacha::ChaCha20Rng;
+use rand_chacha::rand_core::SeedableRng;
+
+// Hands out the RNG used for each protocol run (or benchmark iteration), so
+// runners can switch between OS randomness and reproducible seeded streams
+// without touching the library functions, which all take `&mut RNG`.
+pub trait RngProvider {
+    type Rng: RngCore + CryptoRng;
+
+    fn rng(&mut self) -> Self::Rng;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ThreadRngProvider;
+
+impl RngProvider for ThreadRngProvider {
+    type Rng = ThreadRng;
+
+    fn rng(&mut self) -> ThreadRng {
+        old_rand::thread_rng()
+    }
+}
+
+// Every call returns a ChaCha20 stream derived from the seed and a counter,
+// so run i of a seeded experiment always sees the same randomness.
+#[derive(Clone, Debug)]
+pub struct SeededRngProvider {
+    seed: u64,
+    next_stream: u64,
+}
+
+impl SeededRngProvider {
+    pub fn new(seed: u64) -> Self {
+        SeededRngProvider {
+            seed,
+            next_stream: 0,
+        }
+    }
+}
+
+impl RngProvider for SeededRngProvider {
+    type Rng = ChaCha20Rng;
+
+    fn rng(&mut self) -> ChaCha20Rng {
+        let mut rng = ChaCha20Rng::seed_from_u64(self.seed);
+        rng.set_stream(self.next_stream);
+        self.next_stream += 1;
+        rng
+    }
+}
+
+// Wraps an RNG and counts the bytes drawn from it, to compare entropy use
+// between schemes and phases and to check nonce generation against what the
+// protocol should need.
+pub struct CountingRng<R> {
+    inner: R,
+    bytes: u64,
+}
+
+impl<R: RngCore> CountingRng<R> {
+    pub fn new(inner: R) -> Self {
+        CountingRng { inner, bytes: 0 }
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    // Returns the count so far and starts a new one, for per-phase totals.
+    pub fn take_bytes(&mut self) -> u64 {
+        std::mem::take(&mut self.bytes)
+    }
+}
+
+impl<R: RngCore> RngCore for CountingRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.bytes += 4;
+        self.inner.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.bytes += 8;
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.bytes += dest.len() as u64;
+        self.inner.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), old_rand::Error> {
+        self.bytes += dest.len() as u64;
+        self.inner.try_fill_bytes(dest)
+    }
+}
+
+impl<R: CryptoRng> CryptoRng for CountingRng<R> {}
@@ -0,0 +1,44 @@
+use std::env;
+use std::path::Path;
+use std::process;
+use thesis::preflight;
+use thesis::queue::{ExperimentConfig, ExperimentQueue};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let force = args.iter().any(|arg| arg == "--force");
+    let Some(root) = args.iter().find(|arg| !arg.starts_with("--")) else {
+        eprintln!("Usage: run_queue <queue-dir> [--force]");
+        process::exit(2);
+    };
+
+    let mut queue = match ExperimentQueue::open(root) {
+        Ok(queue) => queue,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+
+    let report = preflight::run_checks(Path::new(root), preflight::MIN_FREE_BYTES);
+    if !report.passed() {
+        for check in &report.checks {
+            eprintln!("{}", check);
+        }
+        if !force {
+            eprintln!("Preflight failed; refusing measured runs (use --force to override).");
+            process::exit(1);
+        }
+    }
+
+    let progress = |index: usize, total: usize, config: &ExperimentConfig| {
+        println!("[{}/{}] running {}", index + 1, total, config.name);
+    };
+    match queue.run(progress) {
+        Ok(summary) => println!("Done: {} experiments completed, {} failed", summary.completed, summary.failed),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    }
+}
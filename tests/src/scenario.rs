@@ -0,0 +1,309 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::latency::LatencyMatrix;
+use crate::netsim::{self, NetworkModel, ScenarioAction, ScenarioEvent, SignerFaults, SimConfig, SimProtocol, SimReport};
+use crate::rng::{RngProvider, SeededRngProvider};
+
+// A scenario file describes one adversarial `netsim` experiment completely:
+// network, protocol parameters, seed and a timeline such as "at 10 ms signer
+// 3 goes offline; at 50 ms the coordinator restarts; partition until 90 ms".
+// The same file and seed always give the same reports.
+
+#[derive(Debug)]
+pub enum ScenarioError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Csv(csv::Error),
+    // Not a square matrix of non-negative latencies, or its size differs
+    // from `system_size`.
+    InvalidLatencies,
+    InvalidThreshold { system_size: usize, threshold: usize },
+    UnknownParticipant(usize),
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScenarioError::Io(e) => write!(f, "io error: {e}"),
+            ScenarioError::Json(e) => write!(f, "json error: {e}"),
+            ScenarioError::Csv(e) => write!(f, "csv error: {e}"),
+            ScenarioError::InvalidLatencies => write!(f, "latency matrix does not match the system size"),
+            ScenarioError::InvalidThreshold { system_size, threshold } => {
+                write!(f, "threshold {threshold} is not between 1 and the system size {system_size}")
+            }
+            ScenarioError::UnknownParticipant(index) => write!(f, "participant {index} is not in the committee"),
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+impl From<io::Error> for ScenarioError {
+    fn from(e: io::Error) -> Self {
+        ScenarioError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ScenarioError {
+    fn from(e: serde_json::Error) -> Self {
+        ScenarioError::Json(e)
+    }
+}
+
+impl From<csv::Error> for ScenarioError {
+    fn from(e: csv::Error) -> Self {
+        ScenarioError::Csv(e)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LatencySpec {
+    // The same one-way latency between any two participants.
+    Uniform { ms: f64 },
+    // `LatencyMatrix::synthetic`, drawn from the scenario seed.
+    Synthetic { max_ms: f64 },
+    // A measured matrix, relative to the current directory.
+    Csv { path: PathBuf },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "all_protocols")]
+    pub protocols: Vec<SimProtocol>,
+    pub system_size: usize,
+    pub threshold: usize,
+    #[serde(default)]
+    pub coordinator: usize,
+    pub latency: LatencySpec,
+    #[serde(default)]
+    pub jitter_ms: f64,
+    #[serde(default)]
+    pub drop_probability: f64,
+    #[serde(default = "default_processing_ms")]
+    pub processing_ms: f64,
+    #[serde(default = "default_retransmit_ms")]
+    pub retransmit_ms: f64,
+    #[serde(default = "default_deadline_ms")]
+    pub deadline_ms: f64,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default = "default_trials")]
+    pub trials: usize,
+    #[serde(default)]
+    pub events: Vec<ScenarioEvent>,
+}
+
+fn all_protocols() -> Vec<SimProtocol> {
+    SimProtocol::ALL.to_vec()
+}
+
+fn default_processing_ms() -> f64 {
+    1.0
+}
+
+fn default_retransmit_ms() -> f64 {
+    500.0
+}
+
+fn default_deadline_ms() -> f64 {
+    10_000.0
+}
+
+fn default_trials() -> usize {
+    1
+}
+
+impl Scenario {
+    pub fn from_json(bytes: &[u8]) -> Result<Self, ScenarioError> {
+        let scenario: Scenario = serde_json::from_slice(bytes)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ScenarioError> {
+        Self::from_json(&fs::read(path)?)
+    }
+
+    fn validate(&self) -> Result<(), ScenarioError> {
+        if self.threshold == 0 || self.threshold > self.system_size {
+            return Err(ScenarioError::InvalidThreshold {
+                system_size: self.system_size,
+                threshold: self.threshold,
+            });
+        }
+        let mut participants = vec![self.coordinator];
+        for event in &self.events {
+            match &event.action {
+                ScenarioAction::Offline { signer }
+                | ScenarioAction::Online { signer }
+                | ScenarioAction::Crash { signer }
+                | ScenarioAction::Slow { signer, .. }
+                | ScenarioAction::Silent { signer }
+                | ScenarioAction::Invalid { signer }
+                | ScenarioAction::Honest { signer } => participants.push(*signer),
+                ScenarioAction::Partition { signers, .. } => participants.extend(signers),
+                ScenarioAction::CoordinatorRestart { .. } => {}
+            }
+        }
+        match participants.into_iter().find(|&index| index >= self.system_size) {
+            Some(index) => Err(ScenarioError::UnknownParticipant(index)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn config(&self) -> SimConfig {
+        SimConfig {
+            coordinator: self.coordinator,
+            threshold: self.threshold,
+            retransmit_ms: self.retransmit_ms,
+            deadline_ms: self.deadline_ms,
+        }
+    }
+
+    // Every protocol `trials` times, in the order listed. A synthetic matrix
+    // uses the first stream of the seed and the runs the second.
+    pub fn run(&self) -> Result<Vec<SimReport>, ScenarioError> {
+        let mut provider = SeededRngProvider::new(self.seed);
+        let mut matrix_rng = provider.rng();
+        let latencies = match &self.latency {
+            LatencySpec::Uniform { ms } => {
+                let n = self.system_size;
+                let rows = (0..n).map(|from| (0..n).map(|to| if from == to { 0.0 } else { *ms }).collect()).collect();
+                LatencyMatrix::new(rows).ok_or(ScenarioError::InvalidLatencies)?
+            }
+            LatencySpec::Synthetic { max_ms } => LatencyMatrix::synthetic(self.system_size, *max_ms, &mut matrix_rng),
+            LatencySpec::Csv { path } => LatencyMatrix::from_csv(path)?.ok_or(ScenarioError::InvalidLatencies)?,
+        };
+        if latencies.system_size() != self.system_size {
+            return Err(ScenarioError::InvalidLatencies);
+        }
+        let network = NetworkModel {
+            latencies,
+            jitter_ms: self.jitter_ms,
+            drop_probability: self.drop_probability,
+            processing_ms: self.processing_ms,
+        };
+
+        let config = self.config();
+        let mut rng = provider.rng();
+        let mut reports = Vec::with_capacity(self.protocols.len() * self.trials);
+        for &protocol in &self.protocols {
+            for _ in 0..self.trials {
+                reports.push(netsim::simulate_scenario(
+                    protocol,
+                    &network,
+                    &config,
+                    &SignerFaults::default(),
+                    &self.events,
+                    &mut rng,
+                ));
+            }
+        }
+        Ok(reports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 5-of-7 with 10 ms links, as in the netsim tests, so the completion
+    // times follow from the timeline by hand.
+    fn scenario(protocol: &str, events: &str) -> Scenario {
+        let json = format!(
+            r#"{{"protocols": ["{protocol}"], "system_size": 7, "threshold": 5,
+                "latency": {{"kind": "uniform", "ms": 10.0}}, "events": {events}}}"#
+        );
+        Scenario::from_json(json.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn offline_signers_delay_roast_until_one_returns() {
+        // Only four signers answer until signer 1 is back for the
+        // retransmission at 500 ms; its commitment arrives at 521 ms.
+        let scenario = scenario(
+            "roast",
+            r#"[{"at_ms": 0, "action": "offline", "signer": 1},
+                {"at_ms": 0, "action": "offline", "signer": 2},
+                {"at_ms": 0, "action": "offline", "signer": 3},
+                {"at_ms": 100, "action": "online", "signer": 1}]"#,
+        );
+        let report = scenario.run().unwrap()[0];
+        assert_eq!((report.completion_ms, report.rounds, report.sessions), (Some(542.0), 2, 1));
+        assert_eq!(report.messages_dropped, 5);
+    }
+
+    #[test]
+    fn crashed_and_slow_signers_delay_roast() {
+        // Signer 1 never answers and 2 and 3 answer 100 ms late, so the first
+        // session opens on signer 2's commitment at 121 ms and completes with
+        // its share at 242 ms, before the second session can.
+        let scenario = scenario(
+            "roast",
+            r#"[{"at_ms": 0, "action": "crash", "signer": 1},
+                {"at_ms": 0, "action": "slow", "signer": 2, "delay_ms": 100.0},
+                {"at_ms": 0, "action": "slow", "signer": 3, "delay_ms": 100.0}]"#,
+        );
+        let report = scenario.run().unwrap()[0];
+        assert_eq!((report.completion_ms, report.rounds, report.sessions), (Some(242.0), 2, 2));
+        assert_eq!(report.messages_dropped, 0);
+    }
+
+    #[test]
+    fn coordinator_restart_starts_frost_over() {
+        // Restarting at 30 ms loses the shares that arrive at 42 ms; the new
+        // session starts when the coordinator is back at 130 ms.
+        let scenario = scenario("frost", r#"[{"at_ms": 30, "action": "coordinator_restart", "downtime_ms": 100}]"#);
+        let report = scenario.run().unwrap()[0];
+        assert_eq!((report.completion_ms, report.sessions), (Some(172.0), 2));
+        assert_eq!(report.messages_dropped, 4);
+    }
+
+    #[test]
+    fn partition_drops_messages_across_the_cut() {
+        let scenario = scenario(
+            "multisig",
+            r#"[{"at_ms": 0, "action": "partition", "signers": [1, 2, 3], "until_ms": 100}]"#,
+        );
+        let report = scenario.run().unwrap()[0];
+        assert_eq!((report.completion_ms, report.messages_dropped), (Some(521.0), 3));
+    }
+
+    #[test]
+    fn scenarios_are_validated() {
+        let bad_signer = r#"{"system_size": 7, "threshold": 5, "latency": {"kind": "uniform", "ms": 1.0},
+            "events": [{"at_ms": 5, "action": "silent", "signer": 7}]}"#;
+        assert!(matches!(
+            Scenario::from_json(bad_signer.as_bytes()),
+            Err(ScenarioError::UnknownParticipant(7))
+        ));
+        let bad_threshold = r#"{"system_size": 7, "threshold": 8, "latency": {"kind": "uniform", "ms": 1.0}}"#;
+        assert!(matches!(
+            Scenario::from_json(bad_threshold.as_bytes()),
+            Err(ScenarioError::InvalidThreshold { .. })
+        ));
+        let unknown_field = r#"{"system_size": 7, "threshold": 5, "latency": {"kind": "uniform", "ms": 1.0}, "seeds": 1}"#;
+        assert!(matches!(Scenario::from_json(unknown_field.as_bytes()), Err(ScenarioError::Json(_))));
+
+        let scenario = scenario("roast", "[]");
+        assert_eq!(scenario.protocols, [SimProtocol::Roast]);
+        assert_eq!((scenario.trials, scenario.retransmit_ms), (1, 500.0));
+    }
+
+    #[test]
+    fn example_scenario_runs_reproducibly() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/scenarios/restart_and_partition.json");
+        let scenario = Scenario::load(path).unwrap();
+        let reports = scenario.run().unwrap();
+        assert_eq!(reports.len(), 30);
+        assert_eq!(reports, scenario.run().unwrap());
+    }
+}
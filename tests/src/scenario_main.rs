@@ -0,0 +1,26 @@
+use std::env;
+use std::process;
+use thesis::scenario::Scenario;
+
+// Runs scenario files and prints one JSON report per protocol run.
+fn main() {
+    let paths: Vec<String> = env::args().skip(1).collect();
+    if paths.is_empty() {
+        eprintln!("Usage: scenario <scenario.json>...");
+        process::exit(2);
+    }
+    for path in &paths {
+        let reports = Scenario::load(path).and_then(|scenario| scenario.run());
+        match reports {
+            Ok(reports) => {
+                for report in reports {
+                    println!("{}", serde_json::to_string(&report).expect("reports serialize"));
+                }
+            }
+            Err(e) => {
+                eprintln!("{path}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
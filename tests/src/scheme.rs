@@ -0,0 +1,289 @@
+use frost_ed25519::{self as frost, Signature};
+use multisig::{Committee, KeypairShare, Signer};
+use old_rand::{CryptoRng, RngCore};
+use std::fmt::Debug;
+
+use crate::Settings;
+use crate::frost::{FrostPackage, FrostRound1, FrostRound2, FrostSettings};
+
+// Common shape of every scheme compared in the thesis, so benchmarks and
+// tests can be written once and instantiated per scheme. Schemes without a
+// commitment round use `()` for `Round1`.
+pub trait ThresholdScheme {
+    type Settings: Settings;
+    type Keys;
+    type Round1;
+    type Round2;
+    type Signature;
+    type Error: Debug;
+
+    const NAME: &'static str;
+
+    fn setup<RNG: RngCore + CryptoRng>(settings: &Self::Settings, rng: &mut RNG) -> Result<Self::Keys, Self::Error>;
+
+    fn sign_round1<RNG: RngCore + CryptoRng>(
+        settings: &Self::Settings,
+        keys: &Self::Keys,
+        rng: &mut RNG,
+    ) -> Result<Self::Round1, Self::Error>;
+
+    fn sign_round2(
+        settings: &Self::Settings,
+        keys: &Self::Keys,
+        round1: &Self::Round1,
+        message: &[u8],
+    ) -> Result<Self::Round2, Self::Error>;
+
+    fn aggregate(
+        settings: &Self::Settings,
+        keys: &Self::Keys,
+        round2: &Self::Round2,
+    ) -> Result<Self::Signature, Self::Error>;
+
+    fn verify(
+        settings: &Self::Settings,
+        keys: &Self::Keys,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), Self::Error>;
+}
+
+// FROST with trusted-dealer key generation.
+pub struct Frost;
+
+// FROST with distributed key generation; signing is identical.
+pub struct FrostDkg;
+
+macro_rules! impl_frost_scheme {
+    ($scheme:ty, $name:expr, $setup:path) => {
+        impl ThresholdScheme for $scheme {
+            type Settings = FrostSettings;
+            type Keys = FrostPackage;
+            type Round1 = FrostRound1;
+            type Round2 = FrostRound2;
+            type Signature = Signature;
+            type Error = frost::Error;
+
+            const NAME: &'static str = $name;
+
+            fn setup<RNG: RngCore + CryptoRng>(settings: &FrostSettings, rng: &mut RNG) -> Result<FrostPackage, frost::Error> {
+                $setup(settings, rng)
+            }
+
+            fn sign_round1<RNG: RngCore + CryptoRng>(
+                settings: &FrostSettings,
+                keys: &FrostPackage,
+                rng: &mut RNG,
+            ) -> Result<FrostRound1, frost::Error> {
+                crate::frost::vote_commitments(settings, keys, rng)
+            }
+
+            fn sign_round2(
+                settings: &FrostSettings,
+                keys: &FrostPackage,
+                round1: &FrostRound1,
+                message: &[u8],
+            ) -> Result<FrostRound2, frost::Error> {
+                crate::frost::sign_message(settings, keys, round1, message)
+            }
+
+            fn aggregate(_settings: &FrostSettings, keys: &FrostPackage, round2: &FrostRound2) -> Result<Signature, frost::Error> {
+                frost::aggregate(round2.signing_package(), round2.signature_shares(), keys.public())
+            }
+
+            fn verify(
+                _settings: &FrostSettings,
+                keys: &FrostPackage,
+                message: &[u8],
+                signature: &Signature,
+            ) -> Result<(), frost::Error> {
+                keys.public().verifying_key().verify(message, signature)
+            }
+        }
+    };
+}
+
+impl_frost_scheme!(Frost, "frost", crate::frost::setup);
+impl_frost_scheme!(FrostDkg, "frost_dkg", crate::frost::setup_dkg);
+
+// The ed25519 multisig baseline: the certificate is t individual signatures,
+// so there is no commitment round and aggregation only collects them. It
+// takes FROST's settings so both sign the same transcripts.
+pub struct Multisig;
+
+pub struct MultisigKeys {
+    participants: Vec<KeypairShare>,
+    committee: Committee,
+}
+
+#[derive(Debug)]
+pub enum MultisigError {
+    InvalidCertificate,
+}
+
+impl ThresholdScheme for Multisig {
+    type Settings = FrostSettings;
+    type Keys = MultisigKeys;
+    type Round1 = ();
+    type Round2 = Vec<multisig::Signature>;
+    type Signature = Vec<multisig::Signature>;
+    type Error = MultisigError;
+
+    const NAME: &'static str = "multisig";
+
+    fn setup<RNG: RngCore + CryptoRng>(settings: &FrostSettings, _rng: &mut RNG) -> Result<MultisigKeys, MultisigError> {
+        let participants: Vec<KeypairShare> = (0..settings.system_size()).map(|_| KeypairShare::default()).collect();
+        let mut committee = Committee::new();
+        for keypair in &participants {
+            committee.add_key(keypair.verifying_share.clone());
+        }
+        Ok(MultisigKeys { participants, committee })
+    }
+
+    fn sign_round1<RNG: RngCore + CryptoRng>(
+        _settings: &FrostSettings,
+        _keys: &MultisigKeys,
+        _rng: &mut RNG,
+    ) -> Result<(), MultisigError> {
+        Ok(())
+    }
+
+    fn sign_round2(
+        settings: &FrostSettings,
+        keys: &MultisigKeys,
+        _round1: &(),
+        message: &[u8],
+    ) -> Result<Self::Round2, MultisigError> {
+        Ok(keys
+            .participants
+            .iter()
+            .take(settings.threshold() as usize)
+            .map(|keypair| keypair.sign(message))
+            .collect())
+    }
+
+    fn aggregate(_settings: &FrostSettings, _keys: &MultisigKeys, round2: &Self::Round2) -> Result<Self::Signature, MultisigError> {
+        Ok(round2.clone())
+    }
+
+    fn verify(
+        settings: &FrostSettings,
+        keys: &MultisigKeys,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), MultisigError> {
+        keys.committee
+            .verify(message, signature, settings.threshold() as usize)
+            .map_err(|_| MultisigError::InvalidCertificate)
+    }
+}
+
+// Runs one complete protocol execution of any scheme.
+pub fn run_once<S, RNG>(settings: &S::Settings, message: &[u8], rng: &mut RNG) -> Result<S::Signature, S::Error>
+where
+    S: ThresholdScheme,
+    RNG: RngCore + CryptoRng,
+{
+    let keys = S::setup(settings, rng)?;
+    let signature = sign_with_keys::<S, RNG>(settings, &keys, message, rng)?;
+    S::verify(settings, &keys, message, &signature)?;
+    Ok(signature)
+}
+
+// One signing session with existing keys.
+pub fn sign_with_keys<S, RNG>(
+    settings: &S::Settings,
+    keys: &S::Keys,
+    message: &[u8],
+    rng: &mut RNG,
+) -> Result<S::Signature, S::Error>
+where
+    S: ThresholdScheme,
+    RNG: RngCore + CryptoRng,
+{
+    let _session = tracing::info_span!("session", scheme = S::NAME).entered();
+    tracing::debug!("session opened");
+    let round1 = S::sign_round1(settings, keys, rng)?;
+    let round2 = S::sign_round2(settings, keys, &round1, message)?;
+    let signature = S::aggregate(settings, keys, &round2)?;
+    tracing::debug!("session closed");
+    Ok(signature)
+}
+
+// Signs a message given only its digest under the settings' hash function,
+// as computed by `transcript::reader_digest`. The signature is over
+// `transcript::digest_transcript`, so verifiers need the digest, not the
+// message.
+pub fn sign_digest<S, RNG>(
+    settings: &S::Settings,
+    keys: &S::Keys,
+    digest: &[u8],
+    rng: &mut RNG,
+) -> Result<S::Signature, S::Error>
+where
+    S: ThresholdScheme,
+    RNG: RngCore + CryptoRng,
+{
+    let message = crate::transcript::digest_transcript(settings.hash_function(), digest);
+    sign_with_keys::<S, RNG>(settings, keys, &message, rng)
+}
+
+pub fn verify_digest<S: ThresholdScheme>(
+    settings: &S::Settings,
+    keys: &S::Keys,
+    digest: &[u8],
+    signature: &S::Signature,
+) -> Result<(), S::Error> {
+    let message = crate::transcript::digest_transcript(settings.hash_function(), digest);
+    S::verify(settings, keys, &message, signature)
+}
+
+// Threshold BLS; round 1 is empty since partial signatures need no nonces.
+#[cfg(feature = "bls")]
+pub struct Bls;
+
+#[cfg(feature = "bls")]
+impl ThresholdScheme for Bls {
+    type Settings = crate::bls::BlsSettings;
+    type Keys = crate::bls::BlsPackage;
+    type Round1 = ();
+    type Round2 = crate::bls::BlsRound2;
+    type Signature = bls12_381::G1Affine;
+    type Error = crate::bls::BlsError;
+
+    const NAME: &'static str = "bls";
+
+    fn setup<RNG: RngCore + CryptoRng>(settings: &Self::Settings, rng: &mut RNG) -> Result<Self::Keys, Self::Error> {
+        crate::bls::setup(settings, rng)
+    }
+
+    fn sign_round1<RNG: RngCore + CryptoRng>(
+        _settings: &Self::Settings,
+        _keys: &Self::Keys,
+        _rng: &mut RNG,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn sign_round2(
+        settings: &Self::Settings,
+        keys: &Self::Keys,
+        _round1: &(),
+        message: &[u8],
+    ) -> Result<Self::Round2, Self::Error> {
+        Ok(crate::bls::sign_message(settings, keys, message))
+    }
+
+    fn aggregate(settings: &Self::Settings, _keys: &Self::Keys, round2: &Self::Round2) -> Result<Self::Signature, Self::Error> {
+        crate::bls::aggregate(settings, round2)
+    }
+
+    fn verify(
+        _settings: &Self::Settings,
+        keys: &Self::Keys,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), Self::Error> {
+        crate::bls::verify(keys.public(), message, signature)
+    }
+}
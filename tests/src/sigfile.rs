@@ -0,0 +1,266 @@
+use frost_ed25519::{self as frost, Signature};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+// Every signature file starts with the magic, then either a zero byte and a
+// one-byte version (binary) or a space and "<version> <format>\n" (text).
+pub const MAGIC: &[u8; 4] = b"THSG";
+pub const VERSION: u8 = 1;
+
+// Upper bound on a single binary record, so a corrupt length prefix cannot
+// trigger a huge allocation.
+const MAX_RECORD_LEN: u32 = 1 << 20;
+
+#[derive(Debug)]
+pub enum SigFileError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Hex(hex::FromHexError),
+    Frost(frost::Error),
+    BadMagic,
+    UnsupportedVersion(u8),
+    UnknownFormat(String),
+    RecordTooLarge(u32),
+}
+
+impl fmt::Display for SigFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SigFileError::Io(e) => write!(f, "io error: {e}"),
+            SigFileError::Json(e) => write!(f, "json error: {e}"),
+            SigFileError::Hex(e) => write!(f, "hex error: {e}"),
+            SigFileError::Frost(e) => write!(f, "frost error: {e}"),
+            SigFileError::BadMagic => write!(f, "not a signature file (bad magic)"),
+            SigFileError::UnsupportedVersion(v) => write!(f, "unsupported signature file version {v}"),
+            SigFileError::UnknownFormat(format) => write!(f, "unknown signature file format {format}"),
+            SigFileError::RecordTooLarge(len) => write!(f, "signature record of {len} bytes exceeds the limit"),
+        }
+    }
+}
+
+impl std::error::Error for SigFileError {}
+
+impl From<io::Error> for SigFileError {
+    fn from(e: io::Error) -> Self {
+        SigFileError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for SigFileError {
+    fn from(e: serde_json::Error) -> Self {
+        SigFileError::Json(e)
+    }
+}
+
+impl From<hex::FromHexError> for SigFileError {
+    fn from(e: hex::FromHexError) -> Self {
+        SigFileError::Hex(e)
+    }
+}
+
+impl From<frost::Error> for SigFileError {
+    fn from(e: frost::Error) -> Self {
+        SigFileError::Frost(e)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SigFormat {
+    // u32 little-endian length prefix per record.
+    Binary,
+    // One hex-encoded record per line.
+    Hex,
+    // One `{"signature": "<hex>"}` object per line.
+    Json,
+}
+
+impl SigFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            SigFormat::Binary => "binary",
+            SigFormat::Hex => "hex",
+            SigFormat::Json => "json",
+        }
+    }
+}
+
+impl std::str::FromStr for SigFormat {
+    type Err = SigFileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "binary" => Ok(SigFormat::Binary),
+            "hex" => Ok(SigFormat::Hex),
+            "json" => Ok(SigFormat::Json),
+            _ => Err(SigFileError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonRecord {
+    signature: String,
+}
+
+// Streams signature records to `W`. Records are opaque bytes, so the same
+// format holds FROST signatures and multisig certificate entries.
+pub struct SignatureWriter<W: Write> {
+    writer: W,
+    format: SigFormat,
+    written: usize,
+}
+
+impl<W: Write> SignatureWriter<W> {
+    pub fn new(mut writer: W, format: SigFormat) -> Result<Self, SigFileError> {
+        writer.write_all(MAGIC)?;
+        match format {
+            SigFormat::Binary => writer.write_all(&[0, VERSION])?,
+            SigFormat::Hex | SigFormat::Json => writeln!(writer, " {} {}", VERSION, format.name())?,
+        }
+        Ok(SignatureWriter {
+            writer,
+            format,
+            written: 0,
+        })
+    }
+
+    pub fn write_record(&mut self, record: &[u8]) -> Result<(), SigFileError> {
+        match self.format {
+            SigFormat::Binary => {
+                let len = u32::try_from(record.len()).unwrap_or(u32::MAX);
+                if len > MAX_RECORD_LEN {
+                    return Err(SigFileError::RecordTooLarge(len));
+                }
+                self.writer.write_all(&len.to_le_bytes())?;
+                self.writer.write_all(record)?;
+            }
+            SigFormat::Hex => writeln!(self.writer, "{}", hex::encode(record))?,
+            SigFormat::Json => {
+                serde_json::to_writer(
+                    &mut self.writer,
+                    &JsonRecord {
+                        signature: hex::encode(record),
+                    },
+                )?;
+                writeln!(self.writer)?;
+            }
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    pub fn write_signature(&mut self, signature: &Signature) -> Result<(), SigFileError> {
+        self.write_record(&signature.serialize()?)
+    }
+
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    // Flushes and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, SigFileError> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+// Reads records back, detecting the format from the header.
+pub struct SignatureReader<R: BufRead> {
+    reader: R,
+    format: SigFormat,
+    line: String,
+}
+
+impl<R: BufRead> SignatureReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, SigFileError> {
+        let mut magic = [0u8; 5];
+        reader.read_exact(&mut magic)?;
+        if &magic[..4] != MAGIC {
+            return Err(SigFileError::BadMagic);
+        }
+        let format = match magic[4] {
+            0 => {
+                let mut version = [0u8; 1];
+                reader.read_exact(&mut version)?;
+                check_version(version[0])?;
+                SigFormat::Binary
+            }
+            b' ' => {
+                let mut header = String::new();
+                reader.read_line(&mut header)?;
+                let (version, format) = header.trim_end().split_once(' ').ok_or(SigFileError::BadMagic)?;
+                check_version(version.parse().map_err(|_| SigFileError::BadMagic)?)?;
+                match format.parse()? {
+                    SigFormat::Binary => return Err(SigFileError::UnknownFormat(format.to_string())),
+                    text => text,
+                }
+            }
+            _ => return Err(SigFileError::BadMagic),
+        };
+        Ok(SignatureReader {
+            reader,
+            format,
+            line: String::new(),
+        })
+    }
+
+    pub fn format(&self) -> SigFormat {
+        self.format
+    }
+
+    pub fn signatures(self) -> impl Iterator<Item = Result<Signature, SigFileError>> {
+        self.map(|record| Ok(Signature::deserialize(&record?)?))
+    }
+
+    fn next_record(&mut self) -> Result<Option<Vec<u8>>, SigFileError> {
+        match self.format {
+            SigFormat::Binary => {
+                let mut len = [0u8; 4];
+                match self.reader.read_exact(&mut len) {
+                    Ok(()) => {}
+                    Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                }
+                let len = u32::from_le_bytes(len);
+                if len > MAX_RECORD_LEN {
+                    return Err(SigFileError::RecordTooLarge(len));
+                }
+                let mut record = vec![0u8; len as usize];
+                self.reader.read_exact(&mut record)?;
+                Ok(Some(record))
+            }
+            SigFormat::Hex | SigFormat::Json => loop {
+                self.line.clear();
+                if self.reader.read_line(&mut self.line)? == 0 {
+                    return Ok(None);
+                }
+                let line = self.line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let encoded = match self.format {
+                    SigFormat::Json => serde_json::from_str::<JsonRecord>(line)?.signature,
+                    _ => line.to_string(),
+                };
+                return Ok(Some(hex::decode(encoded)?));
+            },
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for SignatureReader<R> {
+    type Item = Result<Vec<u8>, SigFileError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+fn check_version(version: u8) -> Result<(), SigFileError> {
+    if version != VERSION {
+        return Err(SigFileError::UnsupportedVersion(version));
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use std::env;
+use std::process;
+use thesis::attest::{self, Manifest, SignedManifest};
+
+fn usage() -> ! {
+    eprintln!("Usage:");
+    eprintln!("  sign_artifacts sign <key-file> <run-id> <out.json> <artifact>...");
+    eprintln!("  sign_artifacts verify <signed-manifest.json> <public-key-hex | public-key-file>");
+    process::exit(2);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("sign") if args.len() >= 5 => attest::read_signing_key(&args[1])
+            .and_then(|key| Manifest::build(&args[2], &args[4..])?.sign(&key))
+            .and_then(|signed| signed.write(&args[3])),
+        Some("verify") if args.len() >= 3 => SignedManifest::read(&args[1]).and_then(|signed| {
+            let trusted = attest::load_trusted_key(&args[2])?;
+            signed.verify(&trusted, true)?;
+            println!(
+                "OK: run {} ({} artifacts) signed by {}",
+                signed.manifest.run_id,
+                signed.manifest.entries.len(),
+                signed.public_key
+            );
+            Ok(())
+        }),
+        _ => usage(),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+}
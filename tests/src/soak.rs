@@ -0,0 +1,76 @@
+use std::env;
+use std::process;
+use std::time::{Duration, Instant};
+use thesis::asserts;
+use thesis::frost::{self, FrostSettings};
+use thesis::hash::HashFunction;
+use thesis::params::Params;
+
+// Alternates signing epochs with share refreshes for many cycles, asserting
+// after every refresh that the group key is unchanged and that signatures
+// made with the refreshed shares still verify. Prints timing drift between
+// the first and last tenth of the run.
+fn main() {
+    let args: Vec<usize> = env::args()
+        .skip(1)
+        .map(|arg| arg.parse().unwrap_or_else(|_| usage()))
+        .collect();
+    let (cycles, signatures_per_epoch, system_size, threshold) = match args.as_slice() {
+        [] => (1000, 5, 7, 5),
+        [cycles, signatures, n, t] => (*cycles, *signatures, *n, *t),
+        _ => usage(),
+    };
+
+    let settings = Params::new(system_size, threshold)
+        .map_err(|e| e.to_string())
+        .and_then(|params| FrostSettings::new(params, HashFunction::default()).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("Error: {e}");
+            process::exit(2);
+        });
+    let mut rng = old_rand::thread_rng();
+    let message = b"soak test message";
+
+    let mut package = frost::setup(&settings, &mut rng).expect("initial key generation");
+    let group_key = *package.public().verifying_key();
+    let mut epoch_times = Vec::with_capacity(cycles);
+    let mut refresh_times = Vec::with_capacity(cycles);
+
+    for cycle in 0..cycles {
+        let start = Instant::now();
+        for _ in 0..signatures_per_epoch {
+            let round1 = frost::vote_commitments(&settings, &package, &mut rng).expect("round 1");
+            let round2 = frost::sign_message(&settings, &package, &round1, message).expect("round 2");
+            frost::aggregate_verify(&settings, &package, &round1, &round2, message).expect("aggregate and verify");
+        }
+        epoch_times.push(start.elapsed());
+
+        let start = Instant::now();
+        package = frost::refresh(&settings, &package, &mut rng).expect("refresh");
+        refresh_times.push(start.elapsed());
+
+        if let Err(failure) = asserts::group_key_unchanged(&group_key, package.public()) {
+            panic!("cycle {cycle}: {failure}");
+        }
+    }
+
+    println!("Soak: {cycles} cycles of {signatures_per_epoch} signatures, n = {system_size}, t = {threshold}: OK");
+    report_drift("signing epoch", &epoch_times);
+    report_drift("refresh", &refresh_times);
+}
+
+fn report_drift(label: &str, times: &[Duration]) {
+    let tenth = (times.len() / 10).max(1);
+    let mean = |slice: &[Duration]| slice.iter().sum::<Duration>().as_secs_f64() * 1e3 / slice.len().max(1) as f64;
+    let first = mean(&times[..tenth.min(times.len())]);
+    let last = mean(&times[times.len().saturating_sub(tenth)..]);
+    println!(
+        "{label}: first 10% {first:.3} ms, last 10% {last:.3} ms, drift {:+.1}%",
+        (last - first) / first * 100.0
+    );
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: soak [<cycles> <signatures-per-epoch> <n> <t>]");
+    process::exit(2);
+}
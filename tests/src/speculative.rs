@@ -0,0 +1,80 @@
+use frost_ed25519::{self as frost, Signature};
+use old_rand::{CryptoRng, RngCore};
+use std::collections::BTreeSet;
+
+use crate::frost::{FrostPackage, FrostSettings};
+use crate::latency::{self, LatencyMatrix};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpeculativeOutcome {
+    // Simulated wall-clock time until the signature is available, in ms.
+    pub completion_ms: f64,
+    pub sessions: usize,
+    pub signature: Signature,
+}
+
+// Invites t + `extra` signers per session (closest first) and signs with the
+// first t that respond, so up to `extra` crashed invitees don't cost a
+// session timeout. Invitees that stay silent are marked unresponsive and the
+// next session invites the next-closest participants instead.
+#[allow(clippy::too_many_arguments)]
+pub fn run_speculative<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    matrix: &LatencyMatrix,
+    coordinator: usize,
+    crashed: &BTreeSet<usize>,
+    extra: usize,
+    timeout_ms: f64,
+    message: &[u8],
+    rng: &mut RNG,
+) -> Result<SpeculativeOutcome, frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let params = settings.params().map_err(|_| frost::Error::InvalidMinSigners)?;
+    let threshold = params.threshold() as usize;
+    let order = matrix.by_round_trip(coordinator);
+    let mut unresponsive: BTreeSet<usize> = BTreeSet::new();
+    let mut elapsed = 0.0;
+    let mut sessions = 0;
+
+    loop {
+        sessions += 1;
+        let invited: Vec<usize> = order
+            .iter()
+            .filter(|signer| !unresponsive.contains(*signer))
+            .take(threshold + extra)
+            .copied()
+            .collect();
+        if invited.len() < threshold {
+            // Fewer than t participants left that might still answer.
+            return Err(frost::Error::IncorrectNumberOfCommitments);
+        }
+
+        // `order` is sorted by round trip, so the responders arrive in this order.
+        let responders: Vec<usize> = invited
+            .iter()
+            .filter(|signer| !crashed.contains(*signer))
+            .take(threshold)
+            .copied()
+            .collect();
+        if responders.len() < threshold {
+            unresponsive.extend(invited.iter().filter(|signer| crashed.contains(*signer)).copied());
+            elapsed += timeout_ms;
+            continue;
+        }
+
+        let signers = latency::to_identifiers(&params, &responders).map_err(|_| frost::Error::UnknownIdentifier)?;
+        let round1 = crate::frost::vote_commitments_for(settings, packages, &signers, rng)?;
+        let round2 = crate::frost::sign_message(settings, packages, &round1, message)?;
+        let signature = frost::aggregate(round2.signing_package(), round2.signature_shares(), packages.public())?;
+        elapsed += matrix.completion_time(coordinator, &responders);
+
+        return Ok(SpeculativeOutcome {
+            completion_ms: elapsed,
+            sessions,
+            signature,
+        });
+    }
+}
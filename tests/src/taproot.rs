@@ -0,0 +1,137 @@
+// BIP-340 signing through frost-secp256k1-tr. The group key is tweaked as
+// for a Taproot key-path spend (BIP-341, optionally committing to a script
+// tree's merkle root), and the aggregate signature is a plain 64-byte
+// Schnorr signature over the x-only output key, checked here against
+// libsecp256k1's own verifier for interop.
+use frost_secp256k1_tr::keys::Tweak;
+use frost_secp256k1_tr::{self as frost_tr, Secp256K1Sha256TR, Signature, VerifyingKey};
+use old_rand::{CryptoRng, RngCore};
+use secp256k1::schnorr;
+use secp256k1::{Secp256k1, XOnlyPublicKey};
+use std::fmt;
+
+use crate::frost::{FrostPackage, FrostSettings, generic};
+
+#[derive(Debug)]
+pub enum TaprootError {
+    Frost(frost_tr::Error),
+    Secp256k1(secp256k1::Error),
+}
+
+impl fmt::Display for TaprootError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaprootError::Frost(e) => write!(f, "frost error: {e}"),
+            TaprootError::Secp256k1(e) => write!(f, "secp256k1 error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TaprootError {}
+
+impl From<frost_tr::Error> for TaprootError {
+    fn from(e: frost_tr::Error) -> Self {
+        TaprootError::Frost(e)
+    }
+}
+
+impl From<secp256k1::Error> for TaprootError {
+    fn from(e: secp256k1::Error) -> Self {
+        TaprootError::Secp256k1(e)
+    }
+}
+
+pub type TaprootPackage = FrostPackage<Secp256K1Sha256TR>;
+
+pub fn setup<RNG>(settings: &FrostSettings, rng: &mut RNG) -> Result<TaprootPackage, frost_tr::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    generic::setup(settings, rng)
+}
+
+// Applies the BIP-341 tweak to every share and the group key, giving key
+// material that signs directly for the output key. `None` is a key-path-only
+// output without a script tree. The VSS commitment no longer matches and is
+// dropped.
+pub fn tweak_package(packages: &TaprootPackage, merkle_root: Option<&[u8]>) -> TaprootPackage {
+    FrostPackage {
+        secret: packages
+            .secret
+            .iter()
+            .map(|(id, key_package)| (*id, key_package.clone().tweak(merkle_root)))
+            .collect(),
+        public: packages.public.clone().tweak(merkle_root),
+        commitment: None,
+    }
+}
+
+// Runs a full session on the tweaked package; the result verifies against
+// `output_key(packages, merkle_root)`.
+pub fn sign_key_path<RNG>(
+    settings: &FrostSettings,
+    packages: &TaprootPackage,
+    merkle_root: Option<&[u8]>,
+    message: &[u8],
+    rng: &mut RNG,
+) -> Result<Signature, frost_tr::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let tweaked = tweak_package(packages, merkle_root);
+    let round1 = generic::vote_commitments(settings, &tweaked, rng)?;
+    let round2 = generic::sign_message(settings, &tweaked, &round1, message)?;
+    frost_tr::aggregate(round2.signing_package(), round2.signature_shares(), tweaked.public())
+}
+
+pub fn output_key(packages: &TaprootPackage, merkle_root: Option<&[u8]>) -> VerifyingKey {
+    *packages.public.clone().tweak(merkle_root).verifying_key()
+}
+
+// The 32-byte x-only encoding used in BIP-340 and Taproot outputs; the
+// serialized key is SEC1-compressed, so this drops the parity byte.
+pub fn x_only(key: &VerifyingKey) -> Result<[u8; 32], TaprootError> {
+    let bytes = key.serialize()?;
+    let x_only = bytes
+        .get(1..)
+        .and_then(|x| <[u8; 32]>::try_from(x).ok())
+        .ok_or(TaprootError::Frost(frost_tr::Error::MalformedVerifyingKey))?;
+    Ok(x_only)
+}
+
+// Verifies with libsecp256k1 rather than frost, as an independent check that
+// the output is a standard BIP-340 signature.
+pub fn verify_bip340(x_only: &[u8; 32], message: &[u8], signature: &Signature) -> Result<(), TaprootError> {
+    let key = XOnlyPublicKey::from_slice(x_only)?;
+    let signature = schnorr::Signature::from_slice(&signature.serialize()?)?;
+    Secp256k1::verification_only().verify_schnorr(&signature, message, &key)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn key_path_signatures_verify_with_libsecp256k1() {
+        let settings = testing::settings(7, 5);
+        let mut rng = testing::rng(1);
+        let package = setup(&settings, &mut rng).unwrap();
+
+        for merkle_root in [None, Some(&[7u8; 32][..])] {
+            let signature = sign_key_path(&settings, &package, merkle_root, b"spend", &mut rng).unwrap();
+            let output = x_only(&output_key(&package, merkle_root)).unwrap();
+            verify_bip340(&output, b"spend", &signature).unwrap();
+            assert!(verify_bip340(&output, b"other spend", &signature).is_err());
+        }
+    }
+
+    #[test]
+    fn script_tree_changes_the_output_key() {
+        let package = setup(&testing::settings(7, 5), &mut testing::rng(2)).unwrap();
+        let key_path = output_key(&package, None);
+        assert_ne!(key_path, output_key(&package, Some(&[7u8; 32])));
+        assert_ne!(&key_path, package.public.verifying_key());
+    }
+}
@@ -0,0 +1,205 @@
+use frost_ed25519::{self as frost, Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::frost::{FrostPackage, FrostSettings};
+use crate::hash::HashFunction;
+use crate::params::{Params, ParamsError};
+use crate::rng::{RngProvider, SeededRngProvider};
+use crate::scheme::{self, ThresholdScheme};
+
+#[derive(Debug)]
+pub enum TestVectorError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Hex(hex::FromHexError),
+    Frost(frost::Error),
+    Params(ParamsError),
+    UnknownScheme(String),
+    // The named field differs from what the seed produces today.
+    Mismatch(&'static str),
+}
+
+impl fmt::Display for TestVectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestVectorError::Io(e) => write!(f, "io error: {e}"),
+            TestVectorError::Json(e) => write!(f, "json error: {e}"),
+            TestVectorError::Hex(e) => write!(f, "hex error: {e}"),
+            TestVectorError::Frost(e) => write!(f, "frost error: {e}"),
+            TestVectorError::Params(e) => write!(f, "invalid parameters: {e}"),
+            TestVectorError::UnknownScheme(name) => write!(f, "no test vectors for scheme {name}"),
+            TestVectorError::Mismatch(field) => write!(f, "{field} does not match the known answer"),
+        }
+    }
+}
+
+impl std::error::Error for TestVectorError {}
+
+impl From<io::Error> for TestVectorError {
+    fn from(e: io::Error) -> Self {
+        TestVectorError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for TestVectorError {
+    fn from(e: serde_json::Error) -> Self {
+        TestVectorError::Json(e)
+    }
+}
+
+impl From<hex::FromHexError> for TestVectorError {
+    fn from(e: hex::FromHexError) -> Self {
+        TestVectorError::Hex(e)
+    }
+}
+
+impl From<frost::Error> for TestVectorError {
+    fn from(e: frost::Error) -> Self {
+        TestVectorError::Frost(e)
+    }
+}
+
+impl From<ParamsError> for TestVectorError {
+    fn from(e: ParamsError) -> Self {
+        TestVectorError::Params(e)
+    }
+}
+
+// A known answer: with the RNG seeded from `seed` (stream 0 of
+// `SeededRngProvider`), key generation and one signing session over
+// `message` must produce exactly `group_key` and `signature`. Multisig is not
+// covered, since its key generation does not take an RNG.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestVector {
+    pub scheme: String,
+    pub seed: u64,
+    pub system_size: u16,
+    pub threshold: u16,
+    pub message: String,
+    pub group_key: String,
+    pub signature: String,
+}
+
+fn run<S>(settings: &FrostSettings, seed: u64, message: &[u8]) -> Result<(VerifyingKey, Signature), frost::Error>
+where
+    S: ThresholdScheme<Settings = FrostSettings, Keys = FrostPackage, Signature = Signature, Error = frost::Error>,
+{
+    let mut rng = SeededRngProvider::new(seed).rng();
+    let keys = S::setup(settings, &mut rng)?;
+    let round1 = S::sign_round1(settings, &keys, &mut rng)?;
+    let round2 = S::sign_round2(settings, &keys, &round1, message)?;
+    let signature = S::aggregate(settings, &keys, &round2)?;
+    Ok((*keys.public().verifying_key(), signature))
+}
+
+pub fn generate_vector(scheme: &str, seed: u64, params: Params, message: &[u8]) -> Result<TestVector, TestVectorError> {
+    let settings = FrostSettings::new(params, HashFunction::default())?;
+    let (group_key, signature) = match scheme {
+        name if name == scheme::Frost::NAME => run::<scheme::Frost>(&settings, seed, message)?,
+        name if name == scheme::FrostDkg::NAME => run::<scheme::FrostDkg>(&settings, seed, message)?,
+        other => return Err(TestVectorError::UnknownScheme(other.to_string())),
+    };
+    Ok(TestVector {
+        scheme: scheme.to_string(),
+        seed,
+        system_size: params.system_size(),
+        threshold: params.threshold(),
+        message: hex::encode(message),
+        group_key: hex::encode(group_key.serialize()?),
+        signature: hex::encode(signature.serialize()?),
+    })
+}
+
+impl TestVector {
+    // The recorded signature verifies under the recorded key, and both are
+    // reproduced from the seed.
+    pub fn check(&self) -> Result<(), TestVectorError> {
+        let message = hex::decode(&self.message)?;
+        let group_key = VerifyingKey::deserialize(&hex::decode(&self.group_key)?)?;
+        let signature = Signature::deserialize(&hex::decode(&self.signature)?)?;
+        group_key.verify(&message, &signature)?;
+
+        let params = Params::new(self.system_size as usize, self.threshold as usize)?;
+        let expected = generate_vector(&self.scheme, self.seed, params, &message)?;
+        if expected.group_key != self.group_key {
+            return Err(TestVectorError::Mismatch("group_key"));
+        }
+        if expected.signature != self.signature {
+            return Err(TestVectorError::Mismatch("signature"));
+        }
+        Ok(())
+    }
+}
+
+// Every scheme at a small and a BFT-sized committee, for a fixed seed.
+pub fn default_vectors(seed: u64) -> Result<Vec<TestVector>, TestVectorError> {
+    let mut vectors = Vec::new();
+    for scheme in [scheme::Frost::NAME, scheme::FrostDkg::NAME] {
+        for params in [Params::new(3, 2)?, Params::bft(3)?] {
+            vectors.push(generate_vector(scheme, seed, params, b"thesis test vector")?);
+        }
+    }
+    Ok(vectors)
+}
+
+pub fn write_vectors<P: AsRef<Path>>(path: P, vectors: &[TestVector]) -> Result<(), TestVectorError> {
+    fs::write(path, serde_json::to_vec_pretty(vectors)?)?;
+    Ok(())
+}
+
+pub fn read_vectors<P: AsRef<Path>>(path: P) -> Result<Vec<TestVector>, TestVectorError> {
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors_are_reproducible_from_their_seed() {
+        let vectors = default_vectors(2024).unwrap();
+        assert_eq!(vectors.len(), 4);
+        assert_eq!(vectors, default_vectors(2024).unwrap());
+        for vector in &vectors {
+            vector.check().unwrap();
+        }
+        // The seed is the only input that changes the answer.
+        let other = default_vectors(2025).unwrap();
+        for (vector, other) in vectors.iter().zip(&other) {
+            assert_ne!(vector.group_key, other.group_key);
+            assert_ne!(vector.signature, other.signature);
+        }
+    }
+
+    #[test]
+    fn altered_vectors_fail_the_check() {
+        let vector = generate_vector(scheme::Frost::NAME, 7, Params::new(3, 2).unwrap(), b"kat").unwrap();
+
+        // Still a valid signature, but not the one the seed produces.
+        let mut reseeded = vector.clone();
+        reseeded.seed += 1;
+        assert!(matches!(reseeded.check(), Err(TestVectorError::Mismatch("group_key"))));
+
+        let mut tampered = vector.clone();
+        tampered.message = hex::encode(b"kat!");
+        assert!(matches!(tampered.check(), Err(TestVectorError::Frost(_))));
+
+        let mut unknown = vector;
+        unknown.scheme = "unknown".to_string();
+        assert!(matches!(unknown.check(), Err(TestVectorError::UnknownScheme(_))));
+    }
+
+    #[test]
+    fn vectors_round_trip_through_json() {
+        let vectors = default_vectors(1).unwrap();
+        let path = std::env::temp_dir().join(format!("thesis-test-vectors-{}.json", std::process::id()));
+        write_vectors(&path, &vectors).unwrap();
+        let read = read_vectors(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read.unwrap(), vectors);
+    }
+}
@@ -0,0 +1,31 @@
+// Shared setup for the unit tests: small committees and seeded randomness, so
+// a failing test fails the same way on every run.
+use frost_ed25519::Signature;
+use rand_chacha::ChaCha20Rng;
+
+use crate::frost::{self, FrostPackage, FrostSettings};
+use crate::hash::HashFunction;
+use crate::params::Params;
+use crate::rng::{RngProvider, SeededRngProvider};
+
+pub(crate) fn rng(seed: u64) -> ChaCha20Rng {
+    SeededRngProvider::new(seed).rng()
+}
+
+pub(crate) fn settings(system_size: usize, threshold: usize) -> FrostSettings {
+    FrostSettings::new(Params::new(system_size, threshold).unwrap(), HashFunction::default()).unwrap()
+}
+
+// A dealt 5-of-7 committee.
+pub(crate) fn committee(seed: u64) -> (FrostSettings, FrostPackage) {
+    let settings = settings(7, 5);
+    let package = frost::setup(&settings, &mut rng(seed)).unwrap();
+    (settings, package)
+}
+
+// One full session with the first t signers.
+pub(crate) fn sign(settings: &FrostSettings, package: &FrostPackage, message: &[u8], rng: &mut ChaCha20Rng) -> Signature {
+    let round1 = frost::vote_commitments(settings, package, rng).unwrap();
+    let round2 = frost::sign_message(settings, package, &round1, message).unwrap();
+    frost_ed25519::aggregate(round2.signing_package(), round2.signature_shares(), package.public()).unwrap()
+}
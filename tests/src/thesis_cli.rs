@@ -0,0 +1,454 @@
+use clap::{Parser, Subcommand};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::process;
+use frost_ed25519::Ed25519Sha512;
+use thesis::artifacts::{ArtifactKind, ArtifactManager};
+use thesis::attest;
+use thesis::asserts::{self, CheckReport, NonceTracker};
+use thesis::fixtures::{FixtureCache, FixtureSource, Keygen};
+use thesis::frost::{self, FrostPackage, FrostSettings};
+use thesis::generate;
+use thesis::hash::HashFunction;
+use thesis::keyfile::{self, KeyFileError};
+use thesis::keystore::{self, KdfParams};
+use thesis::params::Params;
+use thesis::rng::{RngProvider, SeededRngProvider, ThreadRngProvider};
+use thesis::sigfile::{SigFormat, SignatureReader, SignatureWriter};
+use thesis::transcript;
+use thesis::verify::{self, Messages, VerifyError, VerifyInputs};
+use tracing_subscriber::EnvFilter;
+
+// Every command signs and verifies the payload transcript of `--message`, the
+// same bytes the benchmarks sign.
+#[derive(Parser)]
+#[command(name = "thesis-cli", about = "FROST key generation, signing and verification")]
+struct Cli {
+    /// Seed a ChaCha20 RNG instead of using OS randomness, for reproducible
+    /// keys and signatures
+    #[arg(long, global = true, env = "THESIS_SEED")]
+    seed: Option<u64>,
+    /// Re-check the outputs of sign and generate (signatures verify, signers
+    /// are committee members, no nonce reuse) and fail if any check fails
+    #[arg(long, global = true)]
+    selfcheck: bool,
+    /// Encrypt shares written by keygen, and decrypt shares read by sign and
+    /// generate, with this passphrase
+    #[arg(long, global = true, env = "THESIS_PASSPHRASE", hide_env_values = true)]
+    passphrase: Option<String>,
+    /// Log protocol events (sessions, commitments, shares, aggregation) as
+    /// JSON lines; the level is taken from RUST_LOG and defaults to info
+    #[arg(long, global = true)]
+    log_json: bool,
+    /// Register the key files, signature files and fixtures written by
+    /// keygen, sign and generate in the artifact manifest of this directory;
+    /// they must be inside it
+    #[arg(long, global = true, env = "THESIS_ARTIFACTS")]
+    artifacts: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate shares with a trusted dealer and write them to a key directory
+    Keygen {
+        #[arg(long)]
+        n: usize,
+        #[arg(long)]
+        t: usize,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Sign a message with the first t shares of a key directory
+    Sign {
+        #[arg(long)]
+        message: String,
+        #[arg(long)]
+        shares: PathBuf,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Generate many signatures, spot-checking a sample inline and batch
+    /// verifying all of them at the end
+    Generate {
+        /// Key directory to sign with; without it, fresh keys are dealt for --n/--t
+        #[arg(long)]
+        shares: Option<PathBuf>,
+        #[arg(long, env = "THESIS_N", required_unless_present = "shares")]
+        n: Option<usize>,
+        #[arg(long, env = "THESIS_T", required_unless_present = "shares")]
+        t: Option<usize>,
+        #[arg(long, env = "THESIS_COUNT", default_value_t = 100)]
+        count: usize,
+        #[arg(long, env = "THESIS_MESSAGE", default_value = "Hello, world!")]
+        message: String,
+        /// Fraction of signatures verified individually during generation
+        #[arg(long, default_value_t = 0.01)]
+        sample_rate: f64,
+        #[arg(long)]
+        out: PathBuf,
+        /// Signature file format: binary, hex or json
+        #[arg(long, default_value = "binary")]
+        format: SigFormat,
+        /// Cache the keys dealt for --n/--t in this directory and reuse them
+        /// on later runs
+        #[arg(long, env = "THESIS_FIXTURES", conflicts_with = "shares")]
+        fixtures: Option<PathBuf>,
+        /// Seed the cached keys are generated from
+        #[arg(long, default_value_t = 0, requires = "fixtures")]
+        fixture_seed: u64,
+        /// Generate the cached keys again even if a fixture exists
+        #[arg(long, requires = "fixtures")]
+        regenerate: bool,
+    },
+    /// Verify any artifact of the suite: a signature file, a session receipt,
+    /// an artifact manifest or a test vector file, detected from its contents
+    Verify {
+        artifact: PathBuf,
+        /// Payload the signatures are over; needed for signature files and to
+        /// check a receipt's session
+        #[arg(long)]
+        message: Option<String>,
+        /// Public key package (public.json); same as --message
+        #[arg(long)]
+        pubkey: Option<PathBuf>,
+        /// Signature i is over the i-th indexed message, as written by generate
+        #[arg(long)]
+        indexed: bool,
+        /// Re-hash the files listed in a manifest
+        #[arg(long)]
+        check_files: bool,
+        /// Key a manifest must be signed with, as hex or a file holding it;
+        /// required for manifests
+        #[arg(long)]
+        manifest_key: Option<String>,
+    },
+    /// Stream a signature file and verify every signature, printing a summary
+    /// with throughput
+    VerifyFile {
+        file: PathBuf,
+        /// Public key package (public.json or a key directory)
+        #[arg(long)]
+        pubkey: PathBuf,
+        /// Payload every signature is over
+        #[arg(long, required_unless_present = "messages")]
+        message: Option<String>,
+        /// File with one hex-encoded payload per line, one per signature
+        #[arg(long, conflicts_with_all = ["message", "indexed"])]
+        messages: Option<PathBuf>,
+        /// Signature i is over the i-th indexed message, as written by generate
+        #[arg(long, requires = "message")]
+        indexed: bool,
+        #[arg(long, default_value_t = 1)]
+        threads: usize,
+        /// Write the full report, with the result of every signature, here
+        #[arg(long)]
+        report: Option<PathBuf>,
+    },
+    /// Work with signature files
+    Sigfile {
+        #[command(subcommand)]
+        command: SigfileCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum SigfileCommand {
+    /// Print the header, record count and sizes of a signature file, and
+    /// verify a sample of its signatures when --pubkey and --message are given
+    Inspect {
+        file: PathBuf,
+        /// Public key package (public.json or a key directory)
+        #[arg(long, requires = "message")]
+        pubkey: Option<PathBuf>,
+        /// Payload every signature is over
+        #[arg(long, requires = "pubkey")]
+        message: Option<String>,
+        /// Signature i is over the i-th indexed message, as written by generate
+        #[arg(long, requires = "message")]
+        indexed: bool,
+        /// Number of signatures to verify, spread evenly over the file
+        #[arg(long, default_value_t = 16)]
+        sample: usize,
+    },
+}
+
+fn read_keys(dir: &Path, passphrase: Option<&str>) -> Result<FrostPackage, KeyFileError> {
+    Ok(match passphrase {
+        Some(passphrase) => keystore::read_keystore(dir, passphrase)?,
+        None => keyfile::read_keys(dir)?,
+    })
+}
+
+// Opens the manifest for each registration, since the fixture cache writes
+// to the same one.
+fn register(artifacts: Option<&Path>, paths: &[PathBuf], kind: ArtifactKind) -> Result<(), KeyFileError> {
+    if let Some(root) = artifacts {
+        let mut manager = ArtifactManager::open(root)?;
+        for path in paths {
+            manager.register(path, kind)?;
+        }
+    }
+    Ok(())
+}
+
+fn run<P: RngProvider>(
+    command: Command,
+    selfcheck: bool,
+    passphrase: Option<&str>,
+    artifacts: Option<&Path>,
+    provider: &mut P,
+) -> Result<CheckReport, KeyFileError> {
+    let mut rng = provider.rng();
+    let mut checks = CheckReport::default();
+    match command {
+        Command::Keygen { n, t, out } => {
+            let settings = FrostSettings::new(Params::new(n, t)?, HashFunction::default())?;
+            let packages = frost::setup(&settings, &mut rng)?;
+            let written = match passphrase {
+                Some(passphrase) => {
+                    keystore::write_keystore(&out, &packages, passphrase, KdfParams::default(), &mut rng)?
+                }
+                None => keyfile::write_keys(&out, &packages)?,
+            };
+            register(artifacts, &written, ArtifactKind::KeyStore)?;
+            tracing::info!(n, t, out = %out.display(), "wrote key shares");
+        }
+        Command::Sign { message, shares, out } => {
+            let packages = read_keys(&shares, passphrase)?;
+            let settings = keyfile::settings_for(&packages, HashFunction::default())?;
+            let message = transcript::payload_transcript(settings.hash(), message.as_bytes());
+            let _session = tracing::info_span!("session", signers = settings.threshold()).entered();
+            let round1 = frost::vote_commitments(&settings, &packages, &mut rng)?;
+            let round2 = frost::sign_message(&settings, &packages, &round1, &message)?;
+            let signature =
+                frost_ed25519::aggregate(round2.signing_package(), round2.signature_shares(), packages.public())?;
+            keyfile::write_signature(&out, &signature)?;
+            register(artifacts, std::slice::from_ref(&out), ArtifactKind::SignatureShard)?;
+            tracing::info!(out = %out.display(), "wrote signature");
+
+            if selfcheck {
+                checks.record("nonces fresh", NonceTracker::new().observe(&round1));
+                checks.record(
+                    "signers in committee",
+                    asserts::signers_in_committee(
+                        round2.signature_shares().keys(),
+                        packages.public(),
+                        settings.threshold() as usize,
+                    ),
+                );
+                checks.record(
+                    "signature verifies",
+                    asserts::signature_verifies(packages.public(), &message, &keyfile::read_signature(&out)?),
+                );
+            }
+        }
+        Command::Generate {
+            shares,
+            n,
+            t,
+            count,
+            message,
+            sample_rate,
+            out,
+            format,
+            fixtures,
+            fixture_seed,
+            regenerate,
+        } => {
+            let packages = match shares {
+                Some(shares) => read_keys(&shares, passphrase)?,
+                None => {
+                    let params = Params::new(n.expect("required by clap"), t.expect("required by clap"))?;
+                    let settings = FrostSettings::new(params, HashFunction::default())?;
+                    match fixtures {
+                        Some(dir) => {
+                            let mut cache = FixtureCache::new(dir).with_regenerate(regenerate);
+                            if let Some(root) = artifacts {
+                                cache = cache.with_artifacts(root);
+                            }
+                            let (packages, source) = cache.load(Keygen::Dealer, &settings, fixture_seed)?;
+                            if source == FixtureSource::Generated {
+                                let path = cache.path::<Ed25519Sha512>(Keygen::Dealer, &settings, fixture_seed);
+                                tracing::info!(path = %path.display(), "cached keys");
+                            }
+                            packages
+                        }
+                        None => frost::setup(&settings, &mut rng)?,
+                    }
+                }
+            };
+            let settings = keyfile::settings_for(&packages, HashFunction::default())?;
+            let (signatures, report) =
+                generate::generate_signatures(&settings, &packages, message.as_bytes(), count, sample_rate, &mut rng)?;
+            let mut writer = SignatureWriter::new(BufWriter::new(File::create(&out)?), format)?;
+            for signature in &signatures {
+                writer.write_signature(signature)?;
+            }
+            writer.finish()?;
+            register(artifacts, std::slice::from_ref(&out), ArtifactKind::SignatureShard)?;
+            tracing::info!(
+                count = report.count,
+                sampled = report.sampled,
+                generation_s = report.generation.as_secs_f64(),
+                total_s = report.generation_and_verification().as_secs_f64(),
+                spot_verification_s = report.spot_verification.as_secs_f64(),
+                batch_verification_s = report.batch_verification.as_secs_f64(),
+                "generated signatures"
+            );
+
+            if selfcheck {
+                checks.record(
+                    "spot-check fraction",
+                    asserts::within_bounds("sampled signatures", report.sampled as f64, 0.0, count as f64),
+                );
+                let file = SignatureReader::new(BufReader::new(File::open(&out)?))?;
+                let mut read = 0;
+                for (index, signature) in file.signatures().enumerate() {
+                    let message = generate::indexed_message(&settings, message.as_bytes(), index as u64);
+                    checks.record(
+                        &format!("signature {index} verifies"),
+                        asserts::signature_verifies(packages.public(), &message, &signature?),
+                    );
+                    read += 1;
+                }
+                checks.record(
+                    "signature count",
+                    asserts::within_bounds("signatures in file", read as f64, count as f64, count as f64),
+                );
+            }
+        }
+        Command::Verify {
+            artifact,
+            message,
+            pubkey,
+            indexed,
+            check_files,
+            manifest_key,
+        } => {
+            let public = pubkey.map(keyfile::read_public).transpose()?;
+            let manifest_key = manifest_key
+                .map(|key| attest::load_trusted_key(&key).map_err(VerifyError::from))
+                .transpose()?;
+            let inputs = VerifyInputs {
+                public: public.as_ref(),
+                message: message.as_deref().map(str::as_bytes),
+                hash: HashFunction::default(),
+                indexed,
+                check_files,
+                manifest_key: manifest_key.as_ref(),
+            };
+            let outcome = verify::verify_artifact(&artifact, &inputs)?;
+            println!("{}", serde_json::to_string_pretty(&outcome)?);
+            if !outcome.passed() {
+                process::exit(1);
+            }
+        }
+        Command::VerifyFile {
+            file,
+            pubkey,
+            message,
+            messages,
+            indexed,
+            threads,
+            report,
+        } => {
+            let public = keyfile::read_public(pubkey)?;
+            let payloads = match messages {
+                Some(path) => verify::read_message_list(BufReader::new(File::open(path)?))?,
+                None => Vec::new(),
+            };
+            let messages = match message.as_deref().map(str::as_bytes) {
+                Some(payload) if indexed => Messages::Indexed(payload),
+                Some(payload) => Messages::Same(payload),
+                None => Messages::List(&payloads),
+            };
+            let outcome = verify::verify_file(
+                BufReader::new(File::open(&file)?),
+                &public,
+                HashFunction::default(),
+                &messages,
+                threads,
+            )?;
+            if let Some(path) = report {
+                serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), &outcome)?;
+            }
+            tracing::info!(
+                checked = outcome.checked,
+                failed = outcome.failed,
+                elapsed_s = outcome.elapsed_s,
+                signatures_per_second = outcome.signatures_per_second,
+                "verified signature file"
+            );
+            for entry in outcome.entries.iter().filter(|entry| entry.error.is_some()) {
+                tracing::warn!(index = entry.index, error = entry.error.as_deref(), "signature failed");
+            }
+            if !outcome.passed() {
+                process::exit(1);
+            }
+        }
+        Command::Sigfile {
+            command:
+                SigfileCommand::Inspect {
+                    file,
+                    pubkey,
+                    message,
+                    indexed,
+                    sample,
+                },
+        } => {
+            let public = pubkey.map(keyfile::read_public).transpose()?;
+            let messages = match message.as_deref().map(str::as_bytes) {
+                Some(payload) if indexed => Some(Messages::Indexed(payload)),
+                Some(payload) => Some(Messages::Same(payload)),
+                None => None,
+            };
+            let check = public.as_ref().zip(messages.as_ref());
+            let report = verify::inspect_file(&file, check, HashFunction::default(), sample)?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            if !report.passed() {
+                process::exit(1);
+            }
+        }
+    }
+    Ok(checks)
+}
+
+fn init_logging(json: bool) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    if json {
+        tracing_subscriber::fmt().json().with_env_filter(filter).init();
+    } else {
+        tracing_subscriber::fmt().with_env_filter(filter).with_target(false).init();
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    init_logging(cli.log_json);
+    let passphrase = cli.passphrase.as_deref();
+    let artifacts = cli.artifacts.as_deref();
+    let result = match cli.seed {
+        Some(seed) => run(cli.command, cli.selfcheck, passphrase, artifacts, &mut SeededRngProvider::new(seed)),
+        None => run(cli.command, cli.selfcheck, passphrase, artifacts, &mut ThreadRngProvider),
+    };
+    match result {
+        Ok(checks) if checks.passed() => {
+            if cli.selfcheck {
+                tracing::info!(checks = checks.results.len(), "selfcheck passed");
+            }
+        }
+        Ok(checks) => {
+            for (name, failure) in checks.failures() {
+                tracing::error!(check = name, %failure, "selfcheck failed");
+            }
+            process::exit(1);
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "command failed");
+            process::exit(1);
+        }
+    }
+}
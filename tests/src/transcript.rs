@@ -0,0 +1,175 @@
+use std::io::{self, Read};
+
+use crate::hash::{HashFunction, Hasher};
+
+// Merlin-style transcript: structured inputs are absorbed as labelled,
+// length-prefixed fields and the final digest is the exact byte string every
+// scheme signs. Building the message this way (rather than each bench
+// concatenating bytes on its own) keeps multisig, FROST and ROAST comparisons
+// over byte-identical inputs.
+pub struct Transcript {
+    hash: HashFunction,
+    hasher: Hasher,
+}
+
+impl Transcript {
+    pub fn new(protocol_label: &[u8]) -> Self {
+        Transcript::with_hash(HashFunction::default(), protocol_label)
+    }
+
+    pub fn with_hash(hash: HashFunction, protocol_label: &[u8]) -> Self {
+        let mut hasher = hash.hasher();
+        hasher.update_framed(b"thesis-transcript-v1");
+        hasher.update_framed(protocol_label);
+        Transcript { hash, hasher }
+    }
+
+    pub fn hash_function(&self) -> HashFunction {
+        self.hash
+    }
+
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) -> &mut Self {
+        self.hasher.update_framed(label);
+        self.hasher.update_framed(message);
+        self
+    }
+
+    pub fn append_u64(&mut self, label: &[u8], value: u64) -> &mut Self {
+        self.append_message(label, &value.to_le_bytes())
+    }
+
+    // Consumes the transcript and returns the bytes to be signed.
+    pub fn signing_bytes(self) -> Vec<u8> {
+        self.hasher.finalize()
+    }
+}
+
+// The transcript used by the benchmarks and examples for a plain payload.
+pub fn payload_transcript(hash: HashFunction, payload: &[u8]) -> Vec<u8> {
+    let mut transcript = Transcript::with_hash(hash, b"payload");
+    transcript.append_message(b"payload", payload);
+    transcript.signing_bytes()
+}
+
+// Chunk size for `reader_digest`; large enough that the per-call overhead of
+// the hashers does not show up in the large-message benchmarks.
+const READ_CHUNK: usize = 64 * 1024;
+
+// Hashes everything `reader` yields without holding it in memory. Returns the
+// digest and the number of bytes read.
+pub fn reader_digest<R: Read>(hash: HashFunction, mut reader: R) -> io::Result<(Vec<u8>, u64)> {
+    let mut hasher = hash.hasher();
+    let mut buffer = vec![0u8; READ_CHUNK];
+    let mut length = 0u64;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.update(&buffer[..read]);
+        length += read as u64;
+    }
+    Ok((hasher.finalize(), length))
+}
+
+// The bytes signed for a prehashed message. Its own protocol label keeps a
+// digest from ever being signed as if it were a short payload with the same
+// bytes.
+pub fn digest_transcript(hash: HashFunction, digest: &[u8]) -> Vec<u8> {
+    let mut transcript = Transcript::with_hash(hash, b"prehash");
+    transcript.append_message(b"digest", digest);
+    transcript.signing_bytes()
+}
+
+// Same as `digest_transcript` over the digest of a stream, e.g. an artifact
+// too large to load.
+pub fn reader_transcript<R: Read>(hash: HashFunction, reader: R) -> io::Result<Vec<u8>> {
+    let (digest, _) = reader_digest(hash, reader)?;
+    Ok(digest_transcript(hash, &digest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheme::{self, ThresholdScheme};
+    use crate::testing;
+
+    #[test]
+    fn fields_are_framed() {
+        let mut split_late = Transcript::new(b"test");
+        split_late.append_message(b"a", b"bc");
+        let mut split_early = Transcript::new(b"test");
+        split_early.append_message(b"ab", b"c");
+        assert_ne!(split_late.signing_bytes(), split_early.signing_bytes());
+    }
+
+    #[test]
+    fn field_boundaries_do_not_collide() {
+        let fields = |first: &[u8], second: &[u8]| {
+            let mut transcript = Transcript::new(b"test");
+            transcript.append_message(b"first", first).append_message(b"second", second);
+            transcript.signing_bytes()
+        };
+        assert_ne!(fields(b"ab", b"c"), fields(b"a", b"bc"));
+        assert_ne!(fields(b"abc", b""), fields(b"", b"abc"));
+        for hash in HashFunction::ALL {
+            assert_ne!(
+                crate::hash::envelope_digest(hash, b"ab", b"c"),
+                crate::hash::envelope_digest(hash, b"a", b"bc")
+            );
+        }
+    }
+
+    #[test]
+    fn payload_transcript_depends_on_hash_and_label() {
+        for hash in HashFunction::ALL {
+            let bytes = payload_transcript(hash, b"payload");
+            assert_eq!(bytes, payload_transcript(hash, b"payload"));
+            assert_eq!(bytes.len(), hash.output_len());
+            // A digest is never signed as if it were a payload.
+            assert_ne!(digest_transcript(hash, b"payload"), bytes);
+        }
+        assert_ne!(
+            payload_transcript(HashFunction::Sha256, b"payload"),
+            payload_transcript(HashFunction::Blake3, b"payload")
+        );
+    }
+
+    #[test]
+    fn reader_digest_matches_in_memory_digest() {
+        let data: Vec<u8> = (0..3 * READ_CHUNK + 17).map(|index| index as u8).collect();
+        for hash in HashFunction::ALL {
+            let (digest, length) = reader_digest(hash, data.as_slice()).unwrap();
+            assert_eq!(length, data.len() as u64);
+            assert_eq!(digest, hash.digest(&data));
+            assert_eq!(reader_transcript(hash, data.as_slice()).unwrap(), digest_transcript(hash, &digest));
+        }
+    }
+
+    // Every scheme verifies exactly the transcript bytes: a signature over the
+    // transcript of a payload must not verify for the payload itself.
+    fn verifies_transcript<S: ThresholdScheme>(settings: &S::Settings) -> Vec<u8> {
+        let mut rng = testing::rng(1);
+        let transcript = payload_transcript(crate::Settings::hash_function(settings), b"payload");
+        let keys = S::setup(settings, &mut rng).unwrap();
+        let signature = scheme::sign_with_keys::<S, _>(settings, &keys, &transcript, &mut rng).unwrap();
+        S::verify(settings, &keys, &transcript, &signature).unwrap();
+        assert!(S::verify(settings, &keys, b"payload", &signature).is_err());
+        transcript
+    }
+
+    #[test]
+    fn all_schemes_verify_the_same_bytes() {
+        let settings = testing::settings(7, 5);
+        let frost = verifies_transcript::<scheme::Frost>(&settings);
+        assert_eq!(verifies_transcript::<scheme::FrostDkg>(&settings), frost);
+        assert_eq!(verifies_transcript::<scheme::Multisig>(&settings), frost);
+        #[cfg(feature = "bls")]
+        assert_eq!(
+            verifies_transcript::<scheme::Bls>(&crate::bls::BlsSettings::new(settings.params().unwrap())),
+            frost
+        );
+    }
+}
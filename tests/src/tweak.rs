@@ -0,0 +1,173 @@
+// Per-message additive key tweaks: the committee signs under
+// Y' = Y + t * G, where the scalar t is derived from the payload. Adding t to
+// every signing share adds t to the group secret, because the Lagrange
+// coefficients of any signing set sum to one. This is the ed25519 analogue of
+// Taproot-style key tweaking; the arithmetic goes through curve25519-dalek on
+// the serialized frost types.
+use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use frost_ed25519::keys::{KeyPackage, PublicKeyPackage, SigningShare, VerifyingShare};
+use frost_ed25519::{self as frost, Signature, VerifyingKey};
+use old_rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha512};
+
+use crate::frost::{FrostPackage, FrostSettings};
+
+// Hook for deriving the tweak of one message. Signers and verifiers must use
+// the same implementation.
+pub trait MessageTweak {
+    fn derive(&self, group_key: &VerifyingKey, message: &[u8]) -> Result<Scalar, frost::Error>;
+}
+
+// t = SHA-512(label || Y || message) reduced mod l, so the tweak commits to
+// both the group key and the payload.
+pub struct PayloadTweak {
+    pub label: &'static [u8],
+}
+
+impl Default for PayloadTweak {
+    fn default() -> Self {
+        PayloadTweak { label: b"thesis-payload-tweak" }
+    }
+}
+
+impl MessageTweak for PayloadTweak {
+    fn derive(&self, group_key: &VerifyingKey, message: &[u8]) -> Result<Scalar, frost::Error> {
+        let hash = Sha512::new()
+            .chain_update(self.label)
+            .chain_update(group_key.serialize()?)
+            .chain_update(message);
+        Ok(Scalar::from_hash(hash))
+    }
+}
+
+fn point(bytes: &[u8]) -> Result<EdwardsPoint, frost::Error> {
+    let bytes: [u8; 32] = bytes.try_into().map_err(|_| frost::Error::MalformedVerifyingKey)?;
+    CompressedEdwardsY(bytes).decompress().ok_or(frost::Error::MalformedVerifyingKey)
+}
+
+fn tweak_point(bytes: &[u8], tweak: &Scalar) -> Result<Vec<u8>, frost::Error> {
+    Ok((point(bytes)? + ED25519_BASEPOINT_TABLE * tweak).compress().to_bytes().to_vec())
+}
+
+pub fn tweak_verifying_key(key: &VerifyingKey, tweak: &Scalar) -> Result<VerifyingKey, frost::Error> {
+    VerifyingKey::deserialize(&tweak_point(&key.serialize()?, tweak)?)
+}
+
+fn tweak_signing_share(share: &SigningShare, tweak: &Scalar) -> Result<SigningShare, frost::Error> {
+    let bytes: [u8; 32] = share
+        .serialize()
+        .try_into()
+        .map_err(|_| frost::Error::MalformedSigningKey)?;
+    let scalar = Option::<Scalar>::from(Scalar::from_canonical_bytes(bytes)).ok_or(frost::Error::MalformedSigningKey)?;
+    SigningShare::deserialize(&(scalar + tweak).to_bytes())
+}
+
+// Key material for signing one tweaked message. The VSS commitment is
+// dropped, since it commits to the untweaked polynomial.
+pub fn tweak_package(packages: &FrostPackage, tweak: &Scalar) -> Result<FrostPackage, frost::Error> {
+    let verifying_key = tweak_verifying_key(packages.public().verifying_key(), tweak)?;
+    let verifying_shares = packages
+        .public()
+        .verifying_shares()
+        .iter()
+        .map(|(id, share)| Ok((*id, VerifyingShare::deserialize(&tweak_point(&share.serialize()?, tweak)?)?)))
+        .collect::<Result<_, frost::Error>>()?;
+    let secret = packages
+        .secret()
+        .iter()
+        .map(|(id, key_package)| {
+            let key_package = KeyPackage::new(
+                *id,
+                tweak_signing_share(key_package.signing_share(), tweak)?,
+                VerifyingShare::deserialize(&tweak_point(&key_package.verifying_share().serialize()?, tweak)?)?,
+                verifying_key,
+                *key_package.min_signers(),
+            );
+            Ok((*id, key_package))
+        })
+        .collect::<Result<_, frost::Error>>()?;
+    Ok(FrostPackage {
+        secret,
+        public: PublicKeyPackage::new(verifying_shares, verifying_key),
+        commitment: None,
+    })
+}
+
+pub fn sign_tweaked<T, RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    tweak: &T,
+    message: &[u8],
+    rng: &mut RNG,
+) -> Result<Signature, frost::Error>
+where
+    T: MessageTweak,
+    RNG: RngCore + CryptoRng,
+{
+    let scalar = tweak.derive(packages.public().verifying_key(), message)?;
+    let tweaked = tweak_package(packages, &scalar)?;
+    let round1 = crate::frost::vote_commitments(settings, &tweaked, rng)?;
+    let round2 = crate::frost::sign_message(settings, &tweaked, &round1, message)?;
+    frost::aggregate(round2.signing_package(), round2.signature_shares(), tweaked.public())
+}
+
+// Verifies against the untweaked group key by re-deriving the tweak.
+pub fn verify_tweaked<T: MessageTweak>(
+    group_key: &VerifyingKey,
+    tweak: &T,
+    message: &[u8],
+    signature: &Signature,
+) -> Result<(), frost::Error> {
+    let scalar = tweak.derive(group_key, message)?;
+    tweak_verifying_key(group_key, &scalar)?.verify(message, signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn tweaked_signature_verifies_only_under_the_tweaked_key() {
+        let (settings, package) = testing::committee(1);
+        let mut rng = testing::rng(2);
+        let group_key = *package.public().verifying_key();
+        let hook = PayloadTweak::default();
+
+        let signature = sign_tweaked(&settings, &package, &hook, b"payload", &mut rng).unwrap();
+        verify_tweaked(&group_key, &hook, b"payload", &signature).unwrap();
+        assert!(group_key.verify(b"payload", &signature).is_err());
+        // The tweak is bound to the payload, so the signature does not carry over.
+        assert!(verify_tweaked(&group_key, &hook, b"other payload", &signature).is_err());
+    }
+
+    #[test]
+    fn tweaked_package_is_consistent() {
+        let (settings, package) = testing::committee(3);
+        let scalar = PayloadTweak::default().derive(package.public().verifying_key(), b"payload").unwrap();
+        let tweaked = tweak_package(&package, &scalar).unwrap();
+
+        assert_eq!(
+            tweaked.public().verifying_key(),
+            &tweak_verifying_key(package.public().verifying_key(), &scalar).unwrap()
+        );
+        for (id, key_package) in tweaked.secret() {
+            assert_eq!(key_package.verifying_share(), &tweaked.public().verifying_shares()[id]);
+        }
+        let signature = testing::sign(&settings, &tweaked, b"payload", &mut testing::rng(4));
+        tweaked.public().verifying_key().verify(b"payload", &signature).unwrap();
+    }
+
+    #[test]
+    fn tweak_depends_on_label_and_group_key() {
+        let (_, first) = testing::committee(5);
+        let (_, second) = testing::committee(6);
+        let hook = PayloadTweak::default();
+        let scalar = hook.derive(first.public().verifying_key(), b"payload").unwrap();
+        assert_ne!(scalar, hook.derive(second.public().verifying_key(), b"payload").unwrap());
+        let relabelled = PayloadTweak { label: b"another-label" };
+        assert_ne!(scalar, relabelled.derive(first.public().verifying_key(), b"payload").unwrap());
+    }
+}
@@ -0,0 +1,445 @@
+use frost_ed25519::keys::PublicKeyPackage;
+use frost_ed25519::{Signature, VerifyingKey};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::thread;
+use std::time::Instant;
+
+use crate::attest::{AttestError, SignedManifest};
+use crate::generate;
+use crate::hash::HashFunction;
+use crate::receipt::SignedReceipt;
+use crate::sigfile::{self, SigFileError, SigFormat, SignatureReader};
+use crate::test_vectors::TestVector;
+use crate::transcript;
+
+// Errors that stop an artifact from being checked at all. A signature that
+// fails to verify is not an error; it is reported in the `VerifyOutcome`.
+#[derive(Debug)]
+pub enum VerifyError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    SigFile(SigFileError),
+    Hex(hex::FromHexError),
+    Attest(AttestError),
+    UnknownArtifact,
+    MissingInput(&'static str),
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::Io(e) => write!(f, "io error: {e}"),
+            VerifyError::Json(e) => write!(f, "json error: {e}"),
+            VerifyError::SigFile(e) => write!(f, "{e}"),
+            VerifyError::Hex(e) => write!(f, "message list: {e}"),
+            VerifyError::Attest(e) => write!(f, "manifest key: {e}"),
+            VerifyError::UnknownArtifact => {
+                write!(f, "not a signature file, session receipt, artifact manifest or test vector file")
+            }
+            VerifyError::MissingInput(input) => write!(f, "verifying this artifact needs {input}"),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+impl From<io::Error> for VerifyError {
+    fn from(e: io::Error) -> Self {
+        VerifyError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for VerifyError {
+    fn from(e: serde_json::Error) -> Self {
+        VerifyError::Json(e)
+    }
+}
+
+impl From<hex::FromHexError> for VerifyError {
+    fn from(e: hex::FromHexError) -> Self {
+        VerifyError::Hex(e)
+    }
+}
+
+impl From<AttestError> for VerifyError {
+    fn from(e: AttestError) -> Self {
+        VerifyError::Attest(e)
+    }
+}
+
+impl From<SigFileError> for VerifyError {
+    fn from(e: SigFileError) -> Self {
+        VerifyError::SigFile(e)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectedKind {
+    SignatureFile(SigFormat),
+    SessionReceipt,
+    ArtifactManifest,
+    TestVectors,
+}
+
+// Signature files are recognised by their magic; the JSON artifacts by the
+// top-level fields of their signed wrapper, or of the first test vector.
+pub fn detect(bytes: &[u8]) -> Result<DetectedKind, VerifyError> {
+    if bytes.starts_with(sigfile::MAGIC) {
+        let reader = SignatureReader::new(bytes)?;
+        return Ok(DetectedKind::SignatureFile(reader.format()));
+    }
+    let value: serde_json::Value = serde_json::from_slice(bytes).map_err(|_| VerifyError::UnknownArtifact)?;
+    let has = |field: &str| value.get(field).is_some();
+    if has("receipt") && has("coordinator_signature") {
+        Ok(DetectedKind::SessionReceipt)
+    } else if has("manifest") && has("signature") {
+        Ok(DetectedKind::ArtifactManifest)
+    } else if value
+        .as_array()
+        .and_then(|vectors| vectors.first())
+        .is_some_and(|vector| vector.get("seed").is_some() && vector.get("group_key").is_some())
+    {
+        Ok(DetectedKind::TestVectors)
+    } else {
+        Err(VerifyError::UnknownArtifact)
+    }
+}
+
+// What a caller can supply; which inputs are required depends on the kind.
+// `message` is the raw payload and is signed as its payload transcript.
+#[derive(Clone, Copy)]
+pub struct VerifyInputs<'a> {
+    pub public: Option<&'a PublicKeyPackage>,
+    pub message: Option<&'a [u8]>,
+    pub hash: HashFunction,
+    // Signature `i` of the file is over the indexed message, as written by
+    // `thesis-cli generate`.
+    pub indexed: bool,
+    // Also re-hash the files listed in a manifest.
+    pub check_files: bool,
+    // The key a manifest has to be signed with.
+    pub manifest_key: Option<&'a ed25519_dalek::VerifyingKey>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct VerifyOutcome {
+    pub kind: DetectedKind,
+    pub checked: usize,
+    pub failures: Vec<String>,
+}
+
+impl VerifyOutcome {
+    pub fn passed(&self) -> bool {
+        self.checked > 0 && self.failures.is_empty()
+    }
+}
+
+pub fn verify_artifact<P: AsRef<Path>>(path: P, inputs: &VerifyInputs) -> Result<VerifyOutcome, VerifyError> {
+    let bytes = fs::read(path)?;
+    let kind = detect(&bytes)?;
+    let mut outcome = VerifyOutcome {
+        kind,
+        checked: 0,
+        failures: Vec::new(),
+    };
+    match kind {
+        DetectedKind::SignatureFile(_) => {
+            let public = inputs.public.ok_or(VerifyError::MissingInput("a public key package"))?;
+            let payload = inputs.message.ok_or(VerifyError::MissingInput("a message"))?;
+            let messages = if inputs.indexed {
+                Messages::Indexed(payload)
+            } else {
+                Messages::Same(payload)
+            };
+            let report = verify_file(bytes.as_slice(), public, inputs.hash, &messages, 1)?;
+            outcome.checked = report.checked;
+            outcome.failures = report
+                .entries
+                .into_iter()
+                .filter_map(|entry| entry.error.map(|e| format!("signature {}: {e}", entry.index)))
+                .collect();
+        }
+        DetectedKind::SessionReceipt => {
+            let receipt: SignedReceipt = serde_json::from_slice(&bytes)?;
+            // Without the committee and message only the coordinator signature
+            // can be checked.
+            let message = inputs
+                .message
+                .map(|payload| transcript::payload_transcript(receipt.receipt.hash, payload));
+            let session_inputs = inputs.public.zip(message.as_deref());
+            if let Err(e) = receipt.verify(None, session_inputs) {
+                outcome.failures.push(format!("receipt {}: {e}", receipt.receipt.session));
+            }
+            outcome.checked = 1;
+        }
+        DetectedKind::ArtifactManifest => {
+            let manifest: SignedManifest = serde_json::from_slice(&bytes)?;
+            let trusted = inputs.manifest_key.ok_or(VerifyError::MissingInput("a trusted manifest key"))?;
+            if let Err(e) = manifest.verify(trusted, inputs.check_files) {
+                outcome.failures.push(format!("manifest {}: {e}", manifest.manifest.run_id));
+            }
+            outcome.checked = 1;
+        }
+        DetectedKind::TestVectors => {
+            let vectors: Vec<TestVector> = serde_json::from_slice(&bytes)?;
+            for (index, vector) in vectors.iter().enumerate() {
+                if let Err(e) = vector.check() {
+                    outcome.failures.push(format!("vector {index} ({}): {e}", vector.scheme));
+                }
+                outcome.checked += 1;
+            }
+        }
+    }
+    Ok(outcome)
+}
+
+// The payloads the signatures of a file are over; each is signed as its
+// payload transcript.
+pub enum Messages<'a> {
+    // Every signature is over the same payload.
+    Same(&'a [u8]),
+    // Signature `i` is over the i-th indexed payload, as written by
+    // `thesis-cli generate`.
+    Indexed(&'a [u8]),
+    // Signature `i` is over the i-th payload of the list.
+    List(&'a [Vec<u8>]),
+}
+
+impl Messages<'_> {
+    fn message(&self, hash: HashFunction, index: usize) -> Option<Vec<u8>> {
+        match self {
+            Messages::Same(payload) => Some(transcript::payload_transcript(hash, payload)),
+            Messages::Indexed(payload) => Some(generate::indexed_payload(hash, payload, index as u64)),
+            Messages::List(payloads) => payloads
+                .get(index)
+                .map(|payload| transcript::payload_transcript(hash, payload)),
+        }
+    }
+}
+
+// One hex-encoded payload per line; blank lines are skipped.
+pub fn read_message_list<R: BufRead>(reader: R) -> Result<Vec<Vec<u8>>, VerifyError> {
+    let mut payloads = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if !line.is_empty() {
+            payloads.push(hex::decode(line)?);
+        }
+    }
+    Ok(payloads)
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct EntryResult {
+    pub index: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FileReport {
+    pub checked: usize,
+    pub failed: usize,
+    pub elapsed_s: f64,
+    pub signatures_per_second: f64,
+    pub entries: Vec<EntryResult>,
+}
+
+impl FileReport {
+    pub fn passed(&self) -> bool {
+        self.checked > 0 && self.failed == 0
+    }
+}
+
+// Signatures are read and verified in batches of this many, so memory use
+// does not grow with the file.
+const BATCH: usize = 4096;
+
+// Streams a signature file and verifies every entry against the group key,
+// spreading each batch over `threads` threads. Records that fail to parse
+// are reported as failed entries; only an unreadable header is an error.
+pub fn verify_file<R: BufRead>(
+    reader: R,
+    public: &PublicKeyPackage,
+    hash: HashFunction,
+    messages: &Messages,
+    threads: usize,
+) -> Result<FileReport, VerifyError> {
+    let start = Instant::now();
+    let key = public.verifying_key();
+    let mut signatures = SignatureReader::new(reader)?.signatures().enumerate().peekable();
+    let mut entries = Vec::new();
+    while signatures.peek().is_some() {
+        let batch: Vec<(usize, Result<Signature, SigFileError>)> = signatures.by_ref().take(BATCH).collect();
+        let chunk_len = batch.len().div_ceil(threads.max(1));
+        let verified: Vec<EntryResult> = thread::scope(|scope| {
+            let workers: Vec<_> = batch
+                .chunks(chunk_len)
+                .map(|chunk| scope.spawn(move || verify_entries(key, hash, messages, chunk)))
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("verification thread panicked"))
+                .collect()
+        });
+        entries.extend(verified);
+    }
+
+    let elapsed = start.elapsed().as_secs_f64();
+    Ok(FileReport {
+        checked: entries.len(),
+        failed: entries.iter().filter(|entry| entry.error.is_some()).count(),
+        elapsed_s: elapsed,
+        signatures_per_second: entries.len() as f64 / elapsed.max(f64::EPSILON),
+        entries,
+    })
+}
+
+// Summary of a signature file for a quick look before a long verification
+// run: header, record count and length histogram, and the results of
+// verifying `sample` records spread evenly over the file.
+#[derive(Clone, Debug, Serialize)]
+pub struct InspectReport {
+    pub format: SigFormat,
+    pub version: u8,
+    pub file_bytes: u64,
+    pub records: usize,
+    // Record length in bytes to the number of records of that length.
+    pub record_sizes: BTreeMap<usize, usize>,
+    pub sampled: Vec<EntryResult>,
+}
+
+impl InspectReport {
+    pub fn passed(&self) -> bool {
+        self.sampled.iter().all(|entry| entry.error.is_none())
+    }
+}
+
+// Reads the file twice: once to count records, once to verify the sample.
+// Without `check` nothing is verified.
+pub fn inspect_file<P: AsRef<Path>>(
+    path: P,
+    check: Option<(&PublicKeyPackage, &Messages)>,
+    hash: HashFunction,
+    sample: usize,
+) -> Result<InspectReport, VerifyError> {
+    let path = path.as_ref();
+    let reader = SignatureReader::new(BufReader::new(File::open(path)?))?;
+    let format = reader.format();
+    let mut record_sizes = BTreeMap::new();
+    let mut records: usize = 0;
+    for record in reader {
+        *record_sizes.entry(record?.len()).or_insert(0) += 1;
+        records += 1;
+    }
+
+    let mut sampled = Vec::new();
+    if let Some((public, messages)) = check.filter(|_| sample > 0 && records > 0) {
+        let stride = records.div_ceil(sample);
+        let entries: Vec<(usize, Result<Signature, SigFileError>)> =
+            SignatureReader::new(BufReader::new(File::open(path)?))?.signatures().enumerate().step_by(stride).collect();
+        sampled = verify_entries(public.verifying_key(), hash, messages, &entries);
+    }
+
+    Ok(InspectReport {
+        format,
+        version: sigfile::VERSION,
+        file_bytes: fs::metadata(path)?.len(),
+        records,
+        record_sizes,
+        sampled,
+    })
+}
+
+fn verify_entries(
+    key: &VerifyingKey,
+    hash: HashFunction,
+    messages: &Messages,
+    entries: &[(usize, Result<Signature, SigFileError>)],
+) -> Vec<EntryResult> {
+    entries
+        .iter()
+        .map(|(index, signature)| {
+            let result = match (signature, messages.message(hash, *index)) {
+                (Err(e), _) => Err(e.to_string()),
+                (_, None) => Err("no message for this signature".to_string()),
+                (Ok(signature), Some(message)) => key.verify(&message, signature).map_err(|e| e.to_string()),
+            };
+            EntryResult {
+                index: *index,
+                error: result.err(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sigfile::SignatureWriter;
+    use crate::testing;
+
+    #[test]
+    fn generated_signature_file_verifies() {
+        let (settings, package) = testing::committee(1);
+        let (signatures, _) =
+            generate::generate_signatures(&settings, &package, b"payload", 5, 0.0, &mut testing::rng(2)).unwrap();
+        let mut file = SignatureWriter::new(Vec::new(), SigFormat::Binary).unwrap();
+        for signature in &signatures {
+            file.write_signature(signature).unwrap();
+        }
+        let bytes = file.finish().unwrap();
+
+        let report =
+            verify_file(bytes.as_slice(), package.public(), settings.hash(), &Messages::Indexed(b"payload"), 2).unwrap();
+        assert!(report.passed());
+        assert_eq!(report.checked, signatures.len());
+
+        // The entries are over indexed payloads, so none of them is a
+        // signature over the bare payload.
+        let report =
+            verify_file(bytes.as_slice(), package.public(), settings.hash(), &Messages::Same(b"payload"), 2).unwrap();
+        assert_eq!(report.failed, signatures.len());
+    }
+
+    #[test]
+    fn inspect_counts_records_and_verifies_a_sample() {
+        let (settings, package) = testing::committee(1);
+        let (signatures, _) =
+            generate::generate_signatures(&settings, &package, b"payload", 10, 0.0, &mut testing::rng(2)).unwrap();
+        let mut file = SignatureWriter::new(Vec::new(), SigFormat::Hex).unwrap();
+        for signature in &signatures {
+            file.write_signature(signature).unwrap();
+        }
+        let path = std::env::temp_dir().join(format!("thesis-inspect-{}.sig", std::process::id()));
+        fs::write(&path, file.finish().unwrap()).unwrap();
+
+        let indexed = Messages::Indexed(b"payload");
+        let report = inspect_file(&path, Some((package.public(), &indexed)), settings.hash(), 4);
+        let unchecked = inspect_file(&path, None, settings.hash(), 4);
+        let wrong = inspect_file(&path, Some((package.public(), &Messages::Same(b"payload"))), settings.hash(), 4);
+        fs::remove_file(&path).unwrap();
+
+        let report = report.unwrap();
+        assert_eq!((report.format, report.records), (SigFormat::Hex, 10));
+        assert_eq!(report.record_sizes, BTreeMap::from([(64, 10)]));
+        let sampled: Vec<usize> = report.sampled.iter().map(|entry| entry.index).collect();
+        assert_eq!(sampled, [0, 3, 6, 9]);
+        assert!(report.passed());
+        assert!(unchecked.unwrap().sampled.is_empty());
+        assert!(!wrong.unwrap().passed());
+    }
+
+    #[test]
+    fn message_list_is_hex_per_line() {
+        let payloads = read_message_list("00ff\n\n  abcd  \n".as_bytes()).unwrap();
+        assert_eq!(payloads, vec![vec![0x00, 0xff], vec![0xab, 0xcd]]);
+        assert!(read_message_list("zz\n".as_bytes()).is_err());
+    }
+}
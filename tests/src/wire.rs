@@ -0,0 +1,286 @@
+use frost_ed25519::round1::SigningCommitments;
+use frost_ed25519::round2::SignatureShare;
+use frost_ed25519::{Identifier, Signature, SigningPackage};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::frost::{FrostRound1, FrostRound2};
+use crate::hash::HashFunction;
+
+#[derive(Debug)]
+pub enum WireError {
+    Bincode(bincode::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WireError::Bincode(e) => write!(f, "bincode error: {e}"),
+            WireError::Json(e) => write!(f, "json error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
+
+impl From<bincode::Error> for WireError {
+    fn from(e: bincode::Error) -> Self {
+        WireError::Bincode(e)
+    }
+}
+
+impl From<serde_json::Error> for WireError {
+    fn from(e: serde_json::Error) -> Self {
+        WireError::Json(e)
+    }
+}
+
+// bincode with its default fixed-width little-endian configuration is the
+// canonical binary form; JSON is for logs and debugging. Maps are BTreeMaps,
+// so both encodings are deterministic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    Bincode,
+    Json,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 2] = [Encoding::Bincode, Encoding::Json];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::Bincode => "bincode",
+            Encoding::Json => "json",
+        }
+    }
+}
+
+// Signer -> coordinator, round 1.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitmentMsg {
+    pub session: u64,
+    pub signer: Identifier,
+    pub commitments: SigningCommitments,
+}
+
+// Coordinator -> signers: the chosen commitments together with the message.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonceSetMsg {
+    pub session: u64,
+    pub signing_package: SigningPackage,
+}
+
+// Signer -> coordinator, round 2.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShareMsg {
+    pub session: u64,
+    pub signer: Identifier,
+    pub share: SignatureShare,
+}
+
+// Coordinator -> everyone, after aggregation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalSignatureMsg {
+    pub session: u64,
+    pub signers: Vec<Identifier>,
+    pub signature: Signature,
+}
+
+// Coordinator -> signers, before round 1: the message to be signed is given
+// by its digest, so large artifacts never cross the wire. Signers holding
+// the artifact recompute the digest with `transcript::reader_digest` before
+// agreeing to sign `transcript::digest_transcript(hash, digest)`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignDigestMsg {
+    pub session: u64,
+    pub hash: HashFunction,
+    pub length: u64,
+    pub digest: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WireMessage {
+    Commitment(CommitmentMsg),
+    NonceSet(NonceSetMsg),
+    Share(ShareMsg),
+    FinalSignature(FinalSignatureMsg),
+    // Appended last so the bincode tags of the other variants are unchanged.
+    SignDigest(SignDigestMsg),
+}
+
+impl WireMessage {
+    pub fn encode(&self, encoding: Encoding) -> Result<Vec<u8>, WireError> {
+        Ok(match encoding {
+            Encoding::Bincode => bincode::serialize(self)?,
+            Encoding::Json => serde_json::to_vec(self)?,
+        })
+    }
+
+    pub fn decode(bytes: &[u8], encoding: Encoding) -> Result<WireMessage, WireError> {
+        Ok(match encoding {
+            Encoding::Bincode => bincode::deserialize(bytes)?,
+            Encoding::Json => serde_json::from_slice(bytes)?,
+        })
+    }
+}
+
+pub fn commitment_messages(session: u64, round1: &FrostRound1) -> Vec<WireMessage> {
+    round1
+        .commitments()
+        .iter()
+        .map(|(signer, commitments)| {
+            WireMessage::Commitment(CommitmentMsg {
+                session,
+                signer: *signer,
+                commitments: *commitments,
+            })
+        })
+        .collect()
+}
+
+pub fn nonce_set_message(session: u64, round2: &FrostRound2) -> WireMessage {
+    WireMessage::NonceSet(NonceSetMsg {
+        session,
+        signing_package: round2.signing_package().clone(),
+    })
+}
+
+pub fn share_messages(session: u64, round2: &FrostRound2) -> Vec<WireMessage> {
+    round2
+        .signature_shares()
+        .iter()
+        .map(|(signer, share)| {
+            WireMessage::Share(ShareMsg {
+                session,
+                signer: *signer,
+                share: *share,
+            })
+        })
+        .collect()
+}
+
+pub fn final_signature_message(session: u64, round2: &FrostRound2, signature: Signature) -> WireMessage {
+    WireMessage::FinalSignature(FinalSignatureMsg {
+        session,
+        signers: round2.signature_shares().keys().copied().collect(),
+        signature,
+    })
+}
+
+pub fn sign_digest_message(session: u64, hash: HashFunction, digest: &[u8], length: u64) -> WireMessage {
+    WireMessage::SignDigest(SignDigestMsg {
+        session,
+        hash,
+        length,
+        digest: digest.to_vec(),
+    })
+}
+
+// Encoded size in bytes of each message kind for one session. Commitment and
+// share sizes are per signer; the nonce set and final signature are sent once
+// per recipient.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageSizes {
+    pub encoding: Encoding,
+    pub commitment: usize,
+    pub nonce_set: usize,
+    pub share: usize,
+    pub final_signature: usize,
+}
+
+impl MessageSizes {
+    // Bytes moved through the coordinator for one session with `signers`
+    // participants.
+    pub fn session_total(&self, signers: usize) -> usize {
+        signers * (self.commitment + self.nonce_set + self.share + self.final_signature)
+    }
+}
+
+pub fn message_sizes(
+    session: u64,
+    round1: &FrostRound1,
+    round2: &FrostRound2,
+    signature: Signature,
+    encoding: Encoding,
+) -> Result<MessageSizes, WireError> {
+    let first_len = |messages: Vec<WireMessage>| -> Result<usize, WireError> {
+        messages.first().map_or(Ok(0), |message| Ok(message.encode(encoding)?.len()))
+    };
+    Ok(MessageSizes {
+        encoding,
+        commitment: first_len(commitment_messages(session, round1))?,
+        nonce_set: nonce_set_message(session, round2).encode(encoding)?.len(),
+        share: first_len(share_messages(session, round2))?,
+        final_signature: final_signature_message(session, round2, signature).encode(encoding)?.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frost;
+    use crate::testing;
+
+    fn session_messages() -> (Vec<WireMessage>, MessageSizes) {
+        let (settings, package) = testing::committee(1);
+        let mut rng = testing::rng(2);
+        let round1 = frost::vote_commitments(&settings, &package, &mut rng).unwrap();
+        let round2 = frost::sign_message(&settings, &package, &round1, b"wire").unwrap();
+        let signature =
+            frost_ed25519::aggregate(round2.signing_package(), round2.signature_shares(), package.public()).unwrap();
+
+        let mut messages = commitment_messages(7, &round1);
+        messages.push(nonce_set_message(7, &round2));
+        messages.extend(share_messages(7, &round2));
+        messages.push(final_signature_message(7, &round2, signature));
+        messages.push(sign_digest_message(7, HashFunction::default(), &[0xab; 32], 1 << 20));
+        let sizes = message_sizes(7, &round1, &round2, signature, Encoding::Bincode).unwrap();
+        (messages, sizes)
+    }
+
+    #[test]
+    fn every_message_round_trips() {
+        let (messages, _) = session_messages();
+        for encoding in Encoding::ALL {
+            for message in &messages {
+                let bytes = message.encode(encoding).unwrap();
+                assert_eq!(&WireMessage::decode(&bytes, encoding).unwrap(), message, "{}", encoding.name());
+                // Deterministic: encoding the decoded message gives the same bytes.
+                assert_eq!(WireMessage::decode(&bytes, encoding).unwrap().encode(encoding).unwrap(), bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn bincode_tags_are_stable() {
+        let (messages, _) = session_messages();
+        let tags: Vec<u32> = messages
+            .iter()
+            .map(|message| u32::from_le_bytes(message.encode(Encoding::Bincode).unwrap()[..4].try_into().unwrap()))
+            .collect();
+        let mut expected = vec![0; 5];
+        expected.push(1);
+        expected.extend([2; 5]);
+        expected.extend([3, 4]);
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn sizes_match_the_encoded_messages() {
+        let (messages, sizes) = session_messages();
+        let encoded = |index: usize| messages[index].encode(Encoding::Bincode).unwrap().len();
+        assert_eq!(sizes.commitment, encoded(0));
+        assert_eq!(sizes.nonce_set, encoded(5));
+        assert_eq!(sizes.share, encoded(6));
+        assert_eq!(sizes.final_signature, encoded(11));
+        assert_eq!(sizes.session_total(5), 5 * (encoded(0) + encoded(5) + encoded(6) + encoded(11)));
+    }
+
+    #[test]
+    fn garbage_is_rejected() {
+        assert!(matches!(WireMessage::decode(&[0xff; 3], Encoding::Bincode), Err(WireError::Bincode(_))));
+        assert!(matches!(WireMessage::decode(b"{}", Encoding::Json), Err(WireError::Json(_))));
+    }
+}
@@ -0,0 +1,149 @@
+use frost_ed25519::{self as frost, Signature};
+use old_rand::{CryptoRng, RngCore};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::frost::{FrostPackage, FrostSettings};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkloadMode {
+    SignOnly,
+    VerifyOnly,
+    Mixed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WorkloadStats {
+    pub mode: WorkloadMode,
+    pub elapsed: Duration,
+    pub signatures: usize,
+    pub verifications: usize,
+}
+
+impl WorkloadStats {
+    pub fn signatures_per_second(&self) -> f64 {
+        self.signatures as f64 / self.elapsed.as_secs_f64()
+    }
+
+    pub fn verifications_per_second(&self) -> f64 {
+        self.verifications as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+// Produces signatures for the verifier side of the mixed workload.
+pub fn produce_signatures<RNG>(
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    message: &[u8],
+    count: usize,
+    rng: &mut RNG,
+) -> Result<Vec<Signature>, frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    (0..count)
+        .map(|_| {
+            let round1 = crate::frost::vote_commitments(settings, packages, rng)?;
+            let round2 = crate::frost::sign_message(settings, packages, &round1, message)?;
+            frost::aggregate(round2.signing_package(), round2.signature_shares(), packages.public())
+        })
+        .collect()
+}
+
+// Runs full signing sessions on the calling thread and, in `Mixed` mode,
+// verifies previously produced signatures on a second thread for the same
+// duration, the way a production coordinator does both at once. Comparing
+// against the `SignOnly`/`VerifyOnly` runs shows the interference.
+pub fn run_workload<RNG>(
+    mode: WorkloadMode,
+    settings: &FrostSettings,
+    packages: &FrostPackage,
+    message: &[u8],
+    signatures: &[Signature],
+    duration: Duration,
+    rng: &mut RNG,
+) -> Result<WorkloadStats, frost::Error>
+where
+    RNG: RngCore + CryptoRng,
+{
+    let stop = AtomicBool::new(false);
+    let verifications = AtomicUsize::new(0);
+    let start = Instant::now();
+
+    let signed = thread::scope(|scope| {
+        if mode != WorkloadMode::SignOnly && !signatures.is_empty() {
+            scope.spawn(|| {
+                let verifying_key = packages.public().verifying_key();
+                for signature in signatures.iter().cycle() {
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    // Every stored signature is valid, so a failure here is a bug.
+                    verifying_key
+                        .verify(message, signature)
+                        .expect("stored signature should verify");
+                    verifications.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+
+        let mut signed = 0;
+        let result = loop {
+            if start.elapsed() >= duration {
+                break Ok(signed);
+            }
+            if mode == WorkloadMode::VerifyOnly {
+                thread::sleep(Duration::from_millis(1));
+                continue;
+            }
+            let round1 = match crate::frost::vote_commitments(settings, packages, rng) {
+                Ok(round1) => round1,
+                Err(e) => break Err(e),
+            };
+            if let Err(e) = crate::frost::sign_message(settings, packages, &round1, message)
+                .and_then(|round2| crate::frost::aggregate_verify(settings, packages, &round1, &round2, message))
+            {
+                break Err(e);
+            }
+            signed += 1;
+        };
+        stop.store(true, Ordering::Relaxed);
+        result
+    })?;
+
+    Ok(WorkloadStats {
+        mode,
+        elapsed: start.elapsed(),
+        signatures: signed,
+        verifications: verifications.load(Ordering::Relaxed),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn modes_do_only_their_own_work() {
+        let (settings, package) = testing::committee(1);
+        let mut rng = testing::rng(2);
+        let signatures = produce_signatures(&settings, &package, b"workload", 3, &mut rng).unwrap();
+        for signature in &signatures {
+            package.public().verifying_key().verify(b"workload", signature).unwrap();
+        }
+
+        let duration = Duration::from_millis(50);
+        let mut run = |mode| run_workload(mode, &settings, &package, b"workload", &signatures, duration, &mut rng);
+        let sign_only = run(WorkloadMode::SignOnly).unwrap();
+        assert!(sign_only.signatures > 0);
+        assert_eq!(sign_only.verifications, 0);
+        let verify_only = run(WorkloadMode::VerifyOnly).unwrap();
+        assert_eq!(verify_only.signatures, 0);
+        assert!(verify_only.verifications > 0);
+        let mixed = run(WorkloadMode::Mixed).unwrap();
+        assert!(mixed.signatures > 0 && mixed.verifications > 0);
+        assert!(mixed.elapsed >= duration);
+    }
+}